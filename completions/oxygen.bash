@@ -23,7 +23,7 @@ _o2c() {
 
     case "${cmd}" in
         o2c)
-            opts="-o -h -V --display-tokens --display-ast --help --version <O2_FILE>"
+            opts="-o -c -h -V --display-tokens --display-ast --emit --tab-width --entry --help --version"
             if [[ ${cur} == -* || ${COMP_CWORD} -eq 1 ]] ; then
                 COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
                 return 0
@@ -33,6 +33,18 @@ _o2c() {
                     COMPREPLY=($(compgen -f "${cur}"))
                     return 0
                     ;;
+                --emit)
+                    COMPREPLY=($(compgen -W "tokens ast ir asm obj exe" -- "${cur}"))
+                    return 0
+                    ;;
+                --tab-width)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                --entry)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 *)
                     COMPREPLY=()
                     ;;
@@ -67,16 +79,22 @@ _oxygen() {
                 cmd="oxygen"
                 ;;
             oxygen,completions)
-                cmd="oxygen__completions"
+                cmd="oxygen__subcmd__completions"
                 ;;
             oxygen,help)
-                cmd="oxygen__help"
+                cmd="oxygen__subcmd__help"
+                ;;
+            oxygen,run)
+                cmd="oxygen__subcmd__run"
+                ;;
+            oxygen__subcmd__help,completions)
+                cmd="oxygen__subcmd__help__subcmd__completions"
                 ;;
-            oxygen__help,completions)
-                cmd="oxygen__help__completions"
+            oxygen__subcmd__help,help)
+                cmd="oxygen__subcmd__help__subcmd__help"
                 ;;
-            oxygen__help,help)
-                cmd="oxygen__help__help"
+            oxygen__subcmd__help,run)
+                cmd="oxygen__subcmd__help__subcmd__run"
                 ;;
             *)
                 ;;
@@ -85,7 +103,7 @@ _oxygen() {
 
     case "${cmd}" in
         oxygen)
-            opts="-h -V --help --version completions help"
+            opts="-h -V --help --version run completions help"
             if [[ ${cur} == -* || ${COMP_CWORD} -eq 1 ]] ; then
                 COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
                 return 0
@@ -98,7 +116,7 @@ _oxygen() {
             COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
             return 0
             ;;
-        oxygen__completions)
+        oxygen__subcmd__completions)
             opts="-h --help fish bash zsh"
             if [[ ${cur} == -* || ${COMP_CWORD} -eq 2 ]] ; then
                 COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
@@ -112,8 +130,8 @@ _oxygen() {
             COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
             return 0
             ;;
-        oxygen__help)
-            opts="completions help"
+        oxygen__subcmd__help)
+            opts="run completions help"
             if [[ ${cur} == -* || ${COMP_CWORD} -eq 2 ]] ; then
                 COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
                 return 0
@@ -126,7 +144,21 @@ _oxygen() {
             COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
             return 0
             ;;
-        oxygen__help__completions)
+        oxygen__subcmd__help__subcmd__completions)
+            opts=""
+            if [[ ${cur} == -* || ${COMP_CWORD} -eq 3 ]] ; then
+                COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                return 0
+            fi
+            case "${prev}" in
+                *)
+                    COMPREPLY=()
+                    ;;
+            esac
+            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0
+            ;;
+        oxygen__subcmd__help__subcmd__help)
             opts=""
             if [[ ${cur} == -* || ${COMP_CWORD} -eq 3 ]] ; then
                 COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
@@ -140,7 +172,7 @@ _oxygen() {
             COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
             return 0
             ;;
-        oxygen__help__help)
+        oxygen__subcmd__help__subcmd__run)
             opts=""
             if [[ ${cur} == -* || ${COMP_CWORD} -eq 3 ]] ; then
                 COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
@@ -154,6 +186,24 @@ _oxygen() {
             COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
             return 0
             ;;
+        oxygen__subcmd__run)
+            opts="-h --tab-width --help"
+            if [[ ${cur} == -* || ${COMP_CWORD} -eq 2 ]] ; then
+                COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                return 0
+            fi
+            case "${prev}" in
+                --tab-width)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
+                *)
+                    COMPREPLY=()
+                    ;;
+            esac
+            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0
+            ;;
     esac
 }
 
@@ -1,38 +1,51 @@
 complete -c o2c -s o -d 'The output path for the compiled binary' -r -F
+complete -c o2c -l emit -d 'Write the given compilation stages to files next to the output' -r -f -a "tokens\t'The tokens produced by the lexer'
+ast\t'The AST produced by the parser'
+ir\t'The intermediate representation lowered from the AST'
+asm\t'The x86-64 GNU assembly'
+obj\t'The ELF64 object file'
+exe\t'The ELF64 executable'"
+complete -c o2c -l tab-width -d 'The number of columns between tab stops in diagnostics' -r
+complete -c o2c -l entry -d 'The function the compiled program starts at' -r
+complete -c o2c -s c -d 'Compile to an ELF64 object file without linking'
 complete -c o2c -l display-tokens -d 'Display the tokens generated by the compilation'
 complete -c o2c -l display-ast -d 'Display the AST generated from by the compilation'
-complete -c o2c -s h -l help -d 'Print help'
+complete -c o2c -s h -l help -d 'Print help (see more with \'--help\')'
 complete -c o2c -s V -l version -d 'Print version'
 # Print an optspec for argparse to handle cmd's options that are independent of any subcommand.
 function __fish_oxygen_global_optspecs
-	string join \n h/help V/version
+    string join \n h/help V/version
 end
 
 function __fish_oxygen_needs_command
-	# Figure out if the current invocation already has a command.
-	set -l cmd (commandline -opc)
-	set -e cmd[1]
-	argparse -s (__fish_oxygen_global_optspecs) -- $cmd 2>/dev/null
-	or return
-	if set -q argv[1]
-		# Also print the command, so this can be used to figure out what it is.
-		echo $argv[1]
-		return 1
-	end
-	return 0
+    # Figure out if the current invocation already has a command.
+    set -l cmd (commandline -opc)
+    set -e cmd[1]
+    argparse -s (__fish_oxygen_global_optspecs) -- $cmd 2>/dev/null
+    or return
+    if set -q argv[1]
+        # Also print the command, so this can be used to figure out what it is.
+        echo $argv[1]
+        return 1
+    end
+    return 0
 end
 
 function __fish_oxygen_using_subcommand
-	set -l cmd (__fish_oxygen_needs_command)
-	test -z "$cmd"
-	and return 1
-	contains -- $cmd[1] $argv
+    set -l cmd (__fish_oxygen_needs_command)
+    test -z "$cmd"
+    and return 1
+    contains -- $cmd[1] $argv
 end
 
 complete -c oxygen -n "__fish_oxygen_needs_command" -s h -l help -d 'Print help'
 complete -c oxygen -n "__fish_oxygen_needs_command" -s V -l version -d 'Print version'
+complete -c oxygen -n "__fish_oxygen_needs_command" -f -a "run" -d 'Interpret an oxygen file and exit with the return value of `main`'
 complete -c oxygen -n "__fish_oxygen_needs_command" -f -a "completions"
 complete -c oxygen -n "__fish_oxygen_needs_command" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c oxygen -n "__fish_oxygen_using_subcommand run" -l tab-width -d 'The number of columns between tab stops in diagnostics' -r
+complete -c oxygen -n "__fish_oxygen_using_subcommand run" -s h -l help -d 'Print help'
 complete -c oxygen -n "__fish_oxygen_using_subcommand completions" -s h -l help -d 'Print help'
-complete -c oxygen -n "__fish_oxygen_using_subcommand help; and not __fish_seen_subcommand_from completions help" -f -a "completions"
-complete -c oxygen -n "__fish_oxygen_using_subcommand help; and not __fish_seen_subcommand_from completions help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c oxygen -n "__fish_oxygen_using_subcommand help; and not __fish_seen_subcommand_from run completions help" -f -a "run" -d 'Interpret an oxygen file and exit with the return value of `main`'
+complete -c oxygen -n "__fish_oxygen_using_subcommand help; and not __fish_seen_subcommand_from run completions help" -f -a "completions"
+complete -c oxygen -n "__fish_oxygen_using_subcommand help; and not __fish_seen_subcommand_from run completions help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
@@ -1,9 +1,15 @@
 use clap::{CommandFactory, Parser};
 use clap_complete::aot;
-use cmdline::OxygenCommands::Completions;
-use cmdline::OxygenShells;
+use o2::cmdline;
+use o2::cmdline::OxygenCommands::{Completions, Run};
+use o2::cmdline::OxygenShells;
+use o2::diagnostic;
+use o2::error::Error;
+use o2::interpreter::Interpreter;
+use o2::parse_file;
+use o2::semantic;
 
-use std::{io, process::exit};
+use std::{io, path::Path, process::exit};
 
 fn gen_comp<G: aot::Generator>(shell_one: G, shell_two: G) {
     let mut o2c_cmd = cmdline::O2CCli::command();
@@ -15,10 +21,33 @@ fn gen_comp<G: aot::Generator>(shell_one: G, shell_two: G) {
     exit(0);
 }
 
+fn run(oxygen_file: &Path, tab_width: usize) -> Result<i64, Error> {
+    let prog = parse_file(oxygen_file, tab_width)?;
+    semantic::entry::check(&prog, semantic::entry::DEFAULT_ENTRY)?;
+    let symbols = semantic::resolve::resolve(&prog)?;
+    semantic::calls::check(&prog, &symbols)?;
+    let types = semantic::types::check(&prog, &symbols)?;
+    for warning in semantic::flow::check(&prog)? {
+        eprintln!("{}", diagnostic::warning(oxygen_file, &warning));
+    }
+
+    Ok(Interpreter::new(&prog, &symbols, &types).run()?)
+}
+
 fn main() {
     let cli = cmdline::OxygenCli::parse();
 
     match cli.command {
+        Run {
+            oxygen_file,
+            tab_width,
+        } => match run(&oxygen_file, tab_width) {
+            Ok(code) => exit(code as i32),
+            Err(e) => {
+                eprintln!("{}", diagnostic::report(&oxygen_file, &e));
+                exit(1);
+            }
+        },
         Completions { shell } => match shell {
             OxygenShells::Fish => gen_comp(aot::Fish, aot::Fish),
             OxygenShells::Bash => gen_comp(aot::Bash, aot::Bash),
@@ -2,6 +2,8 @@ use std::path::PathBuf;
 
 use clap::{Parser, Subcommand, ValueEnum};
 
+use crate::{lexer::DEFAULT_TAB_WIDTH, semantic::entry::DEFAULT_ENTRY};
+
 /// Represents the posssible cmdline args using the [`clap`] crate.
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
@@ -14,6 +16,11 @@ pub struct O2CCli {
     )]
     pub output_file: Option<PathBuf>,
 
+    /// `true` if an object file should be written instead of an executable,
+    /// `false` otherwise.
+    #[arg(short = 'c', help = "Compile to an ELF64 object file without linking")]
+    pub compile_only: bool,
+
     /// `true` if tokens should be displayed, `false` otherwise.
     #[arg(
         long,
@@ -29,6 +36,35 @@ pub struct O2CCli {
     )]
     pub display_ast: bool,
 
+    /// Contains the pipeline stages to write to files next to the output.
+    #[arg(
+        long,
+        value_name = "STAGE",
+        value_enum,
+        value_delimiter = ',',
+        help = "Write the given compilation stages to files next to the output"
+    )]
+    pub emit: Vec<EmitStage>,
+
+    /// Contains the number of columns between tab stops in diagnostics.
+    #[arg(
+        long,
+        value_name = "WIDTH",
+        default_value_t = DEFAULT_TAB_WIDTH,
+        help = "The number of columns between tab stops in diagnostics",
+        value_parser = validate_tab_width
+    )]
+    pub tab_width: usize,
+
+    /// Contains the name of the function the program starts at.
+    #[arg(
+        long,
+        value_name = "NAME",
+        default_value = DEFAULT_ENTRY,
+        help = "The function the compiled program starts at"
+    )]
+    pub entry: String,
+
     /// Contains the file path to the validated oxygen file to compile.
     #[arg(
         value_name = "O2_FILE",
@@ -47,6 +83,50 @@ fn validate_oxygen_file(s: &str) -> Result<PathBuf, String> {
     }
 }
 
+/// Validates that a given string is a non-zero tab width.
+fn validate_tab_width(s: &str) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(0) => Err("must be at least 1".to_string()),
+        Ok(width) => Ok(width),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Represents the stages of the compilation pipeline which can be emitted.
+///
+/// The variants are ordered in the order the stages are produced.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum)]
+pub enum EmitStage {
+    /// The tokens produced by the lexer.
+    Tokens,
+    /// The AST produced by the parser.
+    Ast,
+    /// The intermediate representation lowered from the AST.
+    Ir,
+    /// The x86-64 GNU assembly.
+    Asm,
+    /// The ELF64 object file.
+    Obj,
+    /// The ELF64 executable.
+    Exe,
+}
+
+impl EmitStage {
+    /// Get the file extension used when writing the stage next to the output.
+    pub fn extension(&self) -> &'static str {
+        use EmitStage::*;
+
+        match self {
+            Tokens => "tokens",
+            Ast => "ast",
+            Ir => "ir",
+            Asm => "s",
+            Obj => "o",
+            Exe => "",
+        }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, ValueEnum)]
 pub enum OxygenShells {
     Fish,
@@ -56,6 +136,23 @@ pub enum OxygenShells {
 
 #[derive(Subcommand, Debug)]
 pub enum OxygenCommands {
+    /// Interpret an oxygen file and exit with the return value of `main`.
+    Run {
+        #[arg(
+            value_name = "O2_FILE",
+            help = "The path to the oxygen file to run",
+            value_parser = validate_oxygen_file
+        )]
+        oxygen_file: PathBuf,
+        #[arg(
+            long,
+            value_name = "WIDTH",
+            default_value_t = DEFAULT_TAB_WIDTH,
+            help = "The number of columns between tab stops in diagnostics",
+            value_parser = validate_tab_width
+        )]
+        tab_width: usize,
+    },
     Completions {
         #[arg(value_enum)]
         shell: OxygenShells,
@@ -83,6 +180,26 @@ mod tests {
         assert!(validate_oxygen_file("some").is_err())
     }
 
+    #[test]
+    fn should_validate_tab_width() {
+        assert_eq!(validate_tab_width("8"), Ok(8));
+        assert!(validate_tab_width("0").is_err());
+        assert!(validate_tab_width("x").is_err());
+    }
+
+    #[test]
+    fn should_parse_emit_stages() {
+        let cli = O2CCli::try_parse_from(["o2c", "--emit=tokens,asm", "some.o2"]).unwrap();
+
+        assert_eq!(cli.emit, vec![EmitStage::Tokens, EmitStage::Asm]);
+    }
+
+    #[test]
+    fn should_order_emit_stages() {
+        assert!(EmitStage::Tokens < EmitStage::Ast);
+        assert!(EmitStage::Obj < EmitStage::Exe);
+    }
+
     #[test]
     fn validate_cli() {
         use clap::CommandFactory;
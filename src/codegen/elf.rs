@@ -0,0 +1,503 @@
+use std::{fs::OpenOptions, io::Write, os::unix::fs::OpenOptionsExt, path::Path};
+
+use crate::{codegen::encode::Encoded, error::CodegenError};
+
+/// The virtual address the executable is loaded at.
+const BASE_ADDRESS: u64 = 0x40_0000;
+/// The page alignment of the loadable segment.
+const PAGE_SIZE: u64 = 0x1000;
+
+/// The size of the ELF64 file header.
+const EHDR_SIZE: u16 = 64;
+/// The size of an ELF64 program header.
+const PHDR_SIZE: u16 = 56;
+/// The size of an ELF64 section header.
+const SHDR_SIZE: u16 = 64;
+/// The size of an ELF64 symbol table entry.
+const SYM_SIZE: u64 = 24;
+/// The size of an ELF64 relocation entry with an addend.
+const RELA_SIZE: u64 = 24;
+
+/// The `e_type` of a relocatable object file.
+const ET_REL: u16 = 1;
+/// The `e_type` of an executable file.
+const ET_EXEC: u16 = 2;
+/// The `e_machine` of x86-64.
+const EM_X86_64: u16 = 62;
+
+/// The `p_type` of a loadable segment.
+const PT_LOAD: u32 = 1;
+/// The `p_flags` of a readable and executable segment.
+const PF_R_X: u32 = 0b101;
+
+/// The `sh_type` of a section holding program defined data.
+const SHT_PROGBITS: u32 = 1;
+/// The `sh_type` of a symbol table section.
+const SHT_SYMTAB: u32 = 2;
+/// The `sh_type` of a string table section.
+const SHT_STRTAB: u32 = 3;
+/// The `sh_type` of a relocation section with addends.
+const SHT_RELA: u32 = 4;
+/// The `sh_flags` of an allocated section.
+const SHF_ALLOC: u64 = 0b010;
+/// The `sh_flags` of an allocated and executable section.
+const SHF_ALLOC_EXECINSTR: u64 = 0b110;
+/// The `sh_flags` of a section whose `sh_info` holds a section header index.
+const SHF_INFO_LINK: u64 = 0x40;
+
+/// The `st_info` binding of a local symbol.
+const STB_LOCAL: u8 = 0;
+/// The `st_info` binding of a global symbol.
+const STB_GLOBAL: u8 = 1;
+/// The `st_info` type of a function symbol.
+const STT_FUNC: u8 = 2;
+/// The `st_info` type of a section symbol.
+const STT_SECTION: u8 = 3;
+
+/// The relocation type of a 32-bit `%rip` relative displacement.
+const R_X86_64_PC32: u64 = 2;
+
+/// The section header index of `.text`.
+const TEXT_INDEX: u16 = 1;
+/// The section header index of `.rodata`.
+const RODATA_INDEX: u16 = 2;
+/// The section header index of `.symtab`.
+const SYMTAB_INDEX: u32 = 4;
+/// The section header index of `.strtab`.
+const STRTAB_INDEX: u32 = 5;
+/// The section header index of `.shstrtab`.
+const SHSTRTAB_INDEX: u16 = 6;
+/// The number of section headers written.
+const SECTION_COUNT: u16 = 7;
+/// The symbol table index of the `.rodata` section symbol.
+const RODATA_SYMBOL: u64 = 1;
+
+/// A representation of the kind of ELF file to write.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum ElfKind {
+    /// A relocatable object file.
+    Object,
+    /// A statically linked executable.
+    Executable,
+}
+
+/// Build a relocatable ELF64 object file containing the encoded code.
+pub fn object(encoded: &Encoded) -> Vec<u8> {
+    build(encoded, ElfKind::Object, 0)
+}
+
+/// Build a static ELF64 executable containing the encoded code which starts
+/// executing at the symbol named `entry`.
+pub fn executable(encoded: &Encoded, entry: &str) -> Result<Vec<u8>, CodegenError> {
+    let entry = encoded
+        .symbol(entry)
+        .ok_or_else(|| CodegenError::UndefinedLabel {
+            label: entry.to_string(),
+            span: None,
+        })?
+        .offset as u64;
+
+    Ok(build(encoded, ElfKind::Executable, entry))
+}
+
+/// Write the bytes to `path` and mark the file as executable.
+pub fn write_executable(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o755)
+        .open(path)?;
+
+    file.write_all(bytes)
+}
+
+fn build(encoded: &Encoded, kind: ElfKind, entry: u64) -> Vec<u8> {
+    let phdr_count: u16 = match kind {
+        ElfKind::Object => 0,
+        ElfKind::Executable => 1,
+    };
+    let text_offset = (EHDR_SIZE + phdr_count * PHDR_SIZE) as u64;
+    let text_size = encoded.code.len() as u64;
+    let rodata_offset = align(text_offset + text_size, 16);
+    let rodata_size = encoded.data.len() as u64;
+    let (text_addr, rodata_addr) = match kind {
+        ElfKind::Object => (0, 0),
+        ElfKind::Executable => (BASE_ADDRESS + text_offset, BASE_ADDRESS + rodata_offset),
+    };
+
+    // References into `.rodata` are resolved here for an executable, or left
+    // to the linker for an object.
+    let mut code = encoded.code.clone();
+    let mut rela = Bytes::new();
+    for relocation in &encoded.relocations {
+        match kind {
+            ElfKind::Object => {
+                rela.u64(relocation.at as u64);
+                rela.u64((RODATA_SYMBOL << 32) | R_X86_64_PC32);
+                // The displacement is relative to the end of the 32-bit field.
+                rela.u64((relocation.offset as i64 - 4) as u64);
+            }
+            ElfKind::Executable => {
+                let target = rodata_addr + relocation.offset as u64;
+                let rel = target as i64 - (text_addr + relocation.at as u64 + 4) as i64;
+                code[relocation.at..relocation.at + 4].copy_from_slice(&(rel as i32).to_le_bytes());
+            }
+        }
+    }
+
+    // Symbol table, locals must come before globals.
+    let mut strtab = StringTable::new();
+    let mut symtab = Bytes::new();
+    symtab.zeros(SYM_SIZE as usize);
+    symtab.u32(0);
+    symtab.u8((STB_LOCAL << 4) | STT_SECTION);
+    symtab.u8(0);
+    symtab.u16(RODATA_INDEX);
+    symtab.u64(rodata_addr);
+    symtab.u64(0);
+    let mut symbols: Vec<_> = encoded.symbols.iter().collect();
+    symbols.sort_by_key(|s| s.global);
+    let first_global = 2 + symbols.iter().filter(|s| !s.global).count() as u32;
+    for symbol in symbols {
+        let binding = if symbol.global { STB_GLOBAL } else { STB_LOCAL };
+        symtab.u32(strtab.add(&symbol.name));
+        symtab.u8((binding << 4) | STT_FUNC);
+        symtab.u8(0);
+        symtab.u16(TEXT_INDEX);
+        symtab.u64(text_addr + symbol.offset as u64);
+        symtab.u64(0);
+    }
+
+    let mut shstrtab = StringTable::new();
+    let text_name = shstrtab.add(".text");
+    let rodata_name = shstrtab.add(".rodata");
+    let rela_name = shstrtab.add(".rela.text");
+    let symtab_name = shstrtab.add(".symtab");
+    let strtab_name = shstrtab.add(".strtab");
+    let shstrtab_name = shstrtab.add(".shstrtab");
+
+    let rela_offset = align(rodata_offset + rodata_size, 8);
+    let symtab_offset = rela_offset + rela.len();
+    let strtab_offset = symtab_offset + symtab.len();
+    let shstrtab_offset = strtab_offset + strtab.len();
+    let shdr_offset = align(shstrtab_offset + shstrtab.len(), 8);
+
+    let mut out = Bytes::new();
+
+    // ELF header
+    out.bytes(&[0x7F, b'E', b'L', b'F', 2, 1, 1, 0]);
+    out.zeros(8);
+    out.u16(match kind {
+        ElfKind::Object => ET_REL,
+        ElfKind::Executable => ET_EXEC,
+    });
+    out.u16(EM_X86_64);
+    out.u32(1);
+    out.u64(match kind {
+        ElfKind::Object => 0,
+        ElfKind::Executable => text_addr + entry,
+    });
+    out.u64(if phdr_count == 0 { 0 } else { EHDR_SIZE as u64 });
+    out.u64(shdr_offset);
+    out.u32(0);
+    out.u16(EHDR_SIZE);
+    out.u16(if phdr_count == 0 { 0 } else { PHDR_SIZE });
+    out.u16(phdr_count);
+    out.u16(SHDR_SIZE);
+    out.u16(SECTION_COUNT);
+    out.u16(SHSTRTAB_INDEX);
+
+    // Program header, a single segment mapping the headers, `.text` and
+    // `.rodata`.
+    if kind == ElfKind::Executable {
+        let segment_size = rodata_offset + rodata_size;
+        out.u32(PT_LOAD);
+        out.u32(PF_R_X);
+        out.u64(0);
+        out.u64(BASE_ADDRESS);
+        out.u64(BASE_ADDRESS);
+        out.u64(segment_size);
+        out.u64(segment_size);
+        out.u64(PAGE_SIZE);
+    }
+
+    out.bytes(&code);
+    out.pad_to(rodata_offset);
+    out.bytes(&encoded.data);
+    out.pad_to(rela_offset);
+    out.bytes(&rela.0);
+    out.bytes(&symtab.0);
+    out.bytes(&strtab.bytes.0);
+    out.bytes(&shstrtab.bytes.0);
+    out.pad_to(shdr_offset);
+
+    // Section headers
+    out.zeros(SHDR_SIZE as usize);
+    out.section_header(SectionHeader {
+        name: text_name,
+        kind: SHT_PROGBITS,
+        flags: SHF_ALLOC_EXECINSTR,
+        addr: text_addr,
+        offset: text_offset,
+        size: text_size,
+        link: 0,
+        info: 0,
+        align: 16,
+        entsize: 0,
+    });
+    out.section_header(SectionHeader {
+        name: rodata_name,
+        kind: SHT_PROGBITS,
+        flags: SHF_ALLOC,
+        addr: rodata_addr,
+        offset: rodata_offset,
+        size: rodata_size,
+        link: 0,
+        info: 0,
+        align: 16,
+        entsize: 0,
+    });
+    out.section_header(SectionHeader {
+        name: rela_name,
+        kind: SHT_RELA,
+        flags: SHF_INFO_LINK,
+        addr: 0,
+        offset: rela_offset,
+        size: rela.len(),
+        link: SYMTAB_INDEX,
+        info: TEXT_INDEX as u32,
+        align: 8,
+        entsize: RELA_SIZE,
+    });
+    out.section_header(SectionHeader {
+        name: symtab_name,
+        kind: SHT_SYMTAB,
+        flags: 0,
+        addr: 0,
+        offset: symtab_offset,
+        size: symtab.len(),
+        link: STRTAB_INDEX,
+        info: first_global,
+        align: 8,
+        entsize: SYM_SIZE,
+    });
+    out.section_header(SectionHeader {
+        name: strtab_name,
+        kind: SHT_STRTAB,
+        flags: 0,
+        addr: 0,
+        offset: strtab_offset,
+        size: strtab.len(),
+        link: 0,
+        info: 0,
+        align: 1,
+        entsize: 0,
+    });
+    out.section_header(SectionHeader {
+        name: shstrtab_name,
+        kind: SHT_STRTAB,
+        flags: 0,
+        addr: 0,
+        offset: shstrtab_offset,
+        size: shstrtab.len(),
+        link: 0,
+        info: 0,
+        align: 1,
+        entsize: 0,
+    });
+
+    out.0
+}
+
+/// Round `value` up to the next multiple of `to`.
+fn align(value: u64, to: u64) -> u64 {
+    value.div_ceil(to) * to
+}
+
+/// A representation of an ELF64 section header.
+struct SectionHeader {
+    name: u32,
+    kind: u32,
+    flags: u64,
+    addr: u64,
+    offset: u64,
+    size: u64,
+    link: u32,
+    info: u32,
+    align: u64,
+    entsize: u64,
+}
+
+/// A little endian byte buffer.
+struct Bytes(Vec<u8>);
+
+impl Bytes {
+    fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    fn len(&self) -> u64 {
+        self.0.len() as u64
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn zeros(&mut self, count: usize) {
+        self.0.resize(self.0.len() + count, 0);
+    }
+
+    fn pad_to(&mut self, offset: u64) {
+        self.0.resize(offset as usize, 0);
+    }
+
+    fn u8(&mut self, value: u8) {
+        self.0.push(value);
+    }
+
+    fn u16(&mut self, value: u16) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    fn section_header(&mut self, header: SectionHeader) {
+        self.u32(header.name);
+        self.u32(header.kind);
+        self.u64(header.flags);
+        self.u64(header.addr);
+        self.u64(header.offset);
+        self.u64(header.size);
+        self.u32(header.link);
+        self.u32(header.info);
+        self.u64(header.align);
+        self.u64(header.entsize);
+    }
+}
+
+/// A null terminated ELF string table.
+struct StringTable {
+    bytes: Bytes,
+}
+
+impl StringTable {
+    /// Create a new [`StringTable`] starting with the empty string.
+    fn new() -> Self {
+        Self {
+            bytes: Bytes(vec![0]),
+        }
+    }
+
+    fn len(&self) -> u64 {
+        self.bytes.len()
+    }
+
+    /// Add the string returning its offset in the table.
+    fn add(&mut self, s: &str) -> u32 {
+        let offset = self.bytes.len() as u32;
+        self.bytes.bytes(s.as_bytes());
+        self.bytes.u8(0);
+        offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::codegen::encode::{Relocation, Symbol};
+
+    use super::*;
+
+    fn encoded() -> Encoded {
+        Encoded {
+            code: vec![0xC3, 0xC3],
+            data: Vec::new(),
+            symbols: vec![
+                Symbol {
+                    name: "_start".to_string(),
+                    offset: 1,
+                    global: true,
+                },
+                Symbol {
+                    name: "main".to_string(),
+                    offset: 0,
+                    global: false,
+                },
+            ],
+            relocations: Vec::new(),
+        }
+    }
+
+    fn u16_at(bytes: &[u8], at: usize) -> u16 {
+        u16::from_le_bytes(bytes[at..at + 2].try_into().unwrap())
+    }
+
+    fn u64_at(bytes: &[u8], at: usize) -> u64 {
+        u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap())
+    }
+
+    #[test]
+    fn should_align() {
+        assert_eq!(align(0, 8), 0);
+        assert_eq!(align(1, 8), 8);
+        assert_eq!(align(16, 8), 16);
+    }
+
+    #[test]
+    fn should_add_to_string_table() {
+        let mut table = StringTable::new();
+
+        assert_eq!(table.add(".text"), 1);
+        assert_eq!(table.add("main"), 7);
+        assert_eq!(table.len(), 12);
+    }
+
+    #[test]
+    fn should_build_executable() {
+        let bytes = executable(&encoded(), "_start").unwrap();
+
+        assert_eq!(&bytes[..4], b"\x7FELF");
+        assert_eq!(u16_at(&bytes, 16), ET_EXEC);
+        assert_eq!(u16_at(&bytes, 18), EM_X86_64);
+        assert_eq!(u64_at(&bytes, 24), BASE_ADDRESS + 120 + 1);
+        assert_eq!(&bytes[120..122], [0xC3, 0xC3]);
+    }
+
+    #[test]
+    fn should_patch_data_references_in_executable() {
+        let mut encoded = encoded();
+        encoded.code = vec![0x48, 0x8D, 0x05, 0, 0, 0, 0, 0xC3];
+        encoded.data = b"hi\0".to_vec();
+        encoded.relocations = vec![Relocation { at: 3, offset: 1 }];
+
+        let bytes = executable(&encoded, "_start").unwrap();
+
+        // `.text` at 120 is followed by `.rodata` aligned to 128.
+        assert_eq!(&bytes[128..131], b"hi\0");
+        assert_eq!(&bytes[123..127], (129 - 127i32).to_le_bytes());
+    }
+
+    #[test]
+    fn should_build_object() {
+        let bytes = object(&encoded());
+
+        assert_eq!(u16_at(&bytes, 16), ET_REL);
+        assert_eq!(u64_at(&bytes, 24), 0);
+        assert_eq!(u16_at(&bytes, 56), 0);
+        assert_eq!(&bytes[64..66], [0xC3, 0xC3]);
+    }
+
+    #[test]
+    fn should_error_missing_entry() {
+        let res = executable(&encoded(), "missing");
+
+        assert!(res.is_err_and(|e| matches!(e, CodegenError::UndefinedLabel { .. })));
+    }
+}
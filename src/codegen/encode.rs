@@ -0,0 +1,765 @@
+use std::collections::HashMap;
+
+use crate::{
+    codegen::instruction::{self, FloatRegister, Instruction, Register},
+    error::CodegenError,
+};
+
+/// The REX prefix with the `W` bit set selecting 64-bit operands.
+const REX_W: u8 = 0x48;
+/// The REX prefix `R` bit extending the ModRM `reg` field.
+const REX_R: u8 = 0x04;
+/// The REX prefix `B` bit extending the ModRM `rm` field.
+const REX_B: u8 = 0x01;
+/// The bare REX prefix.
+const REX: u8 = 0x40;
+/// The operand size prefix selecting the packed and 64-bit move forms of SSE
+/// instructions.
+const OPERAND_SIZE: u8 = 0x66;
+/// The prefix selecting the scalar double forms of SSE instructions.
+const SCALAR_DOUBLE: u8 = 0xF2;
+/// The prefix selecting the scalar single forms of SSE instructions.
+const SCALAR_SINGLE: u8 = 0xF3;
+
+/// A representation of a symbol defined in the machine code.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Symbol {
+    /// Contains the name of the symbol.
+    pub name: String,
+    /// Contains the byte offset of the symbol from the start of the code.
+    pub offset: usize,
+    /// `true` if the symbol is visible outside the object, `false` otherwise.
+    pub global: bool,
+}
+
+/// A representation of a reference from the machine code into the read-only
+/// data which can only be resolved once the data is placed.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Relocation {
+    /// Contains the offset of the 32-bit `%rip` relative displacement to patch.
+    pub at: usize,
+    /// Contains the byte offset of the target from the start of the data.
+    pub offset: usize,
+}
+
+/// A representation of encoded machine code.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Encoded {
+    /// Contains the raw machine code.
+    pub code: Vec<u8>,
+    /// Contains the raw read-only data.
+    pub data: Vec<u8>,
+    /// Contains every non-local label defined in the machine code.
+    pub symbols: Vec<Symbol>,
+    /// Contains every reference from the machine code into the data.
+    pub relocations: Vec<Relocation>,
+}
+
+impl Encoded {
+    /// Find the symbol with the given name.
+    pub fn symbol(&self, name: &str) -> Option<&Symbol> {
+        self.symbols.iter().find(|s| s.name == name)
+    }
+}
+
+/// A reference to a label which is patched once all labels are known.
+#[derive(Debug)]
+struct Fixup {
+    /// Contains the name of the referenced label.
+    label: String,
+    /// Contains the offset of the 32-bit displacement to patch.
+    at: usize,
+}
+
+/// Encode the instructions into x86-64 machine code.
+///
+/// Labels and bytes are placed in the current [`instruction::Section`], every other
+/// instruction is always placed in the code.
+pub fn encode(instructions: &[Instruction]) -> Result<Encoded, CodegenError> {
+    let mut code: Vec<u8> = Vec::new();
+    let mut data: Vec<u8> = Vec::new();
+    let mut section = instruction::Section::Text;
+    let mut labels: HashMap<&str, usize> = HashMap::new();
+    let mut data_labels: HashMap<&str, usize> = HashMap::new();
+    let mut globals: Vec<&str> = Vec::new();
+    let mut order: Vec<&str> = Vec::new();
+    let mut fixups: Vec<Fixup> = Vec::new();
+
+    for instruction in instructions {
+        use Instruction::*;
+
+        match instruction {
+            Global(label) => globals.push(label),
+            Label(label) => {
+                if labels.contains_key(label.as_str()) || data_labels.contains_key(label.as_str()) {
+                    return Err(CodegenError::DuplicateLabel {
+                        label: label.clone(),
+                        span: None,
+                    });
+                }
+                match section {
+                    instruction::Section::Text => {
+                        labels.insert(label, code.len());
+                        order.push(label);
+                    }
+                    instruction::Section::Rodata => {
+                        data_labels.insert(label, data.len());
+                    }
+                }
+            }
+            Section(to) => section = *to,
+            Bytes(bytes) => match section {
+                instruction::Section::Text => code.extend_from_slice(bytes),
+                instruction::Section::Rodata => data.extend_from_slice(bytes),
+            },
+            Push(reg) => {
+                rex_b(&mut code, *reg);
+                code.push(0x50 + reg.low_bits());
+            }
+            Pop(reg) => {
+                rex_b(&mut code, *reg);
+                code.push(0x58 + reg.low_bits());
+            }
+            Mov { dst, src } => {
+                code.push(REX_W | rex_bits(*src, *dst));
+                code.push(0x89);
+                code.push(modrm_direct(src.low_bits(), *dst));
+            }
+            MovImm { dst, imm } => match i32::try_from(*imm) {
+                Ok(imm) => {
+                    code.push(REX_W | rex_bits(Register::Rax, *dst));
+                    code.push(0xC7);
+                    code.push(modrm_direct(0, *dst));
+                    code.extend_from_slice(&imm.to_le_bytes());
+                }
+                Err(_) => {
+                    code.push(REX_W | rex_bits(Register::Rax, *dst));
+                    code.push(0xB8 + dst.low_bits());
+                    code.extend_from_slice(&imm.to_le_bytes());
+                }
+            },
+            Lea { dst, label } => {
+                code.push(REX_W | rex_bits(*dst, Register::Rax));
+                code.push(0x8D);
+                // A `mod` of 0b00 with an `rm` of 0b101 addresses relative to `%rip`.
+                code.push(0b00_000_101 | (dst.low_bits() << 3));
+                fixup(&mut code, &mut fixups, label);
+            }
+            Load { dst, base, offset } => {
+                code.push(REX_W | rex_bits(*dst, *base));
+                code.push(0x8B);
+                modrm_memory(&mut code, dst.low_bits(), *base, *offset);
+            }
+            Store { base, offset, src } => {
+                code.push(REX_W | rex_bits(*src, *base));
+                code.push(0x89);
+                modrm_memory(&mut code, src.low_bits(), *base, *offset);
+            }
+            AddImm { dst, imm } => {
+                code.push(REX_W | rex_bits(Register::Rax, *dst));
+                code.push(0x81);
+                code.push(modrm_direct(0, *dst));
+                code.extend_from_slice(&imm.to_le_bytes());
+            }
+            SubImm { dst, imm } => {
+                code.push(REX_W | rex_bits(Register::Rax, *dst));
+                code.push(0x81);
+                code.push(modrm_direct(5, *dst));
+                code.extend_from_slice(&imm.to_le_bytes());
+            }
+            Add { dst, src } => {
+                code.push(REX_W | rex_bits(*src, *dst));
+                code.push(0x01);
+                code.push(modrm_direct(src.low_bits(), *dst));
+            }
+            Sub { dst, src } => {
+                code.push(REX_W | rex_bits(*src, *dst));
+                code.push(0x29);
+                code.push(modrm_direct(src.low_bits(), *dst));
+            }
+            IMul { dst, src } => {
+                code.push(REX_W | rex_bits(*dst, *src));
+                code.extend_from_slice(&[0x0F, 0xAF]);
+                code.push(modrm_direct(dst.low_bits(), *src));
+            }
+            And { dst, src } => {
+                code.push(REX_W | rex_bits(*src, *dst));
+                code.push(0x21);
+                code.push(modrm_direct(src.low_bits(), *dst));
+            }
+            Or { dst, src } => {
+                code.push(REX_W | rex_bits(*src, *dst));
+                code.push(0x09);
+                code.push(modrm_direct(src.low_bits(), *dst));
+            }
+            Xor { dst, src } => {
+                code.push(REX_W | rex_bits(*src, *dst));
+                code.push(0x31);
+                code.push(modrm_direct(src.low_bits(), *dst));
+            }
+            ShrImm { dst, imm } => {
+                code.push(REX_W | rex_bits(Register::Rax, *dst));
+                code.push(0xC1);
+                code.push(modrm_direct(5, *dst));
+                code.push(*imm);
+            }
+            Cqo => code.extend_from_slice(&[REX_W, 0x99]),
+            IDiv(reg) => {
+                code.push(REX_W | rex_bits(Register::Rax, *reg));
+                code.push(0xF7);
+                code.push(modrm_direct(7, *reg));
+            }
+            Div(reg) => {
+                code.push(REX_W | rex_bits(Register::Rax, *reg));
+                code.push(0xF7);
+                code.push(modrm_direct(6, *reg));
+            }
+            Neg(reg) => {
+                code.push(REX_W | rex_bits(Register::Rax, *reg));
+                code.push(0xF7);
+                code.push(modrm_direct(3, *reg));
+            }
+            Cmp { lhs, rhs } => {
+                code.push(REX_W | rex_bits(*rhs, *lhs));
+                code.push(0x39);
+                code.push(modrm_direct(rhs.low_bits(), *lhs));
+            }
+            Test { lhs, rhs } => {
+                code.push(REX_W | rex_bits(*rhs, *lhs));
+                code.push(0x85);
+                code.push(modrm_direct(rhs.low_bits(), *lhs));
+            }
+            // The prefix of an SSE instruction must come before any REX prefix.
+            MovToFloat { dst, src } => {
+                code.extend_from_slice(&[OPERAND_SIZE, REX_W | rex_bits(Register::Rax, *src)]);
+                code.extend_from_slice(&[0x0F, 0x6E]);
+                code.push(modrm_direct(dst.number(), *src));
+            }
+            MovFromFloat { dst, src } => {
+                code.extend_from_slice(&[OPERAND_SIZE, REX_W | rex_bits(Register::Rax, *dst)]);
+                code.extend_from_slice(&[0x0F, 0x7E]);
+                code.push(modrm_direct(src.number(), *dst));
+            }
+            AddSd { dst, src } => {
+                code.extend_from_slice(&[SCALAR_DOUBLE, 0x0F, 0x58, modrm_float(*dst, *src)])
+            }
+            SubSd { dst, src } => {
+                code.extend_from_slice(&[SCALAR_DOUBLE, 0x0F, 0x5C, modrm_float(*dst, *src)])
+            }
+            MulSd { dst, src } => {
+                code.extend_from_slice(&[SCALAR_DOUBLE, 0x0F, 0x59, modrm_float(*dst, *src)])
+            }
+            DivSd { dst, src } => {
+                code.extend_from_slice(&[SCALAR_DOUBLE, 0x0F, 0x5E, modrm_float(*dst, *src)])
+            }
+            UComISd { lhs, rhs } => {
+                code.extend_from_slice(&[OPERAND_SIZE, 0x0F, 0x2E, modrm_float(*lhs, *rhs)])
+            }
+            Cvtsi2sd { dst, src } => {
+                code.extend_from_slice(&[SCALAR_DOUBLE, REX_W | rex_bits(Register::Rax, *src)]);
+                code.extend_from_slice(&[0x0F, 0x2A]);
+                code.push(modrm_direct(dst.number(), *src));
+            }
+            Cvttsd2si { dst, src } => {
+                code.extend_from_slice(&[SCALAR_DOUBLE, REX_W | rex_bits(*dst, Register::Rax)]);
+                code.extend_from_slice(&[0x0F, 0x2C]);
+                code.push(0b11_000_000 | (dst.low_bits() << 3) | src.number());
+            }
+            Cvtsd2ss { dst, src } => {
+                code.extend_from_slice(&[SCALAR_DOUBLE, 0x0F, 0x5A, modrm_float(*dst, *src)])
+            }
+            Cvtss2sd { dst, src } => {
+                code.extend_from_slice(&[SCALAR_SINGLE, 0x0F, 0x5A, modrm_float(*dst, *src)])
+            }
+            SetCC(cond, reg) => {
+                // Without a REX prefix the byte registers 4-7 select the high
+                // bytes of the first four registers rather than the low bytes.
+                if reg.number() >= 4 {
+                    code.push(REX | rex_bits(Register::Rax, *reg));
+                }
+                code.extend_from_slice(&[0x0F, 0x90 + cond.code()]);
+                code.push(modrm_direct(0, *reg));
+            }
+            MovzxByte(reg) => {
+                code.push(REX_W | rex_bits(*reg, *reg));
+                code.extend_from_slice(&[0x0F, 0xB6]);
+                code.push(modrm_direct(reg.low_bits(), *reg));
+            }
+            Jmp(label) => {
+                code.push(0xE9);
+                fixup(&mut code, &mut fixups, label);
+            }
+            Jcc(cond, label) => {
+                code.extend_from_slice(&[0x0F, 0x80 + cond.code()]);
+                fixup(&mut code, &mut fixups, label);
+            }
+            Call(label) => {
+                code.push(0xE8);
+                fixup(&mut code, &mut fixups, label);
+            }
+            Ret => code.push(0xC3),
+            Syscall => code.extend_from_slice(&[0x0F, 0x05]),
+        }
+    }
+
+    let mut relocations: Vec<Relocation> = Vec::new();
+    for fixup in fixups {
+        if let Some(&offset) = data_labels.get(fixup.label.as_str()) {
+            relocations.push(Relocation {
+                at: fixup.at,
+                offset,
+            });
+            continue;
+        }
+
+        let target =
+            *labels
+                .get(fixup.label.as_str())
+                .ok_or_else(|| CodegenError::UndefinedLabel {
+                    label: fixup.label.clone(),
+                    span: None,
+                })?;
+        // Displacements are relative to the end of the 32-bit field.
+        let rel = target as i64 - (fixup.at as i64 + 4);
+        let rel = i32::try_from(rel).map_err(|_| CodegenError::JumpTooFar {
+            label: fixup.label.clone(),
+            span: None,
+        })?;
+        code[fixup.at..fixup.at + 4].copy_from_slice(&rel.to_le_bytes());
+    }
+
+    let symbols = order
+        .into_iter()
+        .filter(|label| !Instruction::is_local_label(label))
+        .map(|label| Symbol {
+            name: label.to_string(),
+            offset: labels[label],
+            global: globals.contains(&label),
+        })
+        .collect();
+
+    Ok(Encoded {
+        code,
+        data,
+        symbols,
+        relocations,
+    })
+}
+
+/// Push a placeholder 32-bit displacement to the label to be patched later.
+fn fixup(code: &mut Vec<u8>, fixups: &mut Vec<Fixup>, label: &str) {
+    fixups.push(Fixup {
+        label: label.to_string(),
+        at: code.len(),
+    });
+    code.extend_from_slice(&[0; 4]);
+}
+
+/// Push a REX prefix if the register in the opcode or `rm` field is extended.
+fn rex_b(code: &mut Vec<u8>, reg: Register) {
+    if reg.is_extended() {
+        code.push(REX | REX_B);
+    }
+}
+
+/// Get the REX extension bits for the `reg` and `rm` operands.
+fn rex_bits(reg: Register, rm: Register) -> u8 {
+    let mut bits = 0;
+    if reg.is_extended() {
+        bits |= REX_R;
+    }
+    if rm.is_extended() {
+        bits |= REX_B;
+    }
+    bits
+}
+
+/// Build a ModRM byte addressing the `rm` register directly.
+fn modrm_direct(reg: u8, rm: Register) -> u8 {
+    0b11_000_000 | (reg << 3) | rm.low_bits()
+}
+
+/// Build a ModRM byte addressing the `rm` SSE register directly.
+fn modrm_float(reg: FloatRegister, rm: FloatRegister) -> u8 {
+    0b11_000_000 | (reg.number() << 3) | rm.number()
+}
+
+/// Push a ModRM byte, and SIB byte if needed, addressing the memory at a
+/// 32-bit displacement from the `base` register.
+fn modrm_memory(code: &mut Vec<u8>, reg: u8, base: Register, offset: i32) {
+    code.push(0b10_000_000 | (reg << 3) | base.low_bits());
+    // An `rm` of 0b100 selects a SIB byte, so `%rsp` must be given through it.
+    if base.low_bits() == Register::Rsp.low_bits() {
+        code.push(0x24);
+    }
+    code.extend_from_slice(&offset.to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{codegen::instruction::Condition, fixtures::analyse};
+
+    fn encode_one(instruction: Instruction) -> Vec<u8> {
+        encode(&[instruction]).unwrap().code
+    }
+
+    #[test]
+    fn should_encode_push_pop() {
+        assert_eq!(encode_one(Instruction::Push(Register::Rbp)), [0x55]);
+        assert_eq!(encode_one(Instruction::Pop(Register::Rbp)), [0x5D]);
+    }
+
+    #[test]
+    fn should_encode_mov() {
+        assert_eq!(
+            encode_one(Instruction::Mov {
+                dst: Register::Rbp,
+                src: Register::Rsp
+            }),
+            [0x48, 0x89, 0xE5]
+        );
+        assert_eq!(
+            encode_one(Instruction::Mov {
+                dst: Register::Rdi,
+                src: Register::Rax
+            }),
+            [0x48, 0x89, 0xC7]
+        );
+    }
+
+    #[test]
+    fn should_encode_mov_imm() {
+        assert_eq!(
+            encode_one(Instruction::MovImm {
+                dst: Register::Rax,
+                imm: 60
+            }),
+            [0x48, 0xC7, 0xC0, 0x3C, 0x00, 0x00, 0x00]
+        );
+        assert_eq!(
+            encode_one(Instruction::MovImm {
+                dst: Register::Rax,
+                imm: 0x1_0000_0000
+            }),
+            [0x48, 0xB8, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00]
+        );
+    }
+
+    #[test]
+    fn should_encode_load_store() {
+        assert_eq!(
+            encode_one(Instruction::Load {
+                dst: Register::Rax,
+                base: Register::Rbp,
+                offset: -8
+            }),
+            [0x48, 0x8B, 0x85, 0xF8, 0xFF, 0xFF, 0xFF]
+        );
+        assert_eq!(
+            encode_one(Instruction::Store {
+                base: Register::Rbp,
+                offset: -8,
+                src: Register::Rax
+            }),
+            [0x48, 0x89, 0x85, 0xF8, 0xFF, 0xFF, 0xFF]
+        );
+        assert_eq!(
+            encode_one(Instruction::Load {
+                dst: Register::Rax,
+                base: Register::Rsp,
+                offset: 8
+            }),
+            [0x48, 0x8B, 0x84, 0x24, 0x08, 0x00, 0x00, 0x00]
+        );
+    }
+
+    #[test]
+    fn should_encode_sub_imm() {
+        assert_eq!(
+            encode_one(Instruction::SubImm {
+                dst: Register::Rsp,
+                imm: 16
+            }),
+            [0x48, 0x81, 0xEC, 0x10, 0x00, 0x00, 0x00]
+        );
+    }
+
+    #[test]
+    fn should_encode_add_imm() {
+        assert_eq!(
+            encode_one(Instruction::AddImm {
+                dst: Register::Rsp,
+                imm: 16
+            }),
+            [0x48, 0x81, 0xC4, 0x10, 0x00, 0x00, 0x00]
+        );
+    }
+
+    #[test]
+    fn should_encode_arithmetic() {
+        let (dst, src) = (Register::Rax, Register::Rcx);
+
+        assert_eq!(
+            encode_one(Instruction::Add { dst, src }),
+            [0x48, 0x01, 0xC8]
+        );
+        assert_eq!(
+            encode_one(Instruction::Sub { dst, src }),
+            [0x48, 0x29, 0xC8]
+        );
+        assert_eq!(
+            encode_one(Instruction::IMul { dst, src }),
+            [0x48, 0x0F, 0xAF, 0xC1]
+        );
+        assert_eq!(encode_one(Instruction::Cqo), [0x48, 0x99]);
+        assert_eq!(encode_one(Instruction::IDiv(src)), [0x48, 0xF7, 0xF9]);
+        assert_eq!(encode_one(Instruction::Div(src)), [0x48, 0xF7, 0xF1]);
+        assert_eq!(
+            encode_one(Instruction::ShrImm { dst: src, imm: 32 }),
+            [0x48, 0xC1, 0xE9, 0x20]
+        );
+        assert_eq!(encode_one(Instruction::Neg(dst)), [0x48, 0xF7, 0xD8]);
+    }
+
+    #[test]
+    fn should_encode_comparison() {
+        let (lhs, rhs) = (Register::Rax, Register::Rcx);
+
+        assert_eq!(
+            encode_one(Instruction::Cmp { lhs, rhs }),
+            [0x48, 0x39, 0xC8]
+        );
+        assert_eq!(
+            encode_one(Instruction::Test { lhs, rhs: lhs }),
+            [0x48, 0x85, 0xC0]
+        );
+        assert_eq!(
+            encode_one(Instruction::SetCC(Condition::Less, lhs)),
+            [0x0F, 0x9C, 0xC0]
+        );
+        assert_eq!(
+            encode_one(Instruction::SetCC(Condition::Equal, Register::Rdi)),
+            [0x40, 0x0F, 0x94, 0xC7]
+        );
+        assert_eq!(
+            encode_one(Instruction::SetCC(Condition::BelowEqual, lhs)),
+            [0x0F, 0x96, 0xC0]
+        );
+        assert_eq!(
+            encode_one(Instruction::MovzxByte(lhs)),
+            [0x48, 0x0F, 0xB6, 0xC0]
+        );
+    }
+
+    #[test]
+    fn should_encode_bitwise() {
+        let (dst, src) = (Register::Rax, Register::Rcx);
+
+        assert_eq!(
+            encode_one(Instruction::And { dst, src }),
+            [0x48, 0x21, 0xC8]
+        );
+        assert_eq!(encode_one(Instruction::Or { dst, src }), [0x48, 0x09, 0xC8]);
+        assert_eq!(
+            encode_one(Instruction::Xor { dst, src }),
+            [0x48, 0x31, 0xC8]
+        );
+    }
+
+    #[test]
+    fn should_encode_float() {
+        let (xmm0, xmm1) = (FloatRegister::Xmm0, FloatRegister::Xmm1);
+
+        assert_eq!(
+            encode_one(Instruction::MovToFloat {
+                dst: xmm1,
+                src: Register::Rcx
+            }),
+            [0x66, 0x48, 0x0F, 0x6E, 0xC9]
+        );
+        assert_eq!(
+            encode_one(Instruction::MovFromFloat {
+                dst: Register::Rax,
+                src: xmm0
+            }),
+            [0x66, 0x48, 0x0F, 0x7E, 0xC0]
+        );
+        assert_eq!(
+            encode_one(Instruction::AddSd {
+                dst: xmm0,
+                src: xmm1
+            }),
+            [0xF2, 0x0F, 0x58, 0xC1]
+        );
+        assert_eq!(
+            encode_one(Instruction::DivSd {
+                dst: xmm0,
+                src: xmm1
+            }),
+            [0xF2, 0x0F, 0x5E, 0xC1]
+        );
+        assert_eq!(
+            encode_one(Instruction::UComISd {
+                lhs: xmm1,
+                rhs: xmm0
+            }),
+            [0x66, 0x0F, 0x2E, 0xC8]
+        );
+        assert_eq!(
+            encode_one(Instruction::Cvtsi2sd {
+                dst: xmm0,
+                src: Register::Rax
+            }),
+            [0xF2, 0x48, 0x0F, 0x2A, 0xC0]
+        );
+        assert_eq!(
+            encode_one(Instruction::Cvttsd2si {
+                dst: Register::Rcx,
+                src: xmm0
+            }),
+            [0xF2, 0x48, 0x0F, 0x2C, 0xC8]
+        );
+        assert_eq!(
+            encode_one(Instruction::Cvtsd2ss {
+                dst: xmm0,
+                src: xmm0
+            }),
+            [0xF2, 0x0F, 0x5A, 0xC0]
+        );
+        assert_eq!(
+            encode_one(Instruction::Cvtss2sd {
+                dst: xmm0,
+                src: xmm0
+            }),
+            [0xF3, 0x0F, 0x5A, 0xC0]
+        );
+        assert_eq!(
+            encode_one(Instruction::SetCC(Condition::Parity, Register::Rcx)),
+            [0x0F, 0x9A, 0xC1]
+        );
+    }
+
+    #[test]
+    fn should_encode_jump_fixups() {
+        let encoded = encode(&[
+            Instruction::Label(".L0".to_string()),
+            Instruction::Jcc(Condition::NotEqual, ".L1".to_string()),
+            Instruction::Jmp(".L0".to_string()),
+            Instruction::Label(".L1".to_string()),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            encoded.code,
+            [
+                0x0F, 0x85, 0x05, 0x00, 0x00, 0x00, 0xE9, 0xF5, 0xFF, 0xFF, 0xFF
+            ]
+        );
+        assert!(encoded.symbols.is_empty());
+    }
+
+    #[test]
+    fn should_encode_data_relocations() {
+        let encoded = encode(&[
+            Instruction::Lea {
+                dst: Register::Rdi,
+                label: ".Lstr1".to_string(),
+            },
+            Instruction::Section(instruction::Section::Rodata),
+            Instruction::Label(".Lstr0".to_string()),
+            Instruction::Bytes(vec![b'a', 0]),
+            Instruction::Label(".Lstr1".to_string()),
+            Instruction::Bytes(vec![0]),
+        ])
+        .unwrap();
+
+        assert_eq!(encoded.code, [0x48, 0x8D, 0x3D, 0x00, 0x00, 0x00, 0x00]);
+        assert_eq!(encoded.data, [b'a', 0, 0]);
+        assert_eq!(encoded.relocations, vec![Relocation { at: 3, offset: 2 }]);
+    }
+
+    #[test]
+    fn should_encode_ret_syscall() {
+        assert_eq!(encode_one(Instruction::Ret), [0xC3]);
+        assert_eq!(encode_one(Instruction::Syscall), [0x0F, 0x05]);
+    }
+
+    #[test]
+    fn should_encode_call_fixup() {
+        let encoded = encode(&[
+            Instruction::Call("f".to_string()),
+            Instruction::Ret,
+            Instruction::Label("f".to_string()),
+            Instruction::Ret,
+        ])
+        .unwrap();
+
+        assert_eq!(encoded.code, [0xE8, 0x01, 0x00, 0x00, 0x00, 0xC3, 0xC3]);
+        assert_eq!(encoded.symbol("f").unwrap().offset, 6);
+    }
+
+    #[test]
+    fn should_collect_symbols() {
+        let encoded = encode(&[
+            Instruction::Global("_start".to_string()),
+            Instruction::Label("_start".to_string()),
+            Instruction::Label(".L0".to_string()),
+            Instruction::Label("main".to_string()),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            encoded.symbols,
+            vec![
+                Symbol {
+                    name: "_start".to_string(),
+                    offset: 0,
+                    global: true
+                },
+                Symbol {
+                    name: "main".to_string(),
+                    offset: 0,
+                    global: false
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_error_undefined_label() {
+        let res = encode(&[Instruction::Call("missing".to_string())]);
+
+        assert!(res.is_err_and(|e| matches!(e, CodegenError::UndefinedLabel { .. })));
+    }
+
+    #[test]
+    fn should_error_duplicate_label() {
+        let res = encode(&[
+            Instruction::Label("main".to_string()),
+            Instruction::Label("main".to_string()),
+        ]);
+
+        assert!(res.is_err_and(|e| matches!(e, CodegenError::DuplicateLabel { .. })));
+    }
+
+    #[test]
+    fn should_locate_error_at_function_of_label() {
+        let (prog, symbols, _) = analyse("int main() { return 0; }");
+        let res = encode(&[
+            Instruction::Label(".Lmain.0".to_string()),
+            Instruction::Label(".Lmain.0".to_string()),
+        ]);
+
+        let error = res.unwrap_err().locate(&symbols);
+
+        assert_eq!(error.span(), Some(prog[0].span()));
+        assert_eq!(
+            encode(&[Instruction::Call("missing".to_string())])
+                .unwrap_err()
+                .locate(&symbols)
+                .span(),
+            None
+        );
+    }
+}
@@ -0,0 +1,557 @@
+use std::fmt::Display;
+
+/// A representation of the x86-64 general purpose 64-bit registers.
+///
+/// The discriminant of each variant is its encoding number.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Register {
+    Rax = 0,
+    Rcx = 1,
+    Rdx = 2,
+    Rsp = 4,
+    Rbp = 5,
+    Rsi = 6,
+    Rdi = 7,
+}
+
+impl Register {
+    /// Get the 4-bit register number used in the instruction encoding.
+    pub fn number(&self) -> u8 {
+        *self as u8
+    }
+
+    /// `true` if the register needs a REX prefix extension bit, `false`
+    /// otherwise.
+    pub fn is_extended(&self) -> bool {
+        self.number() >= 8
+    }
+
+    /// Get the lower 3 bits of the register number used in a ModRM byte.
+    pub fn low_bits(&self) -> u8 {
+        self.number() & 0b111
+    }
+
+    /// Get the assembly name of the lowest byte of the register.
+    pub fn low_byte(&self) -> &'static str {
+        use Register::*;
+
+        match self {
+            Rax => "%al",
+            Rcx => "%cl",
+            Rdx => "%dl",
+            Rsp => "%spl",
+            Rbp => "%bpl",
+            Rsi => "%sil",
+            Rdi => "%dil",
+        }
+    }
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Register::*;
+
+        let name = match self {
+            Rax => "rax",
+            Rcx => "rcx",
+            Rdx => "rdx",
+            Rsp => "rsp",
+            Rbp => "rbp",
+            Rsi => "rsi",
+            Rdi => "rdi",
+        };
+
+        write!(f, "%{name}")
+    }
+}
+
+/// A representation of the SSE registers used for floating-point values.
+///
+/// The discriminant of each variant is its encoding number, none of which
+/// need a REX prefix extension bit.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FloatRegister {
+    Xmm0 = 0,
+    Xmm1 = 1,
+}
+
+impl FloatRegister {
+    /// Get the 3-bit register number used in a ModRM byte.
+    pub fn number(&self) -> u8 {
+        *self as u8
+    }
+}
+
+impl Display for FloatRegister {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "%xmm{}", self.number())
+    }
+}
+
+/// A representation of the condition codes tested by conditional
+/// instructions after a signed comparison, or after an unsigned or
+/// floating-point comparison for the `Above`, `Below` and `Parity` variants.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Condition {
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Above,
+    AboveEqual,
+    Below,
+    BelowEqual,
+    Parity,
+    NotParity,
+}
+
+impl Condition {
+    /// Get the 4-bit condition code used in the instruction encoding.
+    pub fn code(&self) -> u8 {
+        use Condition::*;
+
+        match self {
+            Below => 0x2,
+            AboveEqual => 0x3,
+            Equal => 0x4,
+            NotEqual => 0x5,
+            BelowEqual => 0x6,
+            Above => 0x7,
+            Parity => 0xA,
+            NotParity => 0xB,
+            Less => 0xC,
+            GreaterEqual => 0xD,
+            LessEqual => 0xE,
+            Greater => 0xF,
+        }
+    }
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Condition::*;
+
+        let suffix = match self {
+            Equal => "e",
+            NotEqual => "ne",
+            Less => "l",
+            LessEqual => "le",
+            Greater => "g",
+            GreaterEqual => "ge",
+            Above => "a",
+            AboveEqual => "ae",
+            Below => "b",
+            BelowEqual => "be",
+            Parity => "p",
+            NotParity => "np",
+        };
+
+        write!(f, "{suffix}")
+    }
+}
+
+/// A representation of the sections instructions and data are placed in.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Section {
+    /// The executable machine code.
+    Text,
+    /// The read-only data.
+    Rodata,
+}
+
+impl Display for Section {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Section::Text => write!(f, "    .text"),
+            Section::Rodata => write!(f, "    .section .rodata"),
+        }
+    }
+}
+
+/// A representation of a single x86-64 instruction or assembler directive.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Instruction {
+    /// Marks the given label as a global symbol.
+    Global(String),
+    /// Defines a label at the current position.
+    Label(String),
+    /// Places everything following in the section.
+    Section(Section),
+    /// Places the raw bytes at the current position.
+    Bytes(Vec<u8>),
+    /// Push the register onto the stack.
+    Push(Register),
+    /// Pop the top of the stack into the register.
+    Pop(Register),
+    /// Copy the `src` register into the `dst` register.
+    Mov { dst: Register, src: Register },
+    /// Load the immediate into the `dst` register.
+    MovImm { dst: Register, imm: i64 },
+    /// Load the address of the label, relative to `%rip`, into the `dst`
+    /// register.
+    Lea { dst: Register, label: String },
+    /// Load the 64-bit value at `offset` from the `base` register into the
+    /// `dst` register.
+    Load {
+        dst: Register,
+        base: Register,
+        offset: i32,
+    },
+    /// Store the `src` register into the 64-bit value at `offset` from the
+    /// `base` register.
+    Store {
+        base: Register,
+        offset: i32,
+        src: Register,
+    },
+    /// Add the immediate to the `dst` register.
+    AddImm { dst: Register, imm: i32 },
+    /// Subtract the immediate from the `dst` register.
+    SubImm { dst: Register, imm: i32 },
+    /// Add the `src` register to the `dst` register.
+    Add { dst: Register, src: Register },
+    /// Subtract the `src` register from the `dst` register.
+    Sub { dst: Register, src: Register },
+    /// Multiply the `dst` register by the `src` register.
+    IMul { dst: Register, src: Register },
+    /// Bitwise and the `src` register into the `dst` register.
+    And { dst: Register, src: Register },
+    /// Bitwise or the `src` register into the `dst` register.
+    Or { dst: Register, src: Register },
+    /// Bitwise exclusive or the `src` register into the `dst` register.
+    Xor { dst: Register, src: Register },
+    /// Shift the `dst` register right by the immediate, filling with zeros.
+    ShrImm { dst: Register, imm: u8 },
+    /// Sign extend `%rax` into `%rdx:%rax`.
+    Cqo,
+    /// Divide `%rdx:%rax` by the register, leaving the quotient in `%rax`
+    /// and the remainder in `%rdx`.
+    IDiv(Register),
+    /// Divide the unsigned `%rdx:%rax` by the register, leaving the quotient
+    /// in `%rax` and the remainder in `%rdx`.
+    Div(Register),
+    /// Negate the register.
+    Neg(Register),
+    /// Compare the `lhs` register with the `rhs` register, setting the flags
+    /// as if `rhs` were subtracted from `lhs`.
+    Cmp { lhs: Register, rhs: Register },
+    /// Bitwise and the `lhs` register with the `rhs` register, setting the
+    /// flags without storing the result.
+    Test { lhs: Register, rhs: Register },
+    /// Copy the bits of the `src` register into the `dst` SSE register.
+    MovToFloat { dst: FloatRegister, src: Register },
+    /// Copy the bits of the `src` SSE register into the `dst` register.
+    MovFromFloat { dst: Register, src: FloatRegister },
+    /// Add the double in the `src` register to the `dst` register.
+    AddSd {
+        dst: FloatRegister,
+        src: FloatRegister,
+    },
+    /// Subtract the double in the `src` register from the `dst` register.
+    SubSd {
+        dst: FloatRegister,
+        src: FloatRegister,
+    },
+    /// Multiply the double in the `dst` register by the `src` register.
+    MulSd {
+        dst: FloatRegister,
+        src: FloatRegister,
+    },
+    /// Divide the double in the `dst` register by the `src` register.
+    DivSd {
+        dst: FloatRegister,
+        src: FloatRegister,
+    },
+    /// Compare the double in the `lhs` register with the `rhs` register,
+    /// setting the flags as an unsigned comparison with the parity flag set
+    /// if either is NaN.
+    UComISd {
+        lhs: FloatRegister,
+        rhs: FloatRegister,
+    },
+    /// Convert the signed integer in the `src` register to a double.
+    Cvtsi2sd { dst: FloatRegister, src: Register },
+    /// Convert the double in the `src` register to a signed integer,
+    /// truncating towards zero.
+    Cvttsd2si { dst: Register, src: FloatRegister },
+    /// Convert the double in the `src` register to a single.
+    Cvtsd2ss {
+        dst: FloatRegister,
+        src: FloatRegister,
+    },
+    /// Convert the single in the `src` register to a double.
+    Cvtss2sd {
+        dst: FloatRegister,
+        src: FloatRegister,
+    },
+    /// Set the low byte of the register to `1` if the condition holds, `0`
+    /// otherwise.
+    SetCC(Condition, Register),
+    /// Zero extend the low byte of the register into the whole register.
+    MovzxByte(Register),
+    /// Jump to the given label.
+    Jmp(String),
+    /// Jump to the given label if the condition holds.
+    Jcc(Condition, String),
+    /// Call the function at the given label.
+    Call(String),
+    /// Return from the current function.
+    Ret,
+    /// Perform a system call.
+    Syscall,
+}
+
+impl Instruction {
+    /// `true` if the label is assembler local and should not become a
+    /// symbol, `false` otherwise.
+    pub fn is_local_label(label: &str) -> bool {
+        label.starts_with(".L")
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Instruction::*;
+
+        match self {
+            Global(label) => write!(f, "    .globl {label}"),
+            Label(label) => write!(f, "{label}:"),
+            Section(section) => write!(f, "{section}"),
+            Bytes(bytes) => {
+                let bytes: Vec<String> = bytes.iter().map(u8::to_string).collect();
+                write!(f, "    .byte {}", bytes.join(", "))
+            }
+            Push(reg) => write!(f, "    pushq {reg}"),
+            Pop(reg) => write!(f, "    popq {reg}"),
+            Mov { dst, src } => write!(f, "    movq {src}, {dst}"),
+            MovImm { dst, imm } if i32::try_from(*imm).is_ok() => {
+                write!(f, "    movq ${imm}, {dst}")
+            }
+            MovImm { dst, imm } => write!(f, "    movabsq ${imm}, {dst}"),
+            Lea { dst, label } => write!(f, "    leaq {label}(%rip), {dst}"),
+            Load { dst, base, offset } => write!(f, "    movq {offset}({base}), {dst}"),
+            Store { base, offset, src } => write!(f, "    movq {src}, {offset}({base})"),
+            AddImm { dst, imm } => write!(f, "    addq ${imm}, {dst}"),
+            SubImm { dst, imm } => write!(f, "    subq ${imm}, {dst}"),
+            Add { dst, src } => write!(f, "    addq {src}, {dst}"),
+            Sub { dst, src } => write!(f, "    subq {src}, {dst}"),
+            IMul { dst, src } => write!(f, "    imulq {src}, {dst}"),
+            And { dst, src } => write!(f, "    andq {src}, {dst}"),
+            Or { dst, src } => write!(f, "    orq {src}, {dst}"),
+            Xor { dst, src } => write!(f, "    xorq {src}, {dst}"),
+            ShrImm { dst, imm } => write!(f, "    shrq ${imm}, {dst}"),
+            Cqo => write!(f, "    cqto"),
+            IDiv(reg) => write!(f, "    idivq {reg}"),
+            Div(reg) => write!(f, "    divq {reg}"),
+            Neg(reg) => write!(f, "    negq {reg}"),
+            Cmp { lhs, rhs } => write!(f, "    cmpq {rhs}, {lhs}"),
+            Test { lhs, rhs } => write!(f, "    testq {rhs}, {lhs}"),
+            MovToFloat { dst, src } => write!(f, "    movq {src}, {dst}"),
+            MovFromFloat { dst, src } => write!(f, "    movq {src}, {dst}"),
+            AddSd { dst, src } => write!(f, "    addsd {src}, {dst}"),
+            SubSd { dst, src } => write!(f, "    subsd {src}, {dst}"),
+            MulSd { dst, src } => write!(f, "    mulsd {src}, {dst}"),
+            DivSd { dst, src } => write!(f, "    divsd {src}, {dst}"),
+            UComISd { lhs, rhs } => write!(f, "    ucomisd {rhs}, {lhs}"),
+            Cvtsi2sd { dst, src } => write!(f, "    cvtsi2sdq {src}, {dst}"),
+            Cvttsd2si { dst, src } => write!(f, "    cvttsd2siq {src}, {dst}"),
+            Cvtsd2ss { dst, src } => write!(f, "    cvtsd2ss {src}, {dst}"),
+            Cvtss2sd { dst, src } => write!(f, "    cvtss2sd {src}, {dst}"),
+            SetCC(cond, reg) => write!(f, "    set{cond} {}", reg.low_byte()),
+            MovzxByte(reg) => write!(f, "    movzbq {}, {reg}", reg.low_byte()),
+            Jmp(label) => write!(f, "    jmp {label}"),
+            Jcc(cond, label) => write!(f, "    j{cond} {label}"),
+            Call(label) => write!(f, "    call {label}"),
+            Ret => write!(f, "    ret"),
+            Syscall => write!(f, "    syscall"),
+        }
+    }
+}
+
+/// Render the instructions as x86-64 GNU assembly.
+pub fn render(instructions: &[Instruction]) -> String {
+    let mut asm = format!("{}\n", Section::Text);
+
+    for instruction in instructions {
+        asm.push_str(&instruction.to_string());
+        asm.push('\n');
+    }
+
+    asm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_get_register_encoding() {
+        assert_eq!(Register::Rax.number(), 0);
+        assert_eq!(Register::Rdi.number(), 7);
+        assert!(!Register::Rdi.is_extended());
+        assert_eq!(Register::Rbp.low_bits(), 5);
+    }
+
+    #[test]
+    fn should_display_register() {
+        assert_eq!(Register::Rax.to_string(), "%rax");
+        assert_eq!(Register::Rdi.to_string(), "%rdi");
+    }
+
+    #[test]
+    fn should_display_instructions() {
+        assert_eq!(
+            Instruction::Mov {
+                dst: Register::Rbp,
+                src: Register::Rsp
+            }
+            .to_string(),
+            "    movq %rsp, %rbp"
+        );
+        assert_eq!(
+            Instruction::MovImm {
+                dst: Register::Rax,
+                imm: 60
+            }
+            .to_string(),
+            "    movq $60, %rax"
+        );
+        assert_eq!(
+            Instruction::MovImm {
+                dst: Register::Rax,
+                imm: i64::MAX
+            }
+            .to_string(),
+            format!("    movabsq ${}, %rax", i64::MAX)
+        );
+        assert_eq!(
+            Instruction::IMul {
+                dst: Register::Rax,
+                src: Register::Rcx
+            }
+            .to_string(),
+            "    imulq %rcx, %rax"
+        );
+        assert_eq!(Instruction::Cqo.to_string(), "    cqto");
+        assert_eq!(
+            Instruction::Load {
+                dst: Register::Rax,
+                base: Register::Rbp,
+                offset: -8
+            }
+            .to_string(),
+            "    movq -8(%rbp), %rax"
+        );
+        assert_eq!(
+            Instruction::Store {
+                base: Register::Rbp,
+                offset: -16,
+                src: Register::Rax
+            }
+            .to_string(),
+            "    movq %rax, -16(%rbp)"
+        );
+        assert_eq!(
+            Instruction::IDiv(Register::Rcx).to_string(),
+            "    idivq %rcx"
+        );
+        assert_eq!(Instruction::Div(Register::Rcx).to_string(), "    divq %rcx");
+        assert_eq!(
+            Instruction::ShrImm {
+                dst: Register::Rcx,
+                imm: 32
+            }
+            .to_string(),
+            "    shrq $32, %rcx"
+        );
+        assert_eq!(Instruction::Label("main".to_string()).to_string(), "main:");
+    }
+
+    #[test]
+    fn should_display_conditional_instructions() {
+        assert_eq!(
+            Instruction::Cmp {
+                lhs: Register::Rax,
+                rhs: Register::Rcx
+            }
+            .to_string(),
+            "    cmpq %rcx, %rax"
+        );
+        assert_eq!(
+            Instruction::SetCC(Condition::LessEqual, Register::Rax).to_string(),
+            "    setle %al"
+        );
+        assert_eq!(
+            Instruction::SetCC(Condition::Below, Register::Rax).to_string(),
+            "    setb %al"
+        );
+        assert_eq!(
+            Instruction::MovzxByte(Register::Rax).to_string(),
+            "    movzbq %al, %rax"
+        );
+        assert_eq!(
+            Instruction::Jcc(Condition::Equal, ".L0".to_string()).to_string(),
+            "    je .L0"
+        );
+        assert_eq!(
+            Instruction::Jmp(".L0".to_string()).to_string(),
+            "    jmp .L0"
+        );
+    }
+
+    #[test]
+    fn should_display_float_instructions() {
+        assert_eq!(FloatRegister::Xmm1.to_string(), "%xmm1");
+        assert_eq!(
+            Instruction::MovToFloat {
+                dst: FloatRegister::Xmm0,
+                src: Register::Rax
+            }
+            .to_string(),
+            "    movq %rax, %xmm0"
+        );
+        assert_eq!(
+            Instruction::DivSd {
+                dst: FloatRegister::Xmm0,
+                src: FloatRegister::Xmm1
+            }
+            .to_string(),
+            "    divsd %xmm1, %xmm0"
+        );
+        assert_eq!(
+            Instruction::UComISd {
+                lhs: FloatRegister::Xmm0,
+                rhs: FloatRegister::Xmm1
+            }
+            .to_string(),
+            "    ucomisd %xmm1, %xmm0"
+        );
+        assert_eq!(
+            Instruction::Cvttsd2si {
+                dst: Register::Rax,
+                src: FloatRegister::Xmm0
+            }
+            .to_string(),
+            "    cvttsd2siq %xmm0, %rax"
+        );
+        assert_eq!(
+            Instruction::SetCC(Condition::NotParity, Register::Rcx).to_string(),
+            "    setnp %cl"
+        );
+    }
+
+    #[test]
+    fn should_detect_local_label() {
+        assert!(Instruction::is_local_label(".L0"));
+        assert!(!Instruction::is_local_label("main"));
+    }
+
+    #[test]
+    fn should_render() {
+        let asm = render(&[Instruction::Label("main".to_string()), Instruction::Ret]);
+
+        assert_eq!(asm, "    .text\nmain:\n    ret\n");
+    }
+}
@@ -0,0 +1,1092 @@
+pub mod elf;
+pub mod encode;
+pub mod instruction;
+
+use instruction::{Condition, FloatRegister, Instruction, Register, Section};
+
+use crate::{
+    ir::{Function, Module, Op},
+    semantic::entry::DEFAULT_ENTRY,
+};
+
+/// The symbol used as the program entry point.
+pub const ENTRY_SYMBOL: &str = "_start";
+
+/// The Linux x86-64 syscall number for `write`.
+const SYS_WRITE: i64 = 1;
+
+/// The Linux x86-64 syscall number for `exit`.
+const SYS_EXIT: i64 = 60;
+
+/// The file descriptor of the standard error stream.
+const STDERR: i64 = 2;
+
+/// The label of the routine which reports a division by zero and exits.
+const DIVIDE_BY_ZERO: &str = ".Ldivide_by_zero";
+
+/// The label of the message reported on a division by zero.
+const DIVIDE_BY_ZERO_MESSAGE: &str = ".Ldivide_by_zero_message";
+
+/// The message reported on a division by zero, matching the interpreter.
+const DIVIDE_BY_ZERO_TEXT: &[u8] = b"error: attempt to divide by zero\n";
+
+/// The size in bytes of a local variable slot.
+const SLOT_SIZE: i32 = 8;
+
+/// The offset from `%rbp` of the last argument pushed by the caller, past the
+/// saved `%rbp` and the return address.
+const ARGS_OFFSET: i32 = 16;
+
+/// A representation of the current state of the code generator.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Codegen {
+    /// Contains the instructions generated so far.
+    instructions: Vec<Instruction>,
+    /// Contains the name of the function called from the entry point.
+    entry: String,
+    /// Contains the number of labels generated, which are not in the IR.
+    labels: usize,
+    /// `true` if any division was generated, `false` otherwise.
+    divides: bool,
+}
+
+impl Codegen {
+    /// Create a new [`Codegen`] with no instructions.
+    pub fn new() -> Self {
+        Self::with_entry(DEFAULT_ENTRY)
+    }
+
+    /// Create a new [`Codegen`] with no instructions whose entry point calls
+    /// the function with the given name.
+    pub fn with_entry(entry: &str) -> Self {
+        Self {
+            instructions: Vec::new(),
+            entry: entry.to_string(),
+            labels: 0,
+            divides: false,
+        }
+    }
+
+    /// Generate x86-64 instructions for the given module.
+    pub fn generate(mut self, module: &Module) -> Vec<Instruction> {
+        self.emit_entry();
+
+        for function in &module.functions {
+            self.gen_function(function);
+        }
+        if self.divides {
+            self.emit_divide_by_zero();
+        }
+
+        if !module.strings.is_empty() || self.divides {
+            self.emit(Instruction::Section(Section::Rodata));
+            for (index, string) in module.strings.iter().enumerate() {
+                self.emit(Instruction::Label(string_label(index)));
+                // Strings are null terminated.
+                let mut bytes = string.as_bytes().to_vec();
+                bytes.push(0);
+                self.emit(Instruction::Bytes(bytes));
+            }
+        }
+        if self.divides {
+            self.emit(Instruction::Label(DIVIDE_BY_ZERO_MESSAGE.to_string()));
+            self.emit(Instruction::Bytes(DIVIDE_BY_ZERO_TEXT.to_vec()));
+        }
+
+        self.instructions
+    }
+
+    /// Emit the `_start` entry point which calls the entry function and
+    /// passes its return value to the `exit` syscall.
+    fn emit_entry(&mut self) {
+        use Instruction::*;
+
+        self.emit(Global(ENTRY_SYMBOL.to_string()));
+        self.emit(Label(ENTRY_SYMBOL.to_string()));
+        self.emit(Call(self.entry.clone()));
+        self.emit(Mov {
+            dst: Register::Rdi,
+            src: Register::Rax,
+        });
+        self.emit(MovImm {
+            dst: Register::Rax,
+            imm: SYS_EXIT,
+        });
+        self.emit(Syscall);
+    }
+
+    fn gen_function(&mut self, function: &Function) {
+        use Instruction::*;
+
+        self.emit(Label(function.name.clone()));
+        self.emit(Push(Register::Rbp));
+        self.emit(Mov {
+            dst: Register::Rbp,
+            src: Register::Rsp,
+        });
+        if function.locals > 0 {
+            self.emit(SubImm {
+                dst: Register::Rsp,
+                imm: function.locals as i32 * SLOT_SIZE,
+            });
+        }
+        // Arguments are pushed in order by the caller so the first is the
+        // furthest from `%rbp`. Copy them into the first local slots.
+        for param in 0..function.params {
+            self.emit(Load {
+                dst: Register::Rax,
+                base: Register::Rbp,
+                offset: ARGS_OFFSET + (function.params - 1 - param) as i32 * SLOT_SIZE,
+            });
+            self.emit(Store {
+                base: Register::Rbp,
+                offset: slot_offset(param),
+                src: Register::Rax,
+            });
+        }
+
+        for op in &function.body {
+            self.gen_op(&function.name, op);
+        }
+    }
+
+    /// Generate a single operation of the named function using the machine
+    /// stack as the value stack.
+    fn gen_op(&mut self, function: &str, op: &Op) {
+        use Instruction::*;
+
+        match op {
+            Op::Const(value) => {
+                self.emit(MovImm {
+                    dst: Register::Rax,
+                    imm: *value,
+                });
+                self.emit(Push(Register::Rax));
+            }
+            Op::Float(value) => {
+                self.emit(MovImm {
+                    dst: Register::Rax,
+                    imm: value.to_bits() as i64,
+                });
+                self.emit(Push(Register::Rax));
+            }
+            Op::Str(index) => {
+                self.emit(Lea {
+                    dst: Register::Rax,
+                    label: string_label(*index),
+                });
+                self.emit(Push(Register::Rax));
+            }
+            Op::Add | Op::Sub | Op::Mul | Op::Div | Op::Rem | Op::UDiv | Op::URem => {
+                self.emit(Pop(Register::Rcx));
+                self.emit(Pop(Register::Rax));
+                let (dst, src) = (Register::Rax, Register::Rcx);
+                match op {
+                    Op::Add => self.emit(Add { dst, src }),
+                    Op::Sub => self.emit(Sub { dst, src }),
+                    Op::Mul => self.emit(IMul { dst, src }),
+                    Op::Div | Op::Rem => {
+                        self.emit_divide_check();
+                        // `idiv` traps on the one quotient which overflows,
+                        // so dividing by `-1` negates instead, wrapping like
+                        // every other operation.
+                        let (divide, end) = (self.new_label(function), self.new_label(function));
+                        self.emit(MovImm {
+                            dst: Register::Rdx,
+                            imm: -1,
+                        });
+                        self.emit(Cmp {
+                            lhs: src,
+                            rhs: Register::Rdx,
+                        });
+                        self.emit(Jcc(Condition::NotEqual, divide.clone()));
+                        self.emit(Neg(dst));
+                        self.emit(Xor {
+                            dst: Register::Rdx,
+                            src: Register::Rdx,
+                        });
+                        self.emit(Jmp(end.clone()));
+                        self.emit(Label(divide));
+                        self.emit(Cqo);
+                        self.emit(IDiv(src));
+                        self.emit(Label(end));
+                    }
+                    _ => {
+                        self.emit_divide_check();
+                        self.emit(Xor {
+                            dst: Register::Rdx,
+                            src: Register::Rdx,
+                        });
+                        self.emit(Div(src));
+                    }
+                }
+                self.emit(Push(if matches!(op, Op::Rem | Op::URem) {
+                    Register::Rdx
+                } else {
+                    Register::Rax
+                }));
+            }
+            Op::Eq
+            | Op::Ne
+            | Op::Lt
+            | Op::Le
+            | Op::Gt
+            | Op::Ge
+            | Op::ULt
+            | Op::ULe
+            | Op::UGt
+            | Op::UGe => {
+                let cond = match op {
+                    Op::Eq => Condition::Equal,
+                    Op::Ne => Condition::NotEqual,
+                    Op::Lt => Condition::Less,
+                    Op::Le => Condition::LessEqual,
+                    Op::Gt => Condition::Greater,
+                    Op::Ge => Condition::GreaterEqual,
+                    Op::ULt => Condition::Below,
+                    Op::ULe => Condition::BelowEqual,
+                    Op::UGt => Condition::Above,
+                    _ => Condition::AboveEqual,
+                };
+                self.emit(Pop(Register::Rcx));
+                self.emit(Pop(Register::Rax));
+                self.emit(Cmp {
+                    lhs: Register::Rax,
+                    rhs: Register::Rcx,
+                });
+                self.emit_set(cond);
+            }
+            Op::FAdd | Op::FSub | Op::FMul | Op::FDiv => {
+                self.emit_float_operands();
+                let (dst, src) = (FloatRegister::Xmm0, FloatRegister::Xmm1);
+                self.emit(match op {
+                    Op::FAdd => AddSd { dst, src },
+                    Op::FSub => SubSd { dst, src },
+                    Op::FMul => MulSd { dst, src },
+                    _ => DivSd { dst, src },
+                });
+                self.emit_push_float();
+            }
+            Op::FEq | Op::FNe | Op::FLt | Op::FLe | Op::FGt | Op::FGe => {
+                self.emit_float_operands();
+                let (xmm0, xmm1) = (FloatRegister::Xmm0, FloatRegister::Xmm1);
+                // A NaN operand sets the zero, parity and carry flags, so `<`
+                // and `<=` swap the operands to test `above` which is false
+                // for NaN just like every other ordered comparison.
+                let (lhs, rhs, cond) = match op {
+                    Op::FEq => (xmm0, xmm1, Condition::Equal),
+                    Op::FNe => (xmm0, xmm1, Condition::NotEqual),
+                    Op::FLt => (xmm1, xmm0, Condition::Above),
+                    Op::FLe => (xmm1, xmm0, Condition::AboveEqual),
+                    Op::FGt => (xmm0, xmm1, Condition::Above),
+                    _ => (xmm0, xmm1, Condition::AboveEqual),
+                };
+                self.emit(UComISd { lhs, rhs });
+                match op {
+                    Op::FEq | Op::FNe => {
+                        // Equality also has to check the parity flag to tell
+                        // NaN apart.
+                        let (parity, combine): (_, fn(Register, Register) -> Instruction) =
+                            if *op == Op::FEq {
+                                (Condition::NotParity, |dst, src| And { dst, src })
+                            } else {
+                                (Condition::Parity, |dst, src| Or { dst, src })
+                            };
+                        self.emit(SetCC(cond, Register::Rax));
+                        self.emit(SetCC(parity, Register::Rcx));
+                        self.emit(MovzxByte(Register::Rax));
+                        self.emit(MovzxByte(Register::Rcx));
+                        self.emit(combine(Register::Rax, Register::Rcx));
+                        self.emit(Push(Register::Rax));
+                    }
+                    _ => self.emit_set(cond),
+                }
+            }
+            Op::FNeg => {
+                // Flipping the sign bit negates every double, including zero.
+                self.emit(Pop(Register::Rax));
+                self.emit(MovImm {
+                    dst: Register::Rcx,
+                    imm: i64::MIN,
+                });
+                self.emit(Xor {
+                    dst: Register::Rax,
+                    src: Register::Rcx,
+                });
+                self.emit(Push(Register::Rax));
+            }
+            Op::IntToFloat => {
+                self.emit(Pop(Register::Rax));
+                self.emit(Cvtsi2sd {
+                    dst: FloatRegister::Xmm0,
+                    src: Register::Rax,
+                });
+                self.emit_push_float();
+            }
+            Op::UIntToFloat => {
+                // The conversion is signed, so the high and low halves are
+                // converted apart and added back together, rounding only
+                // once.
+                let (xmm0, xmm1) = (FloatRegister::Xmm0, FloatRegister::Xmm1);
+                self.emit(Pop(Register::Rax));
+                self.emit(Mov {
+                    dst: Register::Rcx,
+                    src: Register::Rax,
+                });
+                self.emit(ShrImm {
+                    dst: Register::Rcx,
+                    imm: 32,
+                });
+                self.emit(MovImm {
+                    dst: Register::Rdx,
+                    imm: u32::MAX as i64,
+                });
+                self.emit(And {
+                    dst: Register::Rax,
+                    src: Register::Rdx,
+                });
+                self.emit(Cvtsi2sd {
+                    dst: xmm0,
+                    src: Register::Rcx,
+                });
+                self.emit(MovImm {
+                    dst: Register::Rdx,
+                    imm: 2f64.powi(32).to_bits() as i64,
+                });
+                self.emit(MovToFloat {
+                    dst: xmm1,
+                    src: Register::Rdx,
+                });
+                self.emit(MulSd {
+                    dst: xmm0,
+                    src: xmm1,
+                });
+                self.emit(Cvtsi2sd {
+                    dst: xmm1,
+                    src: Register::Rax,
+                });
+                self.emit(AddSd {
+                    dst: xmm0,
+                    src: xmm1,
+                });
+                self.emit_push_float();
+            }
+            Op::FloatToInt => {
+                self.emit(Pop(Register::Rax));
+                self.emit(MovToFloat {
+                    dst: FloatRegister::Xmm0,
+                    src: Register::Rax,
+                });
+                self.emit(Cvttsd2si {
+                    dst: Register::Rax,
+                    src: FloatRegister::Xmm0,
+                });
+                self.emit(Push(Register::Rax));
+            }
+            Op::FloatToSingle => {
+                let xmm0 = FloatRegister::Xmm0;
+                self.emit(Pop(Register::Rax));
+                self.emit(MovToFloat {
+                    dst: xmm0,
+                    src: Register::Rax,
+                });
+                self.emit(Cvtsd2ss {
+                    dst: xmm0,
+                    src: xmm0,
+                });
+                self.emit(Cvtss2sd {
+                    dst: xmm0,
+                    src: xmm0,
+                });
+                self.emit_push_float();
+            }
+            Op::Wrap { size, signed } => {
+                let bits = *size as u32 * 8;
+                let (dst, src) = (Register::Rax, Register::Rcx);
+                self.emit(Pop(Register::Rax));
+                self.emit(MovImm {
+                    dst: src,
+                    imm: (1i64 << bits) - 1,
+                });
+                self.emit(And { dst, src });
+                if *signed {
+                    // Flipping the sign bit and subtracting it again carries
+                    // it into every higher bit.
+                    self.emit(MovImm {
+                        dst: src,
+                        imm: 1i64 << (bits - 1),
+                    });
+                    self.emit(Xor { dst, src });
+                    self.emit(Sub { dst, src });
+                }
+                self.emit(Push(Register::Rax));
+            }
+            Op::Not => {
+                self.emit(Pop(Register::Rax));
+                self.emit(Test {
+                    lhs: Register::Rax,
+                    rhs: Register::Rax,
+                });
+                self.emit_set(Condition::Equal);
+            }
+            Op::Label(label) => self.emit(Label(local_label(function, *label))),
+            Op::Jump(label) => self.emit(Jmp(local_label(function, *label))),
+            Op::JumpIfZero(label) | Op::JumpIfNotZero(label) => {
+                let cond = if matches!(op, Op::JumpIfZero(_)) {
+                    Condition::Equal
+                } else {
+                    Condition::NotEqual
+                };
+                self.emit(Pop(Register::Rax));
+                self.emit(Test {
+                    lhs: Register::Rax,
+                    rhs: Register::Rax,
+                });
+                self.emit(Jcc(cond, local_label(function, *label)));
+            }
+            Op::Load(slot) => {
+                self.emit(Load {
+                    dst: Register::Rax,
+                    base: Register::Rbp,
+                    offset: slot_offset(*slot),
+                });
+                self.emit(Push(Register::Rax));
+            }
+            Op::Store(slot) => {
+                self.emit(Pop(Register::Rax));
+                self.emit(Store {
+                    base: Register::Rbp,
+                    offset: slot_offset(*slot),
+                    src: Register::Rax,
+                });
+            }
+            Op::Neg => {
+                self.emit(Pop(Register::Rax));
+                self.emit(Neg(Register::Rax));
+                self.emit(Push(Register::Rax));
+            }
+            Op::Call { name, args } => {
+                self.emit(Call(name.clone()));
+                if *args > 0 {
+                    self.emit(AddImm {
+                        dst: Register::Rsp,
+                        imm: *args as i32 * SLOT_SIZE,
+                    });
+                }
+                self.emit(Push(Register::Rax));
+            }
+            Op::Pop => self.emit(Pop(Register::Rax)),
+            Op::Return => {
+                self.emit(Pop(Register::Rax));
+                self.emit(Mov {
+                    dst: Register::Rsp,
+                    src: Register::Rbp,
+                });
+                self.emit(Pop(Register::Rbp));
+                self.emit(Ret);
+            }
+        }
+    }
+
+    /// Jump to the routine reporting a division by zero if the divisor in
+    /// `%rcx` is zero.
+    fn emit_divide_check(&mut self) {
+        self.divides = true;
+        self.emit(Instruction::Test {
+            lhs: Register::Rcx,
+            rhs: Register::Rcx,
+        });
+        self.emit(Instruction::Jcc(
+            Condition::Equal,
+            DIVIDE_BY_ZERO.to_string(),
+        ));
+    }
+
+    /// Emit the routine which writes the division by zero message to the
+    /// standard error stream and exits with `1`, like the interpreter.
+    fn emit_divide_by_zero(&mut self) {
+        use Instruction::*;
+
+        self.emit(Label(DIVIDE_BY_ZERO.to_string()));
+        self.emit(MovImm {
+            dst: Register::Rax,
+            imm: SYS_WRITE,
+        });
+        self.emit(MovImm {
+            dst: Register::Rdi,
+            imm: STDERR,
+        });
+        self.emit(Lea {
+            dst: Register::Rsi,
+            label: DIVIDE_BY_ZERO_MESSAGE.to_string(),
+        });
+        self.emit(MovImm {
+            dst: Register::Rdx,
+            imm: DIVIDE_BY_ZERO_TEXT.len() as i64,
+        });
+        self.emit(Syscall);
+        self.emit(MovImm {
+            dst: Register::Rax,
+            imm: SYS_EXIT,
+        });
+        self.emit(MovImm {
+            dst: Register::Rdi,
+            imm: 1,
+        });
+        self.emit(Syscall);
+    }
+
+    /// Get a new label within the named function which is distinct from
+    /// every label of the IR.
+    fn new_label(&mut self, function: &str) -> String {
+        self.labels += 1;
+        format!(".L{function}.gen{}", self.labels)
+    }
+
+    /// Push `1` if the condition holds on the current flags, `0` otherwise.
+    fn emit_set(&mut self, cond: Condition) {
+        self.emit(Instruction::SetCC(cond, Register::Rax));
+        self.emit(Instruction::MovzxByte(Register::Rax));
+        self.emit(Instruction::Push(Register::Rax));
+    }
+
+    /// Pop two floating-point values into `%xmm0` and `%xmm1` in order.
+    fn emit_float_operands(&mut self) {
+        self.emit(Instruction::Pop(Register::Rcx));
+        self.emit(Instruction::Pop(Register::Rax));
+        self.emit(Instruction::MovToFloat {
+            dst: FloatRegister::Xmm0,
+            src: Register::Rax,
+        });
+        self.emit(Instruction::MovToFloat {
+            dst: FloatRegister::Xmm1,
+            src: Register::Rcx,
+        });
+    }
+
+    /// Push the floating-point value in `%xmm0`.
+    fn emit_push_float(&mut self) {
+        self.emit(Instruction::MovFromFloat {
+            dst: Register::Rax,
+            src: FloatRegister::Xmm0,
+        });
+        self.emit(Instruction::Push(Register::Rax));
+    }
+
+    fn emit(&mut self, instruction: Instruction) {
+        self.instructions.push(instruction);
+    }
+}
+
+impl Default for Codegen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Get the assembler local name of a label within the named function.
+fn local_label(function: &str, label: usize) -> String {
+    format!(".L{function}.{label}")
+}
+
+/// Get the name of the function a label belongs to, the label of a function
+/// being its name and every other label belonging to no function.
+pub fn label_function(label: &str) -> &str {
+    match label.strip_prefix(".L").and_then(|l| l.rsplit_once('.')) {
+        Some((function, _)) => function,
+        None if label.starts_with('.') => "",
+        None => label,
+    }
+}
+
+/// Get the label of the interned string.
+///
+/// Unlike [`local_label`] there is no second `.` so the two never collide.
+fn string_label(index: usize) -> String {
+    format!(".Lstr{index}")
+}
+
+/// Get the offset from `%rbp` of the local variable slot.
+fn slot_offset(slot: usize) -> i32 {
+    -(slot as i32 + 1) * SLOT_SIZE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_create_new_codegen() {
+        assert_eq!(
+            Codegen {
+                instructions: Vec::new(),
+                entry: "main".to_string(),
+                labels: 0,
+                divides: false,
+            },
+            Codegen::new()
+        );
+    }
+
+    #[test]
+    fn should_generate_call_to_other_entry() {
+        let instructions = Codegen::with_entry("test_add").generate(&Module {
+            functions: Vec::new(),
+            strings: Vec::new(),
+        });
+
+        assert!(instructions.contains(&Instruction::Call("test_add".to_string())));
+        assert!(!instructions.contains(&Instruction::Call("main".to_string())));
+    }
+
+    #[test]
+    fn should_generate_entry() {
+        let instructions = Codegen::new().generate(&Module {
+            functions: Vec::new(),
+            strings: Vec::new(),
+        });
+
+        assert_eq!(
+            instructions.first().unwrap(),
+            &Instruction::Global(ENTRY_SYMBOL.to_string())
+        );
+        assert!(instructions.contains(&Instruction::Call("main".to_string())));
+        assert_eq!(instructions.last().unwrap(), &Instruction::Syscall);
+    }
+
+    #[test]
+    fn should_find_function_of_label() {
+        assert_eq!(label_function("main"), "main");
+        assert_eq!(label_function(&local_label("main", 3)), "main");
+        assert_eq!(label_function(&string_label(0)), "");
+    }
+
+    #[test]
+    fn should_generate_strings() {
+        let instructions = Codegen::new().generate(&Module {
+            functions: vec![Function {
+                name: "main".to_string(),
+                params: 0,
+                locals: 0,
+                body: vec![Op::Str(0)],
+            }],
+            strings: vec!["hi".to_string()],
+        });
+
+        assert!(instructions.contains(&Instruction::Lea {
+            dst: Register::Rax,
+            label: ".Lstr0".to_string()
+        }));
+        assert!(instructions.ends_with(&[
+            Instruction::Section(Section::Rodata),
+            Instruction::Label(".Lstr0".to_string()),
+            Instruction::Bytes(vec![b'h', b'i', 0]),
+        ]));
+    }
+
+    #[test]
+    fn should_get_slot_offset() {
+        assert_eq!(slot_offset(0), -8);
+        assert_eq!(slot_offset(2), -24);
+    }
+
+    #[test]
+    fn should_generate_locals() {
+        let instructions = Codegen::new().generate(&Module {
+            functions: vec![Function {
+                name: "main".to_string(),
+                params: 0,
+                locals: 2,
+                body: vec![Op::Store(1), Op::Load(1)],
+            }],
+            strings: Vec::new(),
+        });
+
+        assert!(instructions.ends_with(&[
+            Instruction::SubImm {
+                dst: Register::Rsp,
+                imm: 16
+            },
+            Instruction::Pop(Register::Rax),
+            Instruction::Store {
+                base: Register::Rbp,
+                offset: -16,
+                src: Register::Rax
+            },
+            Instruction::Load {
+                dst: Register::Rax,
+                base: Register::Rbp,
+                offset: -16
+            },
+            Instruction::Push(Register::Rax),
+        ]));
+    }
+
+    #[test]
+    fn should_generate_remainder() {
+        let instructions = Codegen::new().generate(&Module {
+            functions: vec![Function {
+                name: "main".to_string(),
+                params: 0,
+                locals: 0,
+                body: vec![Op::Rem],
+            }],
+            strings: Vec::new(),
+        });
+        let start = instructions
+            .iter()
+            .position(|i| *i == Instruction::Label("main".to_string()))
+            .unwrap();
+
+        assert_eq!(
+            instructions[start + 3..start + 18],
+            [
+                Instruction::Pop(Register::Rcx),
+                Instruction::Pop(Register::Rax),
+                Instruction::Test {
+                    lhs: Register::Rcx,
+                    rhs: Register::Rcx
+                },
+                Instruction::Jcc(Condition::Equal, DIVIDE_BY_ZERO.to_string()),
+                Instruction::MovImm {
+                    dst: Register::Rdx,
+                    imm: -1
+                },
+                Instruction::Cmp {
+                    lhs: Register::Rcx,
+                    rhs: Register::Rdx
+                },
+                Instruction::Jcc(Condition::NotEqual, ".Lmain.gen1".to_string()),
+                Instruction::Neg(Register::Rax),
+                Instruction::Xor {
+                    dst: Register::Rdx,
+                    src: Register::Rdx
+                },
+                Instruction::Jmp(".Lmain.gen2".to_string()),
+                Instruction::Label(".Lmain.gen1".to_string()),
+                Instruction::Cqo,
+                Instruction::IDiv(Register::Rcx),
+                Instruction::Label(".Lmain.gen2".to_string()),
+                Instruction::Push(Register::Rdx),
+            ]
+        );
+        assert!(instructions.ends_with(&[
+            Instruction::Section(Section::Rodata),
+            Instruction::Label(DIVIDE_BY_ZERO_MESSAGE.to_string()),
+            Instruction::Bytes(DIVIDE_BY_ZERO_TEXT.to_vec()),
+        ]));
+    }
+
+    #[test]
+    fn should_generate_divide_by_zero_only_when_dividing() {
+        let instructions = Codegen::new().generate(&Module {
+            functions: vec![Function {
+                name: "main".to_string(),
+                params: 0,
+                locals: 0,
+                body: vec![Op::Add],
+            }],
+            strings: Vec::new(),
+        });
+
+        assert!(!instructions.contains(&Instruction::Label(DIVIDE_BY_ZERO.to_string())));
+        assert!(!instructions.contains(&Instruction::Section(Section::Rodata)));
+    }
+
+    #[test]
+    fn should_generate_unsigned_division() {
+        let instructions = Codegen::new().generate(&Module {
+            functions: vec![Function {
+                name: "main".to_string(),
+                params: 0,
+                locals: 0,
+                body: vec![Op::UDiv],
+            }],
+            strings: Vec::new(),
+        });
+        let start = instructions
+            .iter()
+            .position(|i| *i == Instruction::Label("main".to_string()))
+            .unwrap();
+
+        assert_eq!(
+            instructions[start + 3..start + 10],
+            [
+                Instruction::Pop(Register::Rcx),
+                Instruction::Pop(Register::Rax),
+                Instruction::Test {
+                    lhs: Register::Rcx,
+                    rhs: Register::Rcx
+                },
+                Instruction::Jcc(Condition::Equal, DIVIDE_BY_ZERO.to_string()),
+                Instruction::Xor {
+                    dst: Register::Rdx,
+                    src: Register::Rdx
+                },
+                Instruction::Div(Register::Rcx),
+                Instruction::Push(Register::Rax),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_generate_unsigned_comparison() {
+        let instructions = Codegen::new().generate(&Module {
+            functions: vec![Function {
+                name: "main".to_string(),
+                params: 0,
+                locals: 0,
+                body: vec![Op::ULt],
+            }],
+            strings: Vec::new(),
+        });
+
+        assert!(instructions.ends_with(&[
+            Instruction::Cmp {
+                lhs: Register::Rax,
+                rhs: Register::Rcx
+            },
+            Instruction::SetCC(Condition::Below, Register::Rax),
+            Instruction::MovzxByte(Register::Rax),
+            Instruction::Push(Register::Rax),
+        ]));
+    }
+
+    #[test]
+    fn should_generate_comparison() {
+        let instructions = Codegen::new().generate(&Module {
+            functions: vec![Function {
+                name: "main".to_string(),
+                params: 0,
+                locals: 0,
+                body: vec![Op::Lt],
+            }],
+            strings: Vec::new(),
+        });
+
+        assert!(instructions.ends_with(&[
+            Instruction::Pop(Register::Rcx),
+            Instruction::Pop(Register::Rax),
+            Instruction::Cmp {
+                lhs: Register::Rax,
+                rhs: Register::Rcx
+            },
+            Instruction::SetCC(Condition::Less, Register::Rax),
+            Instruction::MovzxByte(Register::Rax),
+            Instruction::Push(Register::Rax),
+        ]));
+    }
+
+    #[test]
+    fn should_generate_float_comparison() {
+        let instructions = Codegen::new().generate(&Module {
+            functions: vec![Function {
+                name: "main".to_string(),
+                params: 0,
+                locals: 0,
+                body: vec![Op::FLt, Op::FEq],
+            }],
+            strings: Vec::new(),
+        });
+        let operands = [
+            Instruction::Pop(Register::Rcx),
+            Instruction::Pop(Register::Rax),
+            Instruction::MovToFloat {
+                dst: FloatRegister::Xmm0,
+                src: Register::Rax,
+            },
+            Instruction::MovToFloat {
+                dst: FloatRegister::Xmm1,
+                src: Register::Rcx,
+            },
+        ];
+
+        let mut expected = operands.to_vec();
+        expected.extend([
+            Instruction::UComISd {
+                lhs: FloatRegister::Xmm1,
+                rhs: FloatRegister::Xmm0,
+            },
+            Instruction::SetCC(Condition::Above, Register::Rax),
+            Instruction::MovzxByte(Register::Rax),
+            Instruction::Push(Register::Rax),
+        ]);
+        expected.extend(operands);
+        expected.extend([
+            Instruction::UComISd {
+                lhs: FloatRegister::Xmm0,
+                rhs: FloatRegister::Xmm1,
+            },
+            Instruction::SetCC(Condition::Equal, Register::Rax),
+            Instruction::SetCC(Condition::NotParity, Register::Rcx),
+            Instruction::MovzxByte(Register::Rax),
+            Instruction::MovzxByte(Register::Rcx),
+            Instruction::And {
+                dst: Register::Rax,
+                src: Register::Rcx,
+            },
+            Instruction::Push(Register::Rax),
+        ]);
+        assert!(instructions.ends_with(&expected));
+    }
+
+    #[test]
+    fn should_generate_float_conversions() {
+        let instructions = Codegen::new().generate(&Module {
+            functions: vec![Function {
+                name: "main".to_string(),
+                params: 0,
+                locals: 0,
+                body: vec![Op::Float(0.5), Op::FloatToInt, Op::IntToFloat],
+            }],
+            strings: Vec::new(),
+        });
+
+        assert!(instructions.ends_with(&[
+            Instruction::MovImm {
+                dst: Register::Rax,
+                imm: 0.5f64.to_bits() as i64,
+            },
+            Instruction::Push(Register::Rax),
+            Instruction::Pop(Register::Rax),
+            Instruction::MovToFloat {
+                dst: FloatRegister::Xmm0,
+                src: Register::Rax,
+            },
+            Instruction::Cvttsd2si {
+                dst: Register::Rax,
+                src: FloatRegister::Xmm0,
+            },
+            Instruction::Push(Register::Rax),
+            Instruction::Pop(Register::Rax),
+            Instruction::Cvtsi2sd {
+                dst: FloatRegister::Xmm0,
+                src: Register::Rax,
+            },
+            Instruction::MovFromFloat {
+                dst: Register::Rax,
+                src: FloatRegister::Xmm0,
+            },
+            Instruction::Push(Register::Rax),
+        ]));
+    }
+
+    #[test]
+    fn should_generate_jumps() {
+        let instructions = Codegen::new().generate(&Module {
+            functions: vec![Function {
+                name: "main".to_string(),
+                params: 0,
+                locals: 0,
+                body: vec![Op::Label(0), Op::JumpIfZero(0), Op::Jump(0)],
+            }],
+            strings: Vec::new(),
+        });
+
+        assert!(instructions.ends_with(&[
+            Instruction::Label(".Lmain.0".to_string()),
+            Instruction::Pop(Register::Rax),
+            Instruction::Test {
+                lhs: Register::Rax,
+                rhs: Register::Rax
+            },
+            Instruction::Jcc(Condition::Equal, ".Lmain.0".to_string()),
+            Instruction::Jmp(".Lmain.0".to_string()),
+        ]));
+    }
+
+    #[test]
+    fn should_generate_call() {
+        let instructions = Codegen::new().generate(&Module {
+            functions: vec![Function {
+                name: "main".to_string(),
+                params: 0,
+                locals: 0,
+                body: vec![Op::Call {
+                    name: "add".to_string(),
+                    args: 2,
+                }],
+            }],
+            strings: Vec::new(),
+        });
+
+        assert!(instructions.ends_with(&[
+            Instruction::Call("add".to_string()),
+            Instruction::AddImm {
+                dst: Register::Rsp,
+                imm: 16
+            },
+            Instruction::Push(Register::Rax),
+        ]));
+    }
+
+    #[test]
+    fn should_copy_params_to_slots() {
+        let instructions = Codegen::new().generate(&Module {
+            functions: vec![Function {
+                name: "add".to_string(),
+                params: 2,
+                locals: 2,
+                body: Vec::new(),
+            }],
+            strings: Vec::new(),
+        });
+
+        assert!(instructions.ends_with(&[
+            Instruction::Load {
+                dst: Register::Rax,
+                base: Register::Rbp,
+                offset: 24
+            },
+            Instruction::Store {
+                base: Register::Rbp,
+                offset: -8,
+                src: Register::Rax
+            },
+            Instruction::Load {
+                dst: Register::Rax,
+                base: Register::Rbp,
+                offset: 16
+            },
+            Instruction::Store {
+                base: Register::Rbp,
+                offset: -16,
+                src: Register::Rax
+            },
+        ]));
+    }
+
+    #[test]
+    fn should_generate_function_returning_constant() {
+        let instructions = Codegen::new().generate(&Module {
+            functions: vec![Function {
+                name: "main".to_string(),
+                params: 0,
+                locals: 0,
+                body: vec![Op::Const(7), Op::Return],
+            }],
+            strings: Vec::new(),
+        });
+
+        assert!(instructions.ends_with(&[
+            Instruction::Label("main".to_string()),
+            Instruction::Push(Register::Rbp),
+            Instruction::Mov {
+                dst: Register::Rbp,
+                src: Register::Rsp
+            },
+            Instruction::MovImm {
+                dst: Register::Rax,
+                imm: 7
+            },
+            Instruction::Push(Register::Rax),
+            Instruction::Pop(Register::Rax),
+            Instruction::Mov {
+                dst: Register::Rsp,
+                src: Register::Rbp
+            },
+            Instruction::Pop(Register::Rbp),
+            Instruction::Ret,
+        ]));
+    }
+}
@@ -0,0 +1,244 @@
+use std::{
+    fs,
+    io::{self, IsTerminal},
+    path::Path,
+};
+
+use crate::{
+    error::{Error, Warning},
+    span::Span,
+};
+
+/// Render an [`Error`] raised while compiling the file at the given path.
+///
+/// Errors pointing into the source are followed by a [`snippet`] if the file
+/// can still be read. Every error found by the parser is rendered, followed
+/// by a count of them.
+pub fn report(path: &Path, error: &Error) -> String {
+    let source = error.span().and_then(|_| fs::read_to_string(path).ok());
+    let render = |message: String, span: Option<Span>| {
+        let message = heading("error", 31, &message, io::stderr().is_terminal());
+        match span.zip(source.as_deref()) {
+            Some((span, source)) => format!("{message}\n{}", snippet(path, source, &span)),
+            None => message,
+        }
+    };
+
+    match error {
+        Error::Parser(errors) => {
+            let mut reports: Vec<String> = errors
+                .iter()
+                .map(|e| render(e.to_string(), Some(e.span())))
+                .collect();
+            reports.push(render(
+                format!(
+                    "could not compile due to {} error{}",
+                    errors.len(),
+                    if errors.len() == 1 { "" } else { "s" }
+                ),
+                None,
+            ));
+            reports.join("\n\n")
+        }
+        e => render(e.to_string(), e.span()),
+    }
+}
+
+/// Render a [`Warning`] found while compiling the file at the given path.
+///
+/// The warning is followed by a [`snippet`] if the file can still be read.
+pub fn warning(path: &Path, warning: &Warning) -> String {
+    let message = heading(
+        "warning",
+        33,
+        &warning.to_string(),
+        io::stderr().is_terminal(),
+    );
+    match fs::read_to_string(path) {
+        Ok(source) => format!("{message}\n{}", snippet(path, &source, &warning.span())),
+        Err(_) => message,
+    }
+}
+
+/// Render the first line of a diagnostic of the given kind, which is only
+/// painted with the ANSI colour code if it is `coloured`.
+fn heading(kind: &str, colour: u8, message: &str, coloured: bool) -> String {
+    if coloured {
+        format!("\x1b[{colour};1m{kind}:\x1b[0m {message}")
+    } else {
+        format!("{kind}: {message}")
+    }
+}
+
+/// Render the location of a [`Span`] in the style of `rustc`.
+///
+/// ```text
+///  --> main.o2:2:12
+///   |
+/// 2 |     return x
+///   |            ^
+/// ```
+///
+/// Spans covering more than one line are underlined to the end of their first
+/// line, and at least one `^` is always drawn so empty spans remain visible.
+///
+/// The underline is placed using the byte offsets of the span rather than its
+/// columns, so it lines up with the source whatever the tab width.
+pub fn snippet(path: &Path, source: &str, span: &Span) -> String {
+    let line_number = span.start.line;
+    let (line_offset, line) = source
+        .split_inclusive('\n')
+        .scan(0, |offset, line| {
+            let start = *offset;
+            *offset += line.len();
+            Some((start, line.trim_end_matches(['\n', '\r'])))
+        })
+        .nth(line_number.saturating_sub(1))
+        .unwrap_or((source.len(), ""));
+
+    // Count the characters of the line before the byte offset.
+    let chars_before = |offset: usize| {
+        line.char_indices()
+            .take_while(|(i, _)| line_offset + i < offset)
+            .count()
+    };
+    let start = chars_before(span.start.offset);
+    let end = if span.end.line == span.start.line {
+        chars_before(span.end.offset)
+    } else {
+        line.chars().count()
+    };
+    let width = end.saturating_sub(start).max(1);
+
+    // Tabs are kept in the padding so the carets line up with the source.
+    let padding: String = line
+        .chars()
+        .take(start)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+
+    let gutter = " ".repeat(line_number.to_string().len());
+
+    format!(
+        "{gutter}--> {}:{}\n{gutter} |\n{line_number} | {line}\n{gutter} | {padding}{}",
+        path.display(),
+        span.start,
+        "^".repeat(width),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::TermError, span::Position};
+
+    #[test]
+    fn should_count_parser_errors() {
+        let error = Error::Parser(vec![
+            TermError::NoTerm {
+                span: Span::on_line(Position::new(1, 1, 0), 1),
+            }
+            .into(),
+            TermError::NoTerm {
+                span: Span::on_line(Position::new(2, 1, 13), 1),
+            }
+            .into(),
+        ]);
+
+        let report = report(Path::new("missing.o2"), &error);
+
+        assert_eq!(report.matches("no term found to parse").count(), 2);
+        assert!(report.ends_with("could not compile due to 2 errors"));
+    }
+
+    #[test]
+    fn should_render_warning_without_source() {
+        let unreachable = Warning::Unreachable {
+            span: Span::on_line(Position::new(3, 5, 29), 6),
+        };
+
+        assert_eq!(
+            warning(Path::new("missing.o2"), &unreachable),
+            heading(
+                "warning",
+                33,
+                "unreachable statement",
+                io::stderr().is_terminal()
+            )
+        );
+    }
+
+    #[test]
+    fn should_colour_heading_only_for_terminal() {
+        assert_eq!(
+            heading("error", 31, "oops", true),
+            "\x1b[31;1merror:\x1b[0m oops"
+        );
+        assert_eq!(heading("error", 31, "oops", false), "error: oops");
+    }
+
+    #[test]
+    fn should_underline_span() {
+        let source = "int main() {\n    return x;\n}\n";
+        let span = Span::on_line(Position::new(2, 12, 24), 1);
+
+        assert_eq!(
+            snippet(Path::new("main.o2"), source, &span),
+            " --> main.o2:2:12\n  |\n2 |     return x;\n  |            ^"
+        );
+    }
+
+    #[test]
+    fn should_underline_whole_token() {
+        let source = "int main() {\n    return @@@;\n}\n";
+        let span = Span::on_line(Position::new(2, 12, 24), 3);
+
+        assert!(snippet(Path::new("main.o2"), source, &span).ends_with("\n  |            ^^^"));
+    }
+
+    #[test]
+    fn should_underline_empty_span_at_end_of_line() {
+        let source = "return 0";
+        let span = Span::on_line(Position::new(1, 9, 8), 0);
+
+        assert!(snippet(Path::new("main.o2"), source, &span).ends_with("\n  |         ^"));
+    }
+
+    #[test]
+    fn should_underline_to_end_of_line_for_multi_line_span() {
+        let source = "foo(1,\n    2)";
+        let span = Span::new(Position::new(1, 1, 0), Position::new(2, 7, 13));
+
+        assert!(snippet(Path::new("main.o2"), source, &span).ends_with("\n  | ^^^^^^"));
+    }
+
+    #[test]
+    fn should_keep_tabs_in_padding() {
+        let source = "\treturn @;";
+        // The column of `@` with tab stops every 4 columns.
+        let span = Span::on_line(Position::new(1, 12, 8), 1);
+        let snippet = snippet(Path::new("main.o2"), source, &span);
+
+        assert!(snippet.starts_with(" --> main.o2:1:12\n"));
+        assert!(snippet.ends_with("\n  | \t       ^"));
+    }
+
+    #[test]
+    fn should_underline_after_crlf_line_endings() {
+        let source = "int x;\r\n  @;\r\n";
+        let span = Span::on_line(Position::new(2, 3, 10), 1);
+
+        assert!(snippet(Path::new("main.o2"), source, &span).ends_with("\n2 |   @;\n  |   ^"));
+    }
+
+    #[test]
+    fn should_widen_gutter_for_large_line_numbers() {
+        let source = "\n".repeat(9) + "x";
+        let span = Span::on_line(Position::new(10, 1, 9), 1);
+
+        assert_eq!(
+            snippet(Path::new("main.o2"), &source, &span),
+            "  --> main.o2:10:1\n   |\n10 | x\n   | ^"
+        );
+    }
+}
@@ -1,8 +1,12 @@
 use thiserror::Error;
 
 use crate::{
-    lexer::token::TokenType,
-    parser::node::{Statement, Term},
+    codegen::label_function,
+    ir::Module,
+    lexer::token::{Radix, TokenType, Types},
+    parser::node::{Expression, Statement, Term},
+    semantic::resolve::SymbolTable,
+    span::Span,
 };
 
 /// The general error used for the top level program.
@@ -13,31 +17,141 @@ pub enum Error {
     /// Contains the [`LexerError`]
     #[error("{0}")]
     Lexer(#[from] LexerError),
-    /// Contains the [`ParserError`]
+    /// Contains every [`ParserError`] found in the file.
+    #[error("{}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n"))]
+    Parser(Vec<ParserError>),
+    /// Contains the [`SemanticError`]
     #[error("{0}")]
-    Parser(#[from] ParserError),
+    Semantic(#[from] SemanticError),
+    /// Contains the [`TypeError`]
+    #[error("{0}")]
+    Type(#[from] TypeError),
+    /// Contains the [`EntryError`]
+    #[error("{0}")]
+    Entry(#[from] EntryError),
+    /// Contains the [`IrError`]
+    #[error("{0}")]
+    Ir(#[from] IrError),
+    /// Contains the [`CodegenError`]
+    #[error("{0}")]
+    Codegen(#[from] CodegenError),
+    /// Contains the [`InterpreterError`]
+    #[error("{0}")]
+    Interpreter(#[from] InterpreterError),
     /// Contains the [`std::io::Error`]
     #[error("{0}")]
     Io(#[from] std::io::Error),
 }
 
+impl From<Vec<ParserError>> for Error {
+    fn from(errors: Vec<ParserError>) -> Self {
+        Error::Parser(errors)
+    }
+}
+
+impl Error {
+    /// Get the location in the source the error points at if it has one.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Error::Lexer(e) => Some(e.span()),
+            Error::Parser(e) => e.first().map(ParserError::span),
+            Error::Semantic(e) => Some(e.span()),
+            Error::Type(e) => Some(e.span()),
+            Error::Entry(e) => e.span(),
+            Error::Ir(e) => Some(e.span()),
+            Error::Codegen(e) => e.span(),
+            Error::Interpreter(e) => e.span(),
+            Error::Io(_) => None,
+        }
+    }
+}
+
 /// The [`crate::lexer`] errors.
-#[derive(Error, Debug)]
+#[derive(Error, Debug, PartialEq, Eq)]
 pub enum LexerError {
     /// Error representing an unknown character.
     #[error("unknown character '{the_char}'")]
     UnknownCharacter {
         /// Contains the unknown character.
         the_char: char,
-        /// Contains the line number of the unknown character.
-        at_line: usize,
-        /// Contains the column number of the unknown character.
-        at_column: usize,
+        /// Contains the location of the unknown character.
+        span: Span,
+    },
+    /// Error representing an integer literal with digits outside its radix.
+    #[error("malformed {radix} literal '{literal}'")]
+    MalformedLiteral {
+        /// Contains the spelling of the literal.
+        literal: String,
+        /// Contains the radix the literal is written in.
+        radix: Radix,
+        /// Contains the location of the literal.
+        span: Span,
+    },
+    /// Error representing a floating-point literal which cannot be read.
+    #[error("malformed floating-point literal '{literal}'")]
+    MalformedFloat {
+        /// Contains the spelling of the literal.
+        literal: String,
+        /// Contains the location of the literal.
+        span: Span,
+    },
+    /// Error representing a string literal which is not closed on its line.
+    #[error("unterminated string literal")]
+    UnterminatedString {
+        /// Contains the location of the opening `"`.
+        span: Span,
+    },
+    /// Error representing a character literal which is not closed on its
+    /// line.
+    #[error("unterminated character literal")]
+    UnterminatedChar {
+        /// Contains the location of the opening `'`.
+        span: Span,
+    },
+    /// Error representing an unknown or malformed escape sequence.
+    #[error("invalid escape sequence '{escape}'")]
+    InvalidEscape {
+        /// Contains the spelling of the escape sequence.
+        escape: String,
+        /// Contains the location of the escape sequence.
+        span: Span,
+    },
+    /// Error representing a character literal which does not hold exactly
+    /// one character.
+    #[error("character literal must hold exactly one character")]
+    InvalidChar {
+        /// Contains the location of the character literal.
+        span: Span,
+    },
+    /// Error representing a block comment which is never closed.
+    #[error("unterminated block comment")]
+    UnterminatedComment {
+        /// Contains the location of the opening `/*`.
+        span: Span,
     },
 }
 
-#[derive(Error, Debug)]
+impl LexerError {
+    /// Get the location in the source the error points at.
+    pub fn span(&self) -> Span {
+        match self {
+            LexerError::UnknownCharacter { span, .. }
+            | LexerError::MalformedLiteral { span, .. }
+            | LexerError::MalformedFloat { span, .. }
+            | LexerError::UnterminatedString { span }
+            | LexerError::UnterminatedChar { span }
+            | LexerError::InvalidEscape { span, .. }
+            | LexerError::InvalidChar { span }
+            | LexerError::UnterminatedComment { span } => *span,
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
 pub enum ParserError {
+    /// Contains the [`LexerError`] which ended the tokens.
+    #[error("{0}")]
+    Lexer(#[from] LexerError),
     /// Contains the [`StatementError`].
     #[error("{0}")]
     Statement(#[from] StatementError),
@@ -46,36 +160,517 @@ pub enum ParserError {
     Term(#[from] TermError),
 }
 
-pub type ParserResult = Result<(), ParserError>;
+pub type ParserResult = Result<(), Vec<ParserError>>;
 
-#[derive(Error, Debug)]
+impl ParserError {
+    /// Get the location in the source the error points at.
+    pub fn span(&self) -> Span {
+        match self {
+            ParserError::Lexer(e) => e.span(),
+            ParserError::Statement(e) => e.span(),
+            ParserError::Term(e) => e.span(),
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
 pub enum StatementError {
     #[error("{0}")]
     Term(#[from] TermError),
     #[error("{0}")]
     TokenType(#[from] TokenTypeError),
-    #[error("function declaration missing final return statement")]
-    MissingReturn,
+    #[error("variable '{name}' cannot have type void")]
+    VoidVariable { name: String, span: Span },
+    #[error("'break' outside of a loop")]
+    BreakOutsideLoop { span: Span },
+    #[error("'continue' outside of a loop")]
+    ContinueOutsideLoop { span: Span },
+    #[error("statement outside of a function")]
+    OutsideFunction { span: Span },
+    #[error("unexpected {token}, expected {}", one_of(expected))]
+    UnexpectedToken {
+        token: TokenType,
+        expected: Vec<TokenType>,
+        span: Span,
+    },
 }
 
 pub type StatementResult = Result<Statement, StatementError>;
 
+impl StatementError {
+    /// Get the location in the source the error points at.
+    pub fn span(&self) -> Span {
+        use StatementError::*;
+
+        match self {
+            Term(e) => e.span(),
+            TokenType(e) => e.span(),
+            VoidVariable { span, .. }
+            | BreakOutsideLoop { span }
+            | ContinueOutsideLoop { span }
+            | OutsideFunction { span }
+            | UnexpectedToken { span, .. } => *span,
+        }
+    }
+}
+
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum TermError {
     #[error("{0}")]
     TokenType(#[from] TokenTypeError),
+    #[error("{0}")]
+    Lexer(#[from] LexerError),
     #[error("no term found to parse")]
-    NoTerm,
+    NoTerm { span: Span },
+    #[error("literal {value} does not fit in '{target}'")]
+    LiteralOutOfRange {
+        value: String,
+        target: Types,
+        span: Span,
+    },
 }
 
 pub type TermResult = Result<Term, TermError>;
 
+pub type ExpressionResult = Result<Expression, TermError>;
+
+impl TermError {
+    /// Get the location in the source the error points at.
+    pub fn span(&self) -> Span {
+        match self {
+            TermError::TokenType(e) => e.span(),
+            TermError::Lexer(e) => e.span(),
+            TermError::NoTerm { span } | TermError::LiteralOutOfRange { span, .. } => *span,
+        }
+    }
+}
+
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum TokenTypeError {
-    #[error("expected {expected:?} but got {got:?}")]
-    Expected { expected: TokenType, got: TokenType },
-    #[error("expected {expected:?} but got none")]
-    ExpectedGotNone { expected: TokenType },
-    #[error("expected some token but got none")]
-    ExpectedSomeGotNone,
+    #[error("expected {} but found {got}", expected.description())]
+    Expected {
+        expected: TokenType,
+        got: TokenType,
+        span: Span,
+    },
+    #[error("expected {} but reached the end of the file", expected.description())]
+    ExpectedGotNone { expected: TokenType, span: Span },
+    #[error("expected a token but reached the end of the file")]
+    ExpectedSomeGotNone { span: Span },
+}
+
+impl TokenTypeError {
+    /// Get the location in the source the error points at.
+    pub fn span(&self) -> Span {
+        use TokenTypeError::*;
+
+        match self {
+            Expected { span, .. } | ExpectedGotNone { span, .. } | ExpectedSomeGotNone { span } => {
+                *span
+            }
+        }
+    }
 }
+
+/// Describe the expected token types as a list ending `... or ...`.
+fn one_of(expected: &[TokenType]) -> String {
+    let descriptions: Vec<String> = expected.iter().map(TokenType::description).collect();
+    match descriptions.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+        None => "nothing".to_string(),
+    }
+}
+
+/// The [`crate::semantic`] errors.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SemanticError {
+    /// Error representing a call to a function which is never declared.
+    #[error("call to undefined function '{name}'")]
+    UndefinedFunction {
+        /// Contains the name of the function.
+        name: String,
+        /// Contains the location of the call.
+        span: Span,
+    },
+    /// Error representing a call with the wrong number of arguments.
+    #[error("function '{name}' takes {expected} arguments but {got} were given")]
+    ArgumentCount {
+        /// Contains the name of the function.
+        name: String,
+        /// Contains the number of parameters the function declares.
+        expected: usize,
+        /// Contains the number of arguments given.
+        got: usize,
+        /// Contains the location of the call.
+        span: Span,
+    },
+    /// Error representing a function declared more than once.
+    #[error("function '{name}' is already defined")]
+    DuplicateFunction {
+        /// Contains the name of the function.
+        name: String,
+        /// Contains the location of the second definition.
+        span: Span,
+    },
+    /// Error representing a use of a variable which is not in scope.
+    #[error("use of undeclared variable '{name}'")]
+    UndeclaredName {
+        /// Contains the name of the variable.
+        name: String,
+        /// Contains the location of the use.
+        span: Span,
+    },
+    /// Error representing a variable or parameter declared twice in the same
+    /// scope.
+    #[error("variable '{name}' is already declared in this scope")]
+    Redeclared {
+        /// Contains the name of the variable.
+        name: String,
+        /// Contains the location of the second declaration.
+        span: Span,
+    },
+    /// Error representing a function other than a `void` function which
+    /// can reach the end of its body without returning a value.
+    #[error("function '{name}' does not return a value on every path")]
+    MissingReturn {
+        /// Contains the name of the function.
+        name: String,
+        /// Contains the location of the closing brace of the body.
+        span: Span,
+    },
+}
+
+pub type SemanticResult = Result<(), SemanticError>;
+
+impl SemanticError {
+    /// Get the location in the source the error points at.
+    pub fn span(&self) -> Span {
+        match self {
+            SemanticError::UndefinedFunction { span, .. }
+            | SemanticError::ArgumentCount { span, .. }
+            | SemanticError::DuplicateFunction { span, .. }
+            | SemanticError::UndeclaredName { span, .. }
+            | SemanticError::Redeclared { span, .. }
+            | SemanticError::MissingReturn { span, .. } => *span,
+        }
+    }
+}
+
+/// The [`crate::semantic`] warnings.
+///
+/// Warnings are reported without stopping compilation.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum Warning {
+    /// Warning representing a statement which can never be executed.
+    #[error("unreachable statement")]
+    Unreachable {
+        /// Contains the location of the first unreachable statement.
+        span: Span,
+    },
+}
+
+impl Warning {
+    /// Get the location in the source the warning points at.
+    pub fn span(&self) -> Span {
+        match self {
+            Warning::Unreachable { span } => *span,
+        }
+    }
+}
+
+/// The [`crate::semantic::types`] errors.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum TypeError {
+    /// Error representing a value whose type does not convert to the type
+    /// expected where it is used.
+    #[error("mismatched types, expected '{expected}' but found '{got}'")]
+    Mismatch {
+        /// Contains the type expected.
+        expected: Types,
+        /// Contains the type of the value.
+        got: Types,
+        /// Contains the location of the value.
+        span: Span,
+    },
+    /// Error representing an operand whose type the operator cannot be
+    /// applied to.
+    #[error("cannot apply '{op}' to a value of type '{operand}'")]
+    InvalidOperand {
+        /// Contains the spelling of the operator.
+        op: String,
+        /// Contains the type of the operand.
+        operand: Types,
+        /// Contains the location of the operand.
+        span: Span,
+    },
+    /// Error representing an integer literal outside the range of the type
+    /// it is used as.
+    #[error("literal {value} does not fit in '{target}'")]
+    LiteralOutOfRange {
+        /// Contains the value of the literal.
+        value: String,
+        /// Contains the type the literal is used as.
+        target: Types,
+        /// Contains the location of the literal.
+        span: Span,
+    },
+}
+
+pub type TypeResult = Result<(), TypeError>;
+
+impl TypeError {
+    /// Get the location in the source the error points at.
+    pub fn span(&self) -> Span {
+        match self {
+            TypeError::Mismatch { span, .. }
+            | TypeError::InvalidOperand { span, .. }
+            | TypeError::LiteralOutOfRange { span, .. } => *span,
+        }
+    }
+}
+
+/// The [`crate::semantic::entry`] errors.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum EntryError {
+    /// Error representing a program without the entry function.
+    #[error("no '{name}' function found")]
+    Missing {
+        /// Contains the name of the entry function.
+        name: String,
+    },
+    /// Error representing an entry function declared more than once.
+    #[error("'{name}' function defined more than once")]
+    Duplicate {
+        /// Contains the name of the entry function.
+        name: String,
+        /// Contains the location of the second definition.
+        span: Span,
+    },
+    /// Error representing an entry function which does not return an `int`.
+    #[error("'{name}' function must return 'int' but returns '{got}'")]
+    ReturnType {
+        /// Contains the name of the entry function.
+        name: String,
+        /// Contains the return type of the entry function.
+        got: Types,
+        /// Contains the location of the entry function.
+        span: Span,
+    },
+    /// Error representing an entry function which takes parameters, as
+    /// nothing passes it any arguments.
+    #[error("'{name}' function must not take parameters but takes {count}")]
+    Parameters {
+        /// Contains the name of the entry function.
+        name: String,
+        /// Contains the number of parameters the entry function takes.
+        count: usize,
+        /// Contains the location of the parameters.
+        span: Span,
+    },
+}
+
+pub type EntryResult = Result<(), EntryError>;
+
+impl EntryError {
+    /// Get the location in the source the error points at if it has one.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            EntryError::Missing { .. } => None,
+            EntryError::Duplicate { span, .. }
+            | EntryError::ReturnType { span, .. }
+            | EntryError::Parameters { span, .. } => Some(*span),
+        }
+    }
+}
+
+/// The [`crate::ir`] lowering errors.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum IrError {
+    /// Error representing a statement other than a function declaration
+    /// outside of any function.
+    #[error("statement outside of a function")]
+    StatementOutsideFunction {
+        /// Contains the location of the statement.
+        span: Span,
+    },
+    /// Error representing a function declared inside another function.
+    #[error("function '{name}' declared inside another function")]
+    NestedFunction {
+        /// Contains the name of the nested function.
+        name: String,
+        /// Contains the location of the nested function.
+        span: Span,
+    },
+    /// Error representing a `break` or `continue` outside of any loop.
+    #[error("'break' or 'continue' outside of a loop")]
+    OutsideLoop {
+        /// Contains the location of the `break` or `continue`.
+        span: Span,
+    },
+    /// Error representing a `%` with a floating-point operand.
+    #[error("remainder of a floating-point value")]
+    FloatRemainder {
+        /// Contains the location of the remainder.
+        span: Span,
+    },
+}
+
+pub type IrResult = Result<Module, IrError>;
+
+impl IrError {
+    /// Get the location in the source the error points at.
+    pub fn span(&self) -> Span {
+        match self {
+            IrError::StatementOutsideFunction { span }
+            | IrError::NestedFunction { span, .. }
+            | IrError::OutsideLoop { span }
+            | IrError::FloatRemainder { span } => *span,
+        }
+    }
+}
+
+/// The [`crate::codegen`] errors.
+///
+/// Labels are made up by the compiler, so the errors can only point at the
+/// function a label belongs to once they are [`CodegenError::locate`]d.
+#[derive(Error, Debug)]
+pub enum CodegenError {
+    /// Error representing a reference to a label which is never defined.
+    #[error("undefined label '{label}'")]
+    UndefinedLabel {
+        /// Contains the name of the label.
+        label: String,
+        /// Contains the location of the function the label belongs to.
+        span: Option<Span>,
+    },
+    /// Error representing a label defined more than once.
+    #[error("duplicate label '{label}'")]
+    DuplicateLabel {
+        /// Contains the name of the label.
+        label: String,
+        /// Contains the location of the function the label belongs to.
+        span: Option<Span>,
+    },
+    /// Error representing a label too far away for a 32-bit displacement.
+    #[error("label '{label}' is out of range")]
+    JumpTooFar {
+        /// Contains the name of the label.
+        label: String,
+        /// Contains the location of the function the label belongs to.
+        span: Option<Span>,
+    },
+}
+
+impl CodegenError {
+    /// Get the location in the source the error points at if it has one.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            CodegenError::UndefinedLabel { span, .. }
+            | CodegenError::DuplicateLabel { span, .. }
+            | CodegenError::JumpTooFar { span, .. } => *span,
+        }
+    }
+
+    /// Point the error at the declaration of the function its label belongs
+    /// to, if it belongs to one.
+    pub fn locate(mut self, symbols: &SymbolTable) -> Self {
+        let (CodegenError::UndefinedLabel { label, span }
+        | CodegenError::DuplicateLabel { label, span }
+        | CodegenError::JumpTooFar { label, span }) = &mut self;
+        *span = symbols
+            .function(label_function(label))
+            .map(|function| symbols.get(function).span);
+
+        self
+    }
+}
+
+/// The [`crate::interpreter`] errors.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum InterpreterError {
+    /// Error representing a call to a function which is never declared.
+    #[error("undefined function '{name}'")]
+    UndefinedFunction {
+        /// Contains the name of the function.
+        name: String,
+        /// Contains the location of the call, the entry function is called
+        /// from nowhere.
+        span: Option<Span>,
+    },
+    /// Error representing a function which finishes without returning.
+    #[error("function '{name}' finished without returning")]
+    MissingReturn {
+        /// Contains the name of the function.
+        name: String,
+        /// Contains the location of the function.
+        span: Span,
+    },
+    /// Error representing a function declared inside another function.
+    #[error("function '{name}' declared inside another function")]
+    NestedFunction {
+        /// Contains the name of the nested function.
+        name: String,
+        /// Contains the location of the nested function.
+        span: Span,
+    },
+    /// Error representing a division or modulo by zero.
+    #[error("attempt to divide by zero")]
+    DivisionByZero {
+        /// Contains the location of the division.
+        span: Span,
+    },
+    /// Error representing a `break` or `continue` outside of any loop.
+    #[error("'break' or 'continue' outside of a loop")]
+    OutsideLoop {
+        /// Contains the location of the `break` or `continue`.
+        span: Span,
+    },
+    /// Error representing a `%` with a floating-point operand.
+    #[error("remainder of a floating-point value")]
+    FloatRemainder {
+        /// Contains the location of the remainder.
+        span: Span,
+    },
+    /// Error representing a call with the wrong number of arguments.
+    #[error("function '{name}' takes {expected} arguments but {got} were given")]
+    ArgumentCount {
+        /// Contains the name of the function.
+        name: String,
+        /// Contains the number of parameters the function declares.
+        expected: usize,
+        /// Contains the number of arguments given.
+        got: usize,
+        /// Contains the location of the call, the entry function is called
+        /// from nowhere.
+        span: Option<Span>,
+    },
+    /// Error representing a call nested deeper than the interpreter allows.
+    #[error("call to '{name}' exceeds the maximum call depth of {depth}")]
+    CallDepth {
+        /// Contains the name of the function.
+        name: String,
+        /// Contains the maximum number of nested calls.
+        depth: usize,
+        /// Contains the location of the call.
+        span: Span,
+    },
+}
+
+impl InterpreterError {
+    /// Get the location in the source the error points at if it has one.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            InterpreterError::UndefinedFunction { span, .. }
+            | InterpreterError::ArgumentCount { span, .. } => *span,
+            InterpreterError::MissingReturn { span, .. }
+            | InterpreterError::NestedFunction { span, .. }
+            | InterpreterError::DivisionByZero { span }
+            | InterpreterError::OutsideLoop { span }
+            | InterpreterError::FloatRemainder { span }
+            | InterpreterError::CallDepth { span, .. } => Some(*span),
+        }
+    }
+}
+
+pub type InterpreterResult = Result<i64, InterpreterError>;
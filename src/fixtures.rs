@@ -0,0 +1,135 @@
+use crate::{
+    lexer::Lexer,
+    parser::{
+        Parser,
+        node::{Expression, NodeId, Statement, Term},
+    },
+    semantic::{self, resolve::SymbolTable, types::TypeTable},
+    span::{Position, Span},
+};
+
+/// Parse the source into a program, panicking if it does not parse.
+pub fn parse(content: &str) -> Vec<Statement> {
+    let mut prog: Vec<Statement> = Vec::new();
+    Parser::new(Lexer::new(content), &mut prog).parse().unwrap();
+    prog
+}
+
+/// Parse the source into a program, resolve its names and check its types,
+/// panicking if any step fails.
+pub fn analyse(content: &str) -> (Vec<Statement>, SymbolTable, TypeTable) {
+    let prog = parse(content);
+    let symbols = semantic::resolve::resolve(&prog).unwrap();
+    let types = semantic::types::check(&prog, &symbols).unwrap();
+
+    (prog, symbols, types)
+}
+
+/// Get the span of `length` characters from the column of the first line.
+pub fn at(column: usize, length: usize) -> Span {
+    Span::on_line(Position::new(1, column, column - 1), length)
+}
+
+/// Get the id of the node parsed from the location.
+///
+/// # Panics
+///
+/// Panics if no node with an id was parsed from the location.
+pub fn id_at(prog: &[Statement], span: Span) -> NodeId {
+    let mut nodes: Vec<(Span, NodeId)> = Vec::new();
+    prog.iter().for_each(|s| statement_nodes(s, &mut nodes));
+
+    nodes
+        .into_iter()
+        .find_map(|(s, id)| (s == span).then_some(id))
+        .unwrap_or_else(|| panic!("no node at {span:?}"))
+}
+
+fn statement_nodes(statement: &Statement, nodes: &mut Vec<(Span, NodeId)>) {
+    match statement {
+        Statement::FunctionDeclare {
+            params,
+            body,
+            id,
+            span,
+            ..
+        } => {
+            nodes.push((*span, *id));
+            nodes.extend(params.iter().map(|p| (p.span, p.id)));
+            body.iter().for_each(|s| statement_nodes(s, nodes));
+        }
+        Statement::Declare {
+            expression,
+            id,
+            span,
+            ..
+        }
+        | Statement::Assign {
+            expression,
+            id,
+            span,
+            ..
+        } => {
+            nodes.push((*span, *id));
+            expression_nodes(expression, nodes);
+        }
+        Statement::Return {
+            expression: Some(expression),
+            ..
+        }
+        | Statement::Expression { expression, .. } => expression_nodes(expression, nodes),
+        Statement::If {
+            condition,
+            body,
+            else_body,
+            ..
+        } => {
+            expression_nodes(condition, nodes);
+            body.iter()
+                .chain(else_body.iter().flatten())
+                .for_each(|s| statement_nodes(s, nodes));
+        }
+        Statement::While {
+            condition, body, ..
+        } => {
+            expression_nodes(condition, nodes);
+            body.iter().for_each(|s| statement_nodes(s, nodes));
+        }
+        Statement::For {
+            init,
+            condition,
+            step,
+            body,
+            ..
+        } => {
+            init.iter()
+                .chain(step)
+                .for_each(|s| statement_nodes(s, nodes));
+            condition.iter().for_each(|c| expression_nodes(c, nodes));
+            body.iter().for_each(|s| statement_nodes(s, nodes));
+        }
+        Statement::Return {
+            expression: None, ..
+        }
+        | Statement::Break { .. }
+        | Statement::Continue { .. } => {}
+    }
+}
+
+fn expression_nodes(expression: &Expression, nodes: &mut Vec<(Span, NodeId)>) {
+    nodes.push((expression.span(), expression.id()));
+    match expression {
+        Expression::Term(Term::Parenthesised { expression, .. }) => {
+            expression_nodes(expression, nodes)
+        }
+        Expression::Term(Term::Call { args, .. }) => {
+            args.iter().for_each(|arg| expression_nodes(arg, nodes))
+        }
+        Expression::Term(_) => {}
+        Expression::Unary { operand, .. } => expression_nodes(operand, nodes),
+        Expression::Binary { lhs, rhs, .. } => {
+            expression_nodes(lhs, nodes);
+            expression_nodes(rhs, nodes);
+        }
+    }
+}
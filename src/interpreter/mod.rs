@@ -0,0 +1,880 @@
+use std::{collections::HashMap, panic, thread};
+
+use crate::{
+    error::{InterpreterError, InterpreterResult},
+    lexer::token::Types,
+    parser::node::{BinaryOp, Expression, NodeId, Statement, Term, UnaryOp},
+    semantic::{
+        entry::DEFAULT_ENTRY,
+        resolve::{DefId, SymbolTable},
+        types::TypeTable,
+    },
+    span::Span,
+};
+
+/// The maximum number of nested calls, past which the interpreter stops with
+/// an error rather than overflowing its stack.
+pub const MAX_CALL_DEPTH: usize = 10_000;
+
+/// The size of the stack the interpreter runs on, enough for
+/// [`MAX_CALL_DEPTH`] calls even in an unoptimised build.
+const STACK_SIZE: usize = 1 << 30;
+
+/// A representation of a value held by the interpreter.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Value {
+    /// Contains a value of any integer type, wrapping like the compiled code.
+    Int(i64),
+    /// Contains a value of a floating-point type, always held as a double.
+    Float(f64),
+}
+
+impl Value {
+    /// Convert the value between the types like the compiled code does.
+    fn convert(self, from: &Types, to: &Types) -> Self {
+        if *to == Types::Bool {
+            return self.is_true().into();
+        }
+        let value = match self {
+            Value::Int(int) if to.is_float() && from.exceeds_i64() => {
+                Value::Float(int as u64 as f64)
+            }
+            Value::Int(int) if to.is_float() => Value::Float(int as f64),
+            // Out of range values and NaN become the "integer indefinite"
+            // value the processor produces.
+            Value::Float(float) if !to.is_float() && *to != Types::Void => {
+                let limit = 2f64.powi(63);
+                Value::Int(if (-limit..limit).contains(&float) {
+                    float as i64
+                } else {
+                    i64::MIN
+                })
+            }
+            value => value,
+        };
+
+        match value {
+            Value::Float(float) if *to == Types::Float => Value::Float(float as f32 as f64),
+            Value::Int(int) if to.is_integer() => Value::Int(to.wrap(int)),
+            value => value,
+        }
+    }
+
+    /// `true` if the value is non-zero, `false` otherwise.
+    fn is_true(self) -> bool {
+        match self {
+            Value::Int(int) => int != 0,
+            Value::Float(float) => float != 0.0,
+        }
+    }
+
+    /// Get the value of the type as a double.
+    fn as_float(self, from: &Types) -> f64 {
+        match self.convert(from, &Types::Double) {
+            Value::Float(float) => float,
+            Value::Int(_) => unreachable!("converted to a double"),
+        }
+    }
+
+    /// Get the 64 bits the compiled code would hold for the value.
+    fn bits(self) -> i64 {
+        match self {
+            Value::Int(int) => int,
+            Value::Float(float) => float.to_bits() as i64,
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Int(value as i64)
+    }
+}
+
+/// A representation of how control leaves a statement.
+#[derive(Debug, PartialEq)]
+enum Flow {
+    /// Continue with the next statement.
+    Normal,
+    /// Return the value from the enclosing function.
+    Return(Value),
+    /// Exit the innermost enclosing loop, contains the location of the
+    /// `break`.
+    Break(Span),
+    /// Skip to the next iteration of the innermost enclosing loop, contains
+    /// the location of the `continue`.
+    Continue(Span),
+}
+
+/// A tree-walking interpreter over a parsed program.
+#[derive(Debug, PartialEq)]
+pub struct Interpreter<'a> {
+    /// Contains the definition every name resolves to.
+    symbols: &'a SymbolTable,
+    /// Contains the type of every expression.
+    types: &'a TypeTable,
+    /// Contains the declaration of every top level function.
+    functions: HashMap<DefId, &'a Statement>,
+    /// Contains the value of every variable of the current function.
+    frame: HashMap<DefId, Value>,
+    /// Contains the return type of the current function.
+    return_type: &'a Types,
+    /// Contains the number of calls currently being evaluated.
+    depth: usize,
+    /// Contains every distinct string literal evaluated so far, laid out
+    /// back to back with a null terminator as in the data segment.
+    strings: Vec<String>,
+}
+
+impl<'a> Interpreter<'a> {
+    /// Create a new [`Interpreter`] for the given program given the
+    /// definition every name in it resolves to and the type of every
+    /// expression.
+    pub fn new(prog: &'a [Statement], symbols: &'a SymbolTable, types: &'a TypeTable) -> Self {
+        let functions = prog
+            .iter()
+            .filter_map(|s| match s {
+                Statement::FunctionDeclare { id, .. } => Some((symbols.lookup(*id)?, s)),
+                _ => None,
+            })
+            .collect();
+
+        Self {
+            symbols,
+            types,
+            functions,
+            frame: HashMap::new(),
+            return_type: &Types::Int,
+            depth: 0,
+            strings: Vec::new(),
+        }
+    }
+
+    /// Get the address of the string literal within the data segment.
+    fn string(&mut self, value: &str) -> i64 {
+        let index = match self.strings.iter().position(|s| s == value) {
+            Some(index) => index,
+            None => {
+                self.strings.push(value.to_string());
+                self.strings.len() - 1
+            }
+        };
+
+        self.strings[..index]
+            .iter()
+            .map(|s| s.len() as i64 + 1)
+            .sum()
+    }
+
+    /// Evaluate the [`DEFAULT_ENTRY`] function returning its return value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a variable was not resolved.
+    pub fn run(&mut self) -> InterpreterResult {
+        let entry =
+            self.symbols
+                .function(DEFAULT_ENTRY)
+                .ok_or(InterpreterError::UndefinedFunction {
+                    name: DEFAULT_ENTRY.to_string(),
+                    span: None,
+                })?;
+
+        // Every call recurses through several frames of the interpreter, so
+        // it runs on a thread with room for the deepest calls allowed.
+        thread::scope(|scope| {
+            thread::Builder::new()
+                .stack_size(STACK_SIZE)
+                .spawn_scoped(scope, || self.call(entry, &[], None))
+                .expect("interpreter thread is spawned")
+                .join()
+                .unwrap_or_else(|payload| panic::resume_unwind(payload))
+        })
+        .map(Value::bits)
+    }
+
+    /// Evaluate the function with the given id and arguments returning its
+    /// return value, given the location of the call if there is one.
+    fn call(
+        &mut self,
+        function: DefId,
+        args: &[Expression],
+        call_span: Option<Span>,
+    ) -> Result<Value, InterpreterError> {
+        let name = &self.symbols.get(function).name;
+        let Some(Statement::FunctionDeclare {
+            return_type,
+            params,
+            body,
+            span,
+            ..
+        }) = self.functions.get(&function).copied()
+        else {
+            return Err(InterpreterError::UndefinedFunction {
+                name: name.clone(),
+                span: call_span,
+            });
+        };
+        if params.len() != args.len() {
+            return Err(InterpreterError::ArgumentCount {
+                name: name.to_string(),
+                expected: params.len(),
+                got: args.len(),
+                span: call_span,
+            });
+        }
+
+        if let Some(span) = call_span
+            && self.depth >= MAX_CALL_DEPTH
+        {
+            return Err(InterpreterError::CallDepth {
+                name: name.to_string(),
+                depth: MAX_CALL_DEPTH,
+                span,
+            });
+        }
+
+        let mut frame = HashMap::new();
+        for (param, arg) in params.iter().zip(args) {
+            let value = self.eval_as(arg, &param.param_type)?;
+            frame.insert(self.definition(param.id), value);
+        }
+        let caller_frame = std::mem::replace(&mut self.frame, frame);
+        let caller_return_type = std::mem::replace(&mut self.return_type, return_type);
+        self.depth += 1;
+        let res = self.exec_block(body);
+        self.depth -= 1;
+        self.frame = caller_frame;
+        self.return_type = caller_return_type;
+
+        match res? {
+            Flow::Return(value) => Ok(value),
+            Flow::Normal if *return_type == Types::Void => Ok(Value::Int(0)),
+            Flow::Normal => Err(InterpreterError::MissingReturn {
+                name: name.to_string(),
+                span: *span,
+            }),
+            Flow::Break(span) | Flow::Continue(span) => Err(InterpreterError::OutsideLoop { span }),
+        }
+    }
+
+    /// Execute the statements stopping at the first statement which leaves
+    /// the block.
+    fn exec_block(&mut self, body: &[Statement]) -> Result<Flow, InterpreterError> {
+        for statement in body {
+            match self.exec(statement)? {
+                Flow::Normal => {}
+                flow => return Ok(flow),
+            }
+        }
+
+        Ok(Flow::Normal)
+    }
+
+    /// Execute the body of a loop returning `Some` flow if the loop should
+    /// stop.
+    fn exec_loop_body(&mut self, body: &[Statement]) -> Result<Option<Flow>, InterpreterError> {
+        match self.exec_block(body)? {
+            Flow::Normal | Flow::Continue(_) => Ok(None),
+            Flow::Break(_) => Ok(Some(Flow::Normal)),
+            flow @ Flow::Return(_) => Ok(Some(flow)),
+        }
+    }
+
+    /// Execute the statement returning how control leaves it.
+    fn exec(&mut self, statement: &Statement) -> Result<Flow, InterpreterError> {
+        match statement {
+            Statement::FunctionDeclare { name, span, .. } => {
+                Err(InterpreterError::NestedFunction {
+                    name: name.clone(),
+                    span: *span,
+                })
+            }
+            Statement::Return { expression, .. } => match expression {
+                Some(expression) => Ok(Flow::Return(self.eval_as(expression, self.return_type)?)),
+                None => Ok(Flow::Return(Value::Int(0))),
+            },
+            Statement::Declare {
+                var_type,
+                expression,
+                id,
+                ..
+            } => {
+                let value = self.eval_as(expression, var_type)?;
+                let def = self.definition(*id);
+                self.frame.insert(def, value);
+                Ok(Flow::Normal)
+            }
+            Statement::Assign { expression, id, .. } => {
+                let def = self.definition(*id);
+                let value = self.eval_as(expression, self.symbols.get(def).kind.value_type())?;
+                self.frame.insert(def, value);
+                Ok(Flow::Normal)
+            }
+            Statement::If {
+                condition,
+                body,
+                else_body,
+                ..
+            } => {
+                if self.eval(condition)?.is_true() {
+                    self.exec_block(body)
+                } else if let Some(else_body) = else_body {
+                    self.exec_block(else_body)
+                } else {
+                    Ok(Flow::Normal)
+                }
+            }
+            Statement::While {
+                condition, body, ..
+            } => {
+                while self.eval(condition)?.is_true() {
+                    if let Some(flow) = self.exec_loop_body(body)? {
+                        return Ok(flow);
+                    }
+                }
+                Ok(Flow::Normal)
+            }
+            Statement::For {
+                init,
+                condition,
+                step,
+                body,
+                ..
+            } => self.exec_for(init.as_deref(), condition.as_ref(), step.as_deref(), body),
+            Statement::Expression { expression, .. } => {
+                self.eval(expression)?;
+                Ok(Flow::Normal)
+            }
+            Statement::Break { span } => Ok(Flow::Break(*span)),
+            Statement::Continue { span } => Ok(Flow::Continue(*span)),
+        }
+    }
+
+    /// Execute a `for` loop.
+    fn exec_for(
+        &mut self,
+        init: Option<&Statement>,
+        condition: Option<&Expression>,
+        step: Option<&Statement>,
+        body: &[Statement],
+    ) -> Result<Flow, InterpreterError> {
+        if let Some(init) = init {
+            self.exec(init)?;
+        }
+        while match condition {
+            Some(condition) => self.eval(condition)?.is_true(),
+            None => true,
+        } {
+            if let Some(flow) = self.exec_loop_body(body)? {
+                return Ok(flow);
+            }
+            if let Some(step) = step {
+                self.exec(step)?;
+            }
+        }
+
+        Ok(Flow::Normal)
+    }
+
+    /// Get the id of the variable the node names.
+    fn definition(&self, id: NodeId) -> DefId {
+        self.symbols.lookup(id).expect("variable is resolved")
+    }
+
+    /// Get the value of the variable the node names, which is always
+    /// declared before it is used.
+    fn variable(&self, id: NodeId) -> Value {
+        self.frame[&self.definition(id)]
+    }
+
+    /// Evaluate the expression converting its value to the type.
+    fn eval_as(&mut self, expression: &Expression, to: &Types) -> Result<Value, InterpreterError> {
+        let from = self.types.of(expression);
+        Ok(self.eval(expression)?.convert(from, to))
+    }
+
+    /// Evaluate the expression, wrapping on overflow like the compiled code,
+    /// which also wraps `i64::MIN / -1` and stops on a division by zero.
+    fn eval(&mut self, expression: &Expression) -> Result<Value, InterpreterError> {
+        match expression {
+            Expression::Term(term) => self.eval_term(term),
+            Expression::Unary { op, operand, .. } => {
+                let operand_type = self.types.of(operand);
+                let operand = self.eval(operand)?;
+                let value = match (op, operand) {
+                    (UnaryOp::Negate, Value::Int(int)) => Value::Int(int.wrapping_neg()),
+                    (UnaryOp::Negate, Value::Float(float)) => Value::Float(-float),
+                    (UnaryOp::Not, operand) => (!operand.is_true()).into(),
+                };
+                Ok(value.convert(operand_type, self.types.of(expression)))
+            }
+            Expression::Binary {
+                op: BinaryOp::And,
+                lhs,
+                rhs,
+                ..
+            } => Ok((self.eval(lhs)?.is_true() && self.eval(rhs)?.is_true()).into()),
+            Expression::Binary {
+                op: BinaryOp::Or,
+                lhs,
+                rhs,
+                ..
+            } => Ok((self.eval(lhs)?.is_true() || self.eval(rhs)?.is_true()).into()),
+            Expression::Binary {
+                op, lhs, rhs, span, ..
+            } => {
+                let value_type = self.types.of(expression);
+                let (lhs_type, rhs_type) = (self.types.of(lhs), self.types.of(rhs));
+                let (lhs, rhs) = match (self.eval(lhs)?, self.eval(rhs)?) {
+                    (Value::Int(lhs), Value::Int(rhs)) => (lhs, rhs),
+                    // Mixed operands are both converted to a double.
+                    (lhs, rhs) => {
+                        let (lhs, rhs) = (lhs.as_float(lhs_type), rhs.as_float(rhs_type));
+                        let value = Self::eval_float(op, lhs, rhs, *span)?;
+                        return Ok(value.convert(&Types::Double, value_type));
+                    }
+                };
+                // A `u64` only ever meets another `u64`, and smaller unsigned
+                // values give the same result as signed ones.
+                let unsigned = lhs_type.exceeds_i64() || rhs_type.exceeds_i64();
+                let (ulhs, urhs) = (lhs as u64, rhs as u64);
+                let value = match op {
+                    BinaryOp::Add => Value::Int(lhs.wrapping_add(rhs)),
+                    BinaryOp::Subtract => Value::Int(lhs.wrapping_sub(rhs)),
+                    BinaryOp::Multiply => Value::Int(lhs.wrapping_mul(rhs)),
+                    BinaryOp::Divide | BinaryOp::Modulo if rhs == 0 => {
+                        return Err(InterpreterError::DivisionByZero { span: *span });
+                    }
+                    BinaryOp::Divide if unsigned => Value::Int((ulhs / urhs) as i64),
+                    BinaryOp::Modulo if unsigned => Value::Int((ulhs % urhs) as i64),
+                    BinaryOp::Less if unsigned => (ulhs < urhs).into(),
+                    BinaryOp::LessEqual if unsigned => (ulhs <= urhs).into(),
+                    BinaryOp::Greater if unsigned => (ulhs > urhs).into(),
+                    BinaryOp::GreaterEqual if unsigned => (ulhs >= urhs).into(),
+                    BinaryOp::Divide => Value::Int(lhs.wrapping_div(rhs)),
+                    BinaryOp::Modulo => Value::Int(lhs.wrapping_rem(rhs)),
+                    BinaryOp::Equal => (lhs == rhs).into(),
+                    BinaryOp::NotEqual => (lhs != rhs).into(),
+                    BinaryOp::Less => (lhs < rhs).into(),
+                    BinaryOp::LessEqual => (lhs <= rhs).into(),
+                    BinaryOp::Greater => (lhs > rhs).into(),
+                    BinaryOp::GreaterEqual => (lhs >= rhs).into(),
+                    BinaryOp::And | BinaryOp::Or => unreachable!("handled above"),
+                };
+                // Integer arithmetic wraps around at the size of its type.
+                Ok(value.convert(value_type, value_type))
+            }
+        }
+    }
+
+    /// Evaluate the binary operator at the location on two doubles, dividing
+    /// by zero gives an infinity or NaN rather than an error.
+    fn eval_float(
+        op: &BinaryOp,
+        lhs: f64,
+        rhs: f64,
+        span: Span,
+    ) -> Result<Value, InterpreterError> {
+        Ok(match op {
+            BinaryOp::Add => Value::Float(lhs + rhs),
+            BinaryOp::Subtract => Value::Float(lhs - rhs),
+            BinaryOp::Multiply => Value::Float(lhs * rhs),
+            BinaryOp::Divide => Value::Float(lhs / rhs),
+            BinaryOp::Modulo => return Err(InterpreterError::FloatRemainder { span }),
+            BinaryOp::Equal => (lhs == rhs).into(),
+            BinaryOp::NotEqual => (lhs != rhs).into(),
+            BinaryOp::Less => (lhs < rhs).into(),
+            BinaryOp::LessEqual => (lhs <= rhs).into(),
+            BinaryOp::Greater => (lhs > rhs).into(),
+            BinaryOp::GreaterEqual => (lhs >= rhs).into(),
+            BinaryOp::And | BinaryOp::Or => unreachable!("handled by eval"),
+        })
+    }
+
+    fn eval_term(&mut self, term: &Term) -> Result<Value, InterpreterError> {
+        match term {
+            Term::LiteralInteger { value, .. } => Ok(Value::Int(*value as i64)),
+            Term::LiteralFloat { value, .. } => Ok(Value::Float(*value)),
+            Term::LiteralChar { value, .. } => Ok(Value::Int(*value as i64)),
+            Term::LiteralBool { value, .. } => Ok((*value).into()),
+            Term::LiteralString { value, .. } => Ok(Value::Int(self.string(value))),
+            Term::Identifier { id, .. } => Ok(self.variable(*id)),
+            Term::Parenthesised { expression, .. } => self.eval(expression),
+            Term::Call {
+                name,
+                args,
+                id,
+                span,
+            } => {
+                let function = self.symbols.lookup(*id).ok_or_else(|| {
+                    InterpreterError::UndefinedFunction {
+                        name: name.clone(),
+                        span: Some(*span),
+                    }
+                })?;
+                self.call(function, args, Some(*span))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::{analyse, at};
+
+    use super::*;
+
+    /// Run the program in the source after checking it.
+    fn run(content: &str) -> InterpreterResult {
+        let (prog, symbols, types) = analyse(content);
+
+        Interpreter::new(&prog, &symbols, &types).run()
+    }
+
+    /// Run a program returning the value of the expression from `main`.
+    fn eval(expression: &str) -> InterpreterResult {
+        run(&format!("int main() {{ return {expression}; }}"))
+    }
+
+    #[test]
+    fn should_run_main() {
+        assert_eq!(
+            run("int other() { return 1; } int main() { return 7; }"),
+            Ok(7)
+        );
+    }
+
+    #[test]
+    fn should_call_void_function() {
+        assert_eq!(run("void f() {} int main() { f(); return 2; }"), Ok(2));
+    }
+
+    #[test]
+    fn should_stop_at_first_return() {
+        assert_eq!(run("int main() { return 3; return 4; }"), Ok(3));
+    }
+
+    #[test]
+    fn should_error_no_main() {
+        assert!(
+            run("int other() { return 1; }")
+                .is_err_and(|e| matches!(e, InterpreterError::UndefinedFunction { .. }))
+        );
+    }
+
+    #[test]
+    fn should_error_missing_return() {
+        assert!(
+            run("int main() {}")
+                .is_err_and(|e| matches!(e, InterpreterError::MissingReturn { .. }))
+        );
+    }
+
+    #[test]
+    fn should_error_nested_function() {
+        assert_eq!(
+            run("int main() { int inner() { return 0; } }"),
+            Err(InterpreterError::NestedFunction {
+                name: "inner".to_string(),
+                span: at(14, 25),
+            })
+        );
+    }
+
+    #[test]
+    fn should_eval_arithmetic() {
+        assert_eq!(eval("1 + 2 * 3 - 8 / 4 % 3"), Ok(5));
+    }
+
+    #[test]
+    fn should_eval_char_and_string() {
+        let (symbols, types) = (SymbolTable::default(), TypeTable::default());
+        let mut interpreter = Interpreter::new(&[], &symbols, &types);
+
+        assert_eq!(eval("'a'"), Ok(97));
+        // "ab" then "c" are interned one after the other with terminators.
+        assert_eq!(interpreter.string("ab"), 0);
+        assert_eq!(interpreter.string("c"), 3);
+        assert_eq!(interpreter.string("ab"), 0);
+    }
+
+    #[test]
+    fn should_eval_floats() {
+        assert_eq!(eval("1.5 * 3 == 4.5"), Ok(1));
+        assert_eq!(eval("0 - 2.9 < -2.5"), Ok(1));
+        assert_eq!(eval("0.0 / 0.0 == 0.0 / 0.0"), Ok(0));
+        assert_eq!(eval("0.0 / 0.0 != 0.0 / 0.0"), Ok(1));
+        assert_eq!(eval("1.0 / 0.0 > 1000000.0"), Ok(1));
+    }
+
+    #[test]
+    fn should_truncate_floats_to_integers() {
+        assert_eq!(
+            run("int main() { double x = 2.5; int i = x; return i + 10; }"),
+            Ok(12)
+        );
+        assert_eq!(run("int main() { i8 b = -300.9; return b; }"), Ok(-44));
+    }
+
+    #[test]
+    fn should_compare_and_divide_u64_as_unsigned() {
+        let max = "u64 x = 18446744073709551615;";
+        assert_eq!(run(&format!("int main() {{ {max} return x > 1; }}")), Ok(1));
+        assert_eq!(
+            run(&format!(
+                "int main() {{ {max} return x / 2 == 9223372036854775807; }}"
+            )),
+            Ok(1)
+        );
+        assert_eq!(
+            run(&format!("int main() {{ {max} return x % 10 == 5; }}")),
+            Ok(1)
+        );
+        assert_eq!(
+            run(&format!(
+                "int main() {{ {max} double d = x; return d > 1.0; }}"
+            )),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn should_wrap_sized_integers() {
+        assert_eq!(
+            run("int main() { i8 x = 127; x = x + 1; if (x < 0) { return 1; } return 0; }"),
+            Ok(1)
+        );
+        assert_eq!(
+            run("int main() { u32 x = 0; x = x - 1; return x > 0; }"),
+            Ok(1)
+        );
+        assert_eq!(
+            run("int main() { int x = 2147483647; return x + 1 < 0; }"),
+            Ok(1)
+        );
+        assert_eq!(run("int main() { u8 x = 200; return -x; }"), Ok(56));
+    }
+
+    #[test]
+    fn should_eval_bools() {
+        assert_eq!(eval("true"), Ok(1));
+        assert_eq!(eval("!true || false"), Ok(0));
+        assert_eq!(eval("false == 0"), Ok(1));
+    }
+
+    #[test]
+    fn should_convert_values() {
+        assert_eq!(
+            Value::Int(3).convert(&Types::Int, &Types::Double),
+            Value::Float(3.0)
+        );
+        assert_eq!(
+            Value::Float(0.1).convert(&Types::Double, &Types::Float),
+            Value::Float(0.1f32 as f64)
+        );
+        assert_eq!(
+            Value::Float(-2.9).convert(&Types::Double, &Types::I8),
+            Value::Int(-2)
+        );
+        assert_eq!(
+            Value::Float(1e300).convert(&Types::Double, &Types::I64),
+            Value::Int(i64::MIN)
+        );
+        assert_eq!(
+            Value::Float(f64::NAN).convert(&Types::Double, &Types::I64),
+            Value::Int(i64::MIN)
+        );
+        assert_eq!(
+            Value::Float(1e300).convert(&Types::Double, &Types::Int),
+            Value::Int(0)
+        );
+        assert_eq!(
+            Value::Int(300).convert(&Types::Int, &Types::U8),
+            Value::Int(44)
+        );
+        assert_eq!(
+            Value::Int(255).convert(&Types::Int, &Types::I8),
+            Value::Int(-1)
+        );
+        assert_eq!(
+            Value::Int(2).convert(&Types::Int, &Types::Bool),
+            Value::Int(1)
+        );
+        assert_eq!(
+            Value::Float(0.5).convert(&Types::Double, &Types::Bool),
+            Value::Int(1)
+        );
+        assert_eq!(
+            Value::Int(-1).convert(&Types::U64, &Types::Double),
+            Value::Float(u64::MAX as f64)
+        );
+    }
+
+    #[test]
+    fn should_eval_negate_and_parenthesised() {
+        assert_eq!(eval("-(2 - 5)"), Ok(3));
+    }
+
+    #[test]
+    fn should_truncate_division_towards_zero() {
+        assert_eq!(eval("-7 / 2"), Ok(-3));
+        assert_eq!(eval("-7 % 2"), Ok(-1));
+    }
+
+    #[test]
+    fn should_declare_and_assign_variables() {
+        assert_eq!(
+            run("int main() { int x = 5; x = x * 2; int y = x + 1; return y; }"),
+            Ok(11)
+        );
+    }
+
+    #[test]
+    fn should_eval_comparisons() {
+        assert_eq!(eval("1 == 1"), Ok(1));
+        assert_eq!(eval("1 != 1"), Ok(0));
+        assert_eq!(eval("1 < 2"), Ok(1));
+        assert_eq!(eval("2 <= 2"), Ok(1));
+        assert_eq!(eval("1 > 2"), Ok(0));
+        assert_eq!(eval("1 >= 2"), Ok(0));
+    }
+
+    #[test]
+    fn should_eval_logical() {
+        assert_eq!(eval("!5"), Ok(0));
+        assert_eq!(eval("!0"), Ok(1));
+        assert_eq!(eval("5 && 0"), Ok(0));
+        assert_eq!(eval("0 || 5"), Ok(1));
+    }
+
+    #[test]
+    fn should_short_circuit() {
+        assert_eq!(eval("0 && 1 / 0"), Ok(0));
+        assert_eq!(eval("1 || 1 / 0"), Ok(1));
+        assert_eq!(
+            eval("1 && 1 / 0"),
+            Err(InterpreterError::DivisionByZero { span: at(26, 5) })
+        );
+    }
+
+    #[test]
+    fn should_exec_if_else() {
+        let if_else = |condition: &str| {
+            run(&format!(
+                "int main() {{ if ({condition}) {{ return 1; }} else {{ if (0) {{ return 2; }} else {{ return 3; }} }} }}"
+            ))
+        };
+
+        assert_eq!(if_else("1"), Ok(1));
+        assert_eq!(if_else("0"), Ok(3));
+    }
+
+    #[test]
+    fn should_fall_through_if_without_else() {
+        assert_eq!(run("int main() { if (0) { return 1; } return 2; }"), Ok(2));
+    }
+
+    #[test]
+    fn should_exec_while() {
+        assert_eq!(
+            run("int main() { int x = 0; while (x < 5) { x = x + 1; } return x; }"),
+            Ok(5)
+        );
+    }
+
+    #[test]
+    fn should_exec_for_with_break_and_continue() {
+        let content = "
+            int main() {
+                int sum = 0;
+                for (int i = 0; i < 10; i = i + 1) {
+                    if (i == 2) { continue; }
+                    if (i == 5) { break; }
+                    sum = sum + i;
+                }
+                return sum;
+            }
+        ";
+
+        assert_eq!(run(content), Ok(8));
+    }
+
+    #[test]
+    fn should_return_from_inside_loop() {
+        assert_eq!(
+            run("int main() { for (;;) { return 3; } return 4; }"),
+            Ok(3)
+        );
+    }
+
+    #[test]
+    fn should_call_with_arguments() {
+        assert_eq!(
+            run(
+                "int main() { sub(10, 3); return sub(10, 3); } int sub(int a, int b) { return a - b; }"
+            ),
+            Ok(7)
+        );
+    }
+
+    #[test]
+    fn should_error_argument_count() {
+        assert_eq!(
+            run("int main() { return main(1); }"),
+            Err(InterpreterError::ArgumentCount {
+                name: "main".to_string(),
+                expected: 0,
+                got: 1,
+                span: Some(at(21, 7)),
+            })
+        );
+    }
+
+    #[test]
+    fn should_wrap_overflowing_division() {
+        let min = "i64 m = -9223372036854775808; i64 n = -1;";
+        assert_eq!(
+            run(&format!("int main() {{ {min} return m / n == m; }}")),
+            Ok(1)
+        );
+        assert_eq!(
+            run(&format!("int main() {{ {min} return m % n == 0; }}")),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn should_error_division_by_zero() {
+        assert_eq!(
+            eval("1 / 0"),
+            Err(InterpreterError::DivisionByZero { span: at(21, 5) })
+        );
+        assert_eq!(
+            eval("1 % 0"),
+            Err(InterpreterError::DivisionByZero { span: at(21, 5) })
+        );
+    }
+
+    #[test]
+    fn should_error_past_max_call_depth() {
+        let recurse = "int f(int n) { if (n == 0) { return 0; } return 1 + f(n - 1); }";
+        let depth = MAX_CALL_DEPTH as i64 - 1;
+
+        assert_eq!(
+            run(&format!(
+                "{recurse} int main() {{ return f({}); }}",
+                depth - 1
+            )),
+            Ok(depth - 1)
+        );
+        assert_eq!(
+            run(&format!("{recurse} int main() {{ return f({depth}); }}")),
+            Err(InterpreterError::CallDepth {
+                name: "f".to_string(),
+                depth: MAX_CALL_DEPTH,
+                span: at(53, 8),
+            })
+        );
+    }
+}
@@ -0,0 +1,886 @@
+use std::collections::HashMap;
+
+use crate::{
+    error::{IrError, IrResult},
+    ir::{Function, Module, Op},
+    lexer::token::Types,
+    parser::node::{BinaryOp, Expression, NodeId, Parameter, Statement, Term, UnaryOp},
+    semantic::{
+        resolve::{DefId, SymbolTable},
+        types::TypeTable,
+    },
+};
+
+/// Lower the parsed program into a [`Module`] given the definition every
+/// name in it resolves to and the type of every expression.
+///
+/// # Panics
+///
+/// Panics if a variable was not resolved.
+pub fn lower(prog: &[Statement], symbols: &SymbolTable, types: &TypeTable) -> IrResult {
+    let mut functions: Vec<Function> = Vec::new();
+    let mut strings: Vec<String> = Vec::new();
+
+    for statement in prog {
+        match statement {
+            Statement::FunctionDeclare {
+                name,
+                return_type,
+                params,
+                body,
+                ..
+            } => functions.push(FunctionLowerer::new(symbols, types, &mut strings).lower(
+                name,
+                return_type,
+                params,
+                body,
+            )?),
+            statement => {
+                return Err(IrError::StatementOutsideFunction {
+                    span: statement.span(),
+                });
+            }
+        }
+    }
+
+    Ok(Module { functions, strings })
+}
+
+/// A representation of the state while lowering a single function.
+#[derive(Debug, PartialEq)]
+struct FunctionLowerer<'a> {
+    /// Contains the operations lowered so far.
+    ops: Vec<Op>,
+    /// Contains the slot of every variable declared so far.
+    slots: HashMap<DefId, usize>,
+    /// Contains the number of local slots allocated so far.
+    locals: usize,
+    /// Contains the number of labels allocated so far.
+    labels: usize,
+    /// Contains the `continue` and `break` labels of each enclosing loop,
+    /// innermost last.
+    loops: Vec<(usize, usize)>,
+    /// Contains every distinct string literal in the module so far.
+    strings: &'a mut Vec<String>,
+    /// Contains the definition every name resolves to.
+    symbols: &'a SymbolTable,
+    /// Contains the type of every expression.
+    types: &'a TypeTable,
+    /// Contains the return type of the function.
+    return_type: Types,
+}
+
+impl<'a> FunctionLowerer<'a> {
+    fn new(symbols: &'a SymbolTable, types: &'a TypeTable, strings: &'a mut Vec<String>) -> Self {
+        Self {
+            strings,
+            symbols,
+            types,
+            return_type: Types::Int,
+            ops: Vec::new(),
+            slots: HashMap::new(),
+            locals: 0,
+            labels: 0,
+            loops: Vec::new(),
+        }
+    }
+
+    /// Allocate a new label unique within the function.
+    fn new_label(&mut self) -> usize {
+        self.labels += 1;
+        self.labels - 1
+    }
+
+    fn lower(
+        mut self,
+        name: &str,
+        return_type: &Types,
+        params: &[Parameter],
+        body: &[Statement],
+    ) -> Result<Function, IrError> {
+        self.return_type = return_type.clone();
+        for param in params {
+            self.declare(param.id);
+        }
+        self.lower_block(body)?;
+        // A `void` function may run off the end of its body.
+        if *return_type == Types::Void {
+            self.ops.push(Op::Const(0));
+            self.ops.push(Op::Return);
+        }
+
+        Ok(Function {
+            name: name.to_string(),
+            params: params.len(),
+            locals: self.locals,
+            body: self.ops,
+        })
+    }
+
+    /// Allocate a new slot for the variable the node defines.
+    fn declare(&mut self, id: NodeId) -> usize {
+        let def = self.symbols.lookup(id).expect("variable is resolved");
+        let slot = self.locals;
+        self.locals += 1;
+        self.slots.insert(def, slot);
+
+        slot
+    }
+
+    fn lower_block(&mut self, body: &[Statement]) -> Result<(), IrError> {
+        body.iter().try_for_each(|s| self.lower_statement(s))
+    }
+
+    fn lower_statement(&mut self, statement: &Statement) -> Result<(), IrError> {
+        match statement {
+            Statement::FunctionDeclare { name, span, .. } => {
+                return Err(IrError::NestedFunction {
+                    name: name.clone(),
+                    span: *span,
+                });
+            }
+            Statement::Return { expression, .. } => {
+                match expression {
+                    Some(expression) => {
+                        let return_type = self.return_type.clone();
+                        self.lower_expression_as(expression, &return_type)?;
+                    }
+                    None => self.ops.push(Op::Const(0)),
+                }
+                self.ops.push(Op::Return);
+            }
+            Statement::Declare {
+                var_type,
+                expression,
+                id,
+                ..
+            } => {
+                self.lower_expression_as(expression, var_type)?;
+                let slot = self.declare(*id);
+                self.ops.push(Op::Store(slot));
+            }
+            Statement::Assign { expression, id, .. } => {
+                let (slot, var_type) = self.resolve(*id);
+                self.lower_expression_as(expression, &var_type)?;
+                self.ops.push(Op::Store(slot));
+            }
+            Statement::If {
+                condition,
+                body,
+                else_body,
+                ..
+            } => {
+                let else_label = self.new_label();
+                self.lower_condition(condition)?;
+                self.ops.push(Op::JumpIfZero(else_label));
+                self.lower_block(body)?;
+                match else_body {
+                    Some(else_body) => {
+                        let end_label = self.new_label();
+                        self.ops.push(Op::Jump(end_label));
+                        self.ops.push(Op::Label(else_label));
+                        self.lower_block(else_body)?;
+                        self.ops.push(Op::Label(end_label));
+                    }
+                    None => self.ops.push(Op::Label(else_label)),
+                }
+            }
+            Statement::While {
+                condition, body, ..
+            } => {
+                let start_label = self.new_label();
+                let end_label = self.new_label();
+                self.ops.push(Op::Label(start_label));
+                self.lower_condition(condition)?;
+                self.ops.push(Op::JumpIfZero(end_label));
+                self.lower_loop_body(body, start_label, end_label)?;
+                self.ops.push(Op::Jump(start_label));
+                self.ops.push(Op::Label(end_label));
+            }
+            Statement::For {
+                init,
+                condition,
+                step,
+                body,
+                ..
+            } => {
+                let start_label = self.new_label();
+                let step_label = self.new_label();
+                let end_label = self.new_label();
+                if let Some(init) = init {
+                    self.lower_statement(init)?;
+                }
+                self.ops.push(Op::Label(start_label));
+                if let Some(condition) = condition {
+                    self.lower_condition(condition)?;
+                    self.ops.push(Op::JumpIfZero(end_label));
+                }
+                self.lower_loop_body(body, step_label, end_label)?;
+                self.ops.push(Op::Label(step_label));
+                if let Some(step) = step {
+                    self.lower_statement(step)?;
+                }
+                self.ops.push(Op::Jump(start_label));
+                self.ops.push(Op::Label(end_label));
+            }
+            Statement::Expression { expression, .. } => {
+                self.lower_expression(expression)?;
+                self.ops.push(Op::Pop);
+            }
+            Statement::Break { span } => {
+                let (_, break_label) = self
+                    .loops
+                    .last()
+                    .ok_or(IrError::OutsideLoop { span: *span })?;
+                self.ops.push(Op::Jump(*break_label));
+            }
+            Statement::Continue { span } => {
+                let (continue_label, _) = self
+                    .loops
+                    .last()
+                    .ok_or(IrError::OutsideLoop { span: *span })?;
+                self.ops.push(Op::Jump(*continue_label));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Lower the body of a loop which continues at and breaks to the given
+    /// labels.
+    fn lower_loop_body(
+        &mut self,
+        body: &[Statement],
+        continue_label: usize,
+        break_label: usize,
+    ) -> Result<(), IrError> {
+        self.loops.push((continue_label, break_label));
+        let res = self.lower_block(body);
+        self.loops.pop();
+
+        res
+    }
+
+    /// Lower the expression leaving its value converted to the given type on
+    /// top of the value stack.
+    fn lower_expression_as(&mut self, expression: &Expression, to: &Types) -> Result<(), IrError> {
+        self.lower_expression(expression)?;
+        self.convert(self.type_of(expression), to);
+
+        Ok(())
+    }
+
+    /// Lower the expression leaving `0` on top of the value stack if it is
+    /// false and a non-zero value otherwise.
+    fn lower_condition(&mut self, expression: &Expression) -> Result<(), IrError> {
+        self.lower_expression(expression)?;
+        if self.type_of(expression).is_float() {
+            self.ops.push(Op::Float(0.0));
+            self.ops.push(Op::FNe);
+        }
+
+        Ok(())
+    }
+
+    /// Convert the value on top of the value stack between the types.
+    fn convert(&mut self, from: &Types, to: &Types) {
+        if from.is_float() && !to.is_float() && *to != Types::Void {
+            self.ops.push(Op::FloatToInt);
+        } else if !from.is_float() && to.is_float() {
+            self.ops.push(if from.exceeds_i64() {
+                Op::UIntToFloat
+            } else {
+                Op::IntToFloat
+            });
+        }
+        // Every floating-point value is a double until it is stored as a
+        // `float`.
+        if *to == Types::Float && *from != Types::Float {
+            self.ops.push(Op::FloatToSingle);
+        }
+        // Values that may not fit are cut down to the size of the new type.
+        if from.max_value() > to.max_value() || from.min_value() < to.min_value() {
+            self.wrap(to);
+        }
+    }
+
+    /// Cut the integer on top of the value stack down to the size of the type
+    /// if it is smaller than a register.
+    fn wrap(&mut self, to: &Types) {
+        if !to.is_integer() || to.size() >= 8 {
+            return;
+        }
+        match self.ops.last_mut() {
+            // Literals are checked to be in range so a constant just needs its
+            // bits reinterpreted.
+            Some(Op::Const(value)) => *value = to.wrap(*value),
+            _ => self.ops.push(Op::Wrap {
+                size: to.size(),
+                signed: to.is_signed(),
+            }),
+        }
+    }
+
+    /// Lower the expression leaving its value on top of the value stack.
+    fn lower_expression(&mut self, expression: &Expression) -> Result<(), IrError> {
+        match expression {
+            Expression::Term(term) => self.lower_term(term)?,
+            Expression::Unary {
+                op: UnaryOp::Not,
+                operand,
+                ..
+            } => {
+                self.lower_condition(operand)?;
+                self.ops.push(Op::Not);
+            }
+            Expression::Unary { operand, .. } => {
+                self.lower_expression(operand)?;
+                if self.type_of(operand).is_float() {
+                    self.ops.push(Op::FNeg);
+                } else {
+                    self.ops.push(Op::Neg);
+                    self.wrap(self.type_of(expression));
+                }
+            }
+            Expression::Binary {
+                op: op @ (BinaryOp::And | BinaryOp::Or),
+                lhs,
+                rhs,
+                ..
+            } => {
+                // The right operand is skipped once the left decides the
+                // result, leaving `0` for `&&` and `1` for `||`.
+                let (short_circuit, short_value): (fn(usize) -> Op, i64) = match op {
+                    BinaryOp::And => (Op::JumpIfZero, 0),
+                    _ => (Op::JumpIfNotZero, 1),
+                };
+                let short_label = self.new_label();
+                let end_label = self.new_label();
+                self.lower_condition(lhs)?;
+                self.ops.push(short_circuit(short_label));
+                self.lower_condition(rhs)?;
+                self.ops.push(short_circuit(short_label));
+                self.ops.push(Op::Const(1 - short_value));
+                self.ops.push(Op::Jump(end_label));
+                self.ops.push(Op::Label(short_label));
+                self.ops.push(Op::Const(short_value));
+                self.ops.push(Op::Label(end_label));
+            }
+            Expression::Binary { op, lhs, rhs, .. } => {
+                // Mixed operands are both converted to a double.
+                if self.type_of(lhs).is_float() || self.type_of(rhs).is_float() {
+                    self.lower_expression_as(lhs, &Types::Double)?;
+                    self.lower_expression_as(rhs, &Types::Double)?;
+                    self.ops.push(match op {
+                        BinaryOp::Add => Op::FAdd,
+                        BinaryOp::Subtract => Op::FSub,
+                        BinaryOp::Multiply => Op::FMul,
+                        BinaryOp::Divide => Op::FDiv,
+                        BinaryOp::Modulo => {
+                            return Err(IrError::FloatRemainder {
+                                span: expression.span(),
+                            });
+                        }
+                        BinaryOp::Equal => Op::FEq,
+                        BinaryOp::NotEqual => Op::FNe,
+                        BinaryOp::Less => Op::FLt,
+                        BinaryOp::LessEqual => Op::FLe,
+                        BinaryOp::Greater => Op::FGt,
+                        BinaryOp::GreaterEqual => Op::FGe,
+                        BinaryOp::And | BinaryOp::Or => unreachable!("handled above"),
+                    });
+                    // Arithmetic on `float` operands is rounded to a `float`.
+                    if *self.type_of(expression) == Types::Float {
+                        self.ops.push(Op::FloatToSingle);
+                    }
+                    return Ok(());
+                }
+
+                // A `u64` only ever meets another `u64`, and smaller unsigned
+                // values give the same result under the signed operations.
+                let unsigned = self.type_of(lhs).exceeds_i64() || self.type_of(rhs).exceeds_i64();
+                self.lower_expression(lhs)?;
+                self.lower_expression(rhs)?;
+                self.ops.push(match op {
+                    BinaryOp::Add => Op::Add,
+                    BinaryOp::Subtract => Op::Sub,
+                    BinaryOp::Multiply => Op::Mul,
+                    BinaryOp::Divide if unsigned => Op::UDiv,
+                    BinaryOp::Modulo if unsigned => Op::URem,
+                    BinaryOp::Less if unsigned => Op::ULt,
+                    BinaryOp::LessEqual if unsigned => Op::ULe,
+                    BinaryOp::Greater if unsigned => Op::UGt,
+                    BinaryOp::GreaterEqual if unsigned => Op::UGe,
+                    BinaryOp::Divide => Op::Div,
+                    BinaryOp::Modulo => Op::Rem,
+                    BinaryOp::Equal => Op::Eq,
+                    BinaryOp::NotEqual => Op::Ne,
+                    BinaryOp::Less => Op::Lt,
+                    BinaryOp::LessEqual => Op::Le,
+                    BinaryOp::Greater => Op::Gt,
+                    BinaryOp::GreaterEqual => Op::Ge,
+                    BinaryOp::And | BinaryOp::Or => unreachable!("handled above"),
+                });
+                // Integer arithmetic wraps around at the size of its type.
+                self.wrap(self.type_of(expression));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Lower the term leaving its value on top of the value stack.
+    fn lower_term(&mut self, term: &Term) -> Result<(), IrError> {
+        match term {
+            Term::LiteralInteger { value, .. } => self.ops.push(Op::Const(*value as i64)),
+            Term::LiteralChar { value, .. } => self.ops.push(Op::Const(*value as i64)),
+            Term::LiteralBool { value, .. } => self.ops.push(Op::Const(*value as i64)),
+            Term::LiteralFloat { value, .. } => self.ops.push(Op::Float(*value)),
+            Term::LiteralString { value, .. } => {
+                // Equal literals share the same string.
+                let index = match self.strings.iter().position(|s| s == value) {
+                    Some(index) => index,
+                    None => {
+                        self.strings.push(value.clone());
+                        self.strings.len() - 1
+                    }
+                };
+                self.ops.push(Op::Str(index));
+            }
+            Term::Identifier { id, .. } => {
+                let (slot, _) = self.resolve(*id);
+                self.ops.push(Op::Load(slot));
+            }
+            Term::Parenthesised { expression, .. } => self.lower_expression(expression)?,
+            Term::Call { name, args, id, .. } => {
+                let params = self.signature(*id).map(|(_, params)| params);
+                for (index, arg) in args.iter().enumerate() {
+                    match params.and_then(|params| params.get(index)) {
+                        Some(param) => self.lower_expression_as(arg, param)?,
+                        None => self.lower_expression(arg)?,
+                    }
+                }
+                self.ops.push(Op::Call {
+                    name: name.clone(),
+                    args: args.len(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Find the slot and type of the variable the node names.
+    fn resolve(&self, id: NodeId) -> (usize, Types) {
+        let def = self.symbols.lookup(id).expect("variable is resolved");
+
+        (
+            self.slots[&def],
+            self.symbols.get(def).kind.value_type().clone(),
+        )
+    }
+
+    /// Find the return type and parameter types of the function the call
+    /// node names.
+    fn signature(&self, id: NodeId) -> Option<(&'a Types, &'a [Types])> {
+        self.symbols
+            .lookup(id)
+            .and_then(|def| self.symbols.signature(def))
+    }
+
+    /// Get the type of the value the expression leaves on the value stack.
+    fn type_of(&self, expression: &Expression) -> &'a Types {
+        self.types.of(expression)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::{analyse, at};
+
+    use super::*;
+
+    /// Lower the program in the source after checking it.
+    fn lower_source(content: &str) -> IrResult {
+        let (prog, symbols, types) = analyse(content);
+
+        lower(&prog, &symbols, &types)
+    }
+
+    /// Lower the program in the source returning the body of its only
+    /// function.
+    fn body(content: &str) -> Vec<Op> {
+        let mut module = lower_source(content).unwrap();
+        assert_eq!(module.functions.len(), 1);
+
+        module.functions.remove(0).body
+    }
+
+    #[test]
+    fn should_lower_function_returning_literal() {
+        let module = lower_source("int main() { return 7; }").unwrap();
+
+        assert_eq!(
+            module,
+            Module {
+                functions: vec![Function {
+                    name: "main".to_string(),
+                    params: 0,
+                    locals: 0,
+                    body: vec![Op::Const(7), Op::Return],
+                }],
+                strings: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn should_lower_void_function() {
+        assert_eq!(
+            body("void f() { return; }"),
+            vec![Op::Const(0), Op::Return, Op::Const(0), Op::Return]
+        );
+    }
+
+    #[test]
+    fn should_intern_strings() {
+        let module =
+            lower_source(r#"void f() { bool x = "a" == "b"; bool y = "a" == "a"; }"#).unwrap();
+
+        assert_eq!(module.strings, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(
+            module.functions[0].body,
+            vec![
+                Op::Str(0),
+                Op::Str(1),
+                Op::Eq,
+                Op::Store(0),
+                Op::Str(0),
+                Op::Str(0),
+                Op::Eq,
+                Op::Store(1),
+                Op::Const(0),
+                Op::Return
+            ]
+        );
+    }
+
+    #[test]
+    fn should_lower_expression_in_postfix_order() {
+        let wrap = Op::Wrap {
+            size: 4,
+            signed: true,
+        };
+
+        assert_eq!(
+            body("int main() { return -(1 - 2) * 3; }"),
+            vec![
+                Op::Const(1),
+                Op::Const(2),
+                Op::Sub,
+                wrap.clone(),
+                Op::Neg,
+                wrap.clone(),
+                Op::Const(3),
+                Op::Mul,
+                wrap,
+                Op::Return
+            ]
+        );
+    }
+
+    #[test]
+    fn should_lower_variables_to_slots() {
+        let module =
+            lower_source("int main() { int x = 5; int y = x; x = y + 1; return x; }").unwrap();
+
+        assert_eq!(module.functions[0].locals, 2);
+        assert_eq!(
+            module.functions[0].body,
+            vec![
+                Op::Const(5),
+                Op::Store(0),
+                Op::Load(0),
+                Op::Store(1),
+                Op::Load(1),
+                Op::Const(1),
+                Op::Add,
+                Op::Wrap {
+                    size: 4,
+                    signed: true
+                },
+                Op::Store(0),
+                Op::Load(0),
+                Op::Return,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_lower_float_to_int() {
+        assert_eq!(
+            body("int main() { double x = 2.5; int i = x; return i; }"),
+            vec![
+                Op::Float(2.5),
+                Op::Store(0),
+                Op::Load(0),
+                Op::FloatToInt,
+                Op::Wrap {
+                    size: 4,
+                    signed: true
+                },
+                Op::Store(1),
+                Op::Load(1),
+                Op::Return,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_lower_unsigned_operations() {
+        assert_eq!(
+            body("int main() { u64 x = 1; double d = x; return x / 2 > x % 3; }"),
+            vec![
+                Op::Const(1),
+                Op::Store(0),
+                Op::Load(0),
+                Op::UIntToFloat,
+                Op::Store(1),
+                Op::Load(0),
+                Op::Const(2),
+                Op::UDiv,
+                Op::Load(0),
+                Op::Const(3),
+                Op::URem,
+                Op::UGt,
+                Op::Return,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_lower_sized_conversions() {
+        assert_eq!(
+            body("int main() { u8 x = 255; i64 y = x; x = x + 1; return x; }"),
+            vec![
+                Op::Const(255),
+                Op::Store(0),
+                Op::Load(0),
+                Op::Store(1),
+                Op::Load(0),
+                Op::Const(1),
+                Op::Add,
+                Op::Wrap {
+                    size: 1,
+                    signed: false
+                },
+                Op::Store(0),
+                Op::Load(0),
+                Op::Return,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_lower_short_circuit_and() {
+        assert_eq!(
+            body("int main() { return 1 && 2; }"),
+            vec![
+                Op::Const(1),
+                Op::JumpIfZero(0),
+                Op::Const(2),
+                Op::JumpIfZero(0),
+                Op::Const(1),
+                Op::Jump(1),
+                Op::Label(0),
+                Op::Const(0),
+                Op::Label(1),
+                Op::Return,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_lower_short_circuit_or() {
+        assert_eq!(
+            body("int main() { return 1 || 2; }"),
+            vec![
+                Op::Const(1),
+                Op::JumpIfNotZero(0),
+                Op::Const(2),
+                Op::JumpIfNotZero(0),
+                Op::Const(0),
+                Op::Jump(1),
+                Op::Label(0),
+                Op::Const(1),
+                Op::Label(1),
+                Op::Return,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_lower_if_else() {
+        assert_eq!(
+            body("int main() { if (1) { return 2; } else { return 3; } }"),
+            vec![
+                Op::Const(1),
+                Op::JumpIfZero(0),
+                Op::Const(2),
+                Op::Return,
+                Op::Jump(1),
+                Op::Label(0),
+                Op::Const(3),
+                Op::Return,
+                Op::Label(1),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_lower_if_without_else() {
+        assert_eq!(
+            body("void f() { if (1) {} }"),
+            vec![
+                Op::Const(1),
+                Op::JumpIfZero(0),
+                Op::Label(0),
+                Op::Const(0),
+                Op::Return
+            ]
+        );
+    }
+
+    #[test]
+    fn should_lower_while_with_break_and_continue() {
+        assert_eq!(
+            body("void f() { while (1) { continue; break; } }"),
+            vec![
+                Op::Label(0),
+                Op::Const(1),
+                Op::JumpIfZero(1),
+                Op::Jump(0),
+                Op::Jump(1),
+                Op::Jump(0),
+                Op::Label(1),
+                Op::Const(0),
+                Op::Return,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_lower_for() {
+        assert_eq!(
+            body("void f() { for (int i = 0; 1; i = 2) { continue; } }"),
+            vec![
+                Op::Const(0),
+                Op::Store(0),
+                Op::Label(0),
+                Op::Const(1),
+                Op::JumpIfZero(2),
+                Op::Jump(1),
+                Op::Label(1),
+                Op::Const(2),
+                Op::Store(0),
+                Op::Jump(0),
+                Op::Label(2),
+                Op::Const(0),
+                Op::Return,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_lower_params_and_calls() {
+        let module = lower_source("int add(int a, int b) { add(b, 1); return a; }").unwrap();
+
+        assert_eq!(
+            module.functions,
+            vec![Function {
+                name: "add".to_string(),
+                params: 2,
+                locals: 2,
+                body: vec![
+                    Op::Load(1),
+                    Op::Const(1),
+                    Op::Call {
+                        name: "add".to_string(),
+                        args: 2
+                    },
+                    Op::Pop,
+                    Op::Load(0),
+                    Op::Return,
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn should_lower_float_conversions() {
+        assert_eq!(
+            body("void f() { float f = 1; double d = f * 2.5; if (0.5) {} }"),
+            vec![
+                Op::Const(1),
+                Op::IntToFloat,
+                Op::FloatToSingle,
+                Op::Store(0),
+                Op::Load(0),
+                Op::Float(2.5),
+                Op::FMul,
+                Op::FloatToSingle,
+                Op::Store(1),
+                Op::Float(0.5),
+                Op::Float(0.0),
+                Op::FNe,
+                Op::JumpIfZero(0),
+                Op::Label(0),
+                Op::Const(0),
+                Op::Return,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_lower_call_arguments_to_parameter_types() {
+        let module =
+            lower_source("double half(double x) { return x / 2; } double f() { return half(3); }")
+                .unwrap();
+
+        assert_eq!(
+            module.functions[0].body,
+            vec![
+                Op::Load(0),
+                Op::Const(2),
+                Op::IntToFloat,
+                Op::FDiv,
+                Op::Return
+            ]
+        );
+        assert_eq!(
+            module.functions[1].body,
+            vec![
+                Op::Const(3),
+                Op::IntToFloat,
+                Op::Call {
+                    name: "half".to_string(),
+                    args: 1
+                },
+                Op::Return,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_error_nested_function() {
+        let res = lower_source("int main() { int f() { return 1; } return 0; }");
+
+        assert_eq!(
+            res,
+            Err(IrError::NestedFunction {
+                name: "f".to_string(),
+                span: at(14, 21),
+            })
+        );
+    }
+}
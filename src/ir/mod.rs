@@ -0,0 +1,282 @@
+pub mod lower;
+
+use std::fmt::Display;
+
+/// A representation of a lowered program.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Module {
+    /// Contains every function in the program.
+    pub functions: Vec<Function>,
+    /// Contains every distinct string literal in the program.
+    pub strings: Vec<String>,
+}
+
+/// A representation of a lowered function.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Function {
+    /// Contains the name of the function.
+    pub name: String,
+    /// Contains the number of parameters, which occupy the first local
+    /// variable slots in order.
+    pub params: usize,
+    /// Contains the number of local variable slots used by the function.
+    pub locals: usize,
+    /// Contains the operations making up the body of the function.
+    pub body: Vec<Op>,
+}
+
+/// A representation of a single operation on the value stack.
+///
+/// Every value is 64 bits wide, floating-point values are kept as the bits of
+/// a double and are only operated on by the `F` prefixed operations.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Op {
+    /// Push the constant onto the value stack.
+    Const(i64),
+    /// Push the floating-point constant onto the value stack.
+    Float(f64),
+    /// Push the address of the string at the index in [`Module::strings`].
+    Str(usize),
+    /// Pop two values and push their sum.
+    Add,
+    /// Pop two values and push the first minus the second.
+    Sub,
+    /// Pop two values and push their product.
+    Mul,
+    /// Pop two values and push the first divided by the second.
+    Div,
+    /// Pop two values and push the remainder of the first divided by the
+    /// second.
+    Rem,
+    /// Pop two unsigned values and push the first divided by the second.
+    UDiv,
+    /// Pop two unsigned values and push the remainder of the first divided
+    /// by the second.
+    URem,
+    /// Pop a value and push its negation.
+    Neg,
+    /// Pop two floating-point values and push their sum.
+    FAdd,
+    /// Pop two floating-point values and push the first minus the second.
+    FSub,
+    /// Pop two floating-point values and push their product.
+    FMul,
+    /// Pop two floating-point values and push the first divided by the
+    /// second.
+    FDiv,
+    /// Pop a floating-point value and push its negation.
+    FNeg,
+    /// Pop an integer and push it converted to a floating-point value.
+    IntToFloat,
+    /// Pop an unsigned integer and push it converted to a floating-point
+    /// value.
+    UIntToFloat,
+    /// Pop a floating-point value and push it truncated towards zero to an
+    /// integer.
+    FloatToInt,
+    /// Pop a floating-point value and push it rounded to the precision of a
+    /// `float`.
+    FloatToSingle,
+    /// Pop an integer and push its lowest `size` bytes, sign extended if
+    /// `signed` is set and zero extended otherwise.
+    Wrap { size: usize, signed: bool },
+    /// Pop the given number of arguments, pushed in order, and push the
+    /// return value of calling the named function with them.
+    Call { name: String, args: usize },
+    /// Pop and discard a value.
+    Pop,
+    /// Pop two values and push `1` if they are equal, `0` otherwise.
+    Eq,
+    /// Pop two values and push `1` if they are not equal, `0` otherwise.
+    Ne,
+    /// Pop two values and push `1` if the first is less than the second, `0`
+    /// otherwise.
+    Lt,
+    /// Pop two values and push `1` if the first is less than or equal to the
+    /// second, `0` otherwise.
+    Le,
+    /// Pop two values and push `1` if the first is greater than the second,
+    /// `0` otherwise.
+    Gt,
+    /// Pop two values and push `1` if the first is greater than or equal to
+    /// the second, `0` otherwise.
+    Ge,
+    /// Pop two unsigned values and push `1` if the first is less than the
+    /// second, `0` otherwise.
+    ULt,
+    /// Pop two unsigned values and push `1` if the first is less than or
+    /// equal to the second, `0` otherwise.
+    ULe,
+    /// Pop two unsigned values and push `1` if the first is greater than
+    /// the second, `0` otherwise.
+    UGt,
+    /// Pop two unsigned values and push `1` if the first is greater than or
+    /// equal to the second, `0` otherwise.
+    UGe,
+    /// Pop two floating-point values and push `1` if they are equal, `0`
+    /// otherwise.
+    FEq,
+    /// Pop two floating-point values and push `1` if they are not equal, `0`
+    /// otherwise.
+    FNe,
+    /// Pop two floating-point values and push `1` if the first is less than
+    /// the second, `0` otherwise.
+    FLt,
+    /// Pop two floating-point values and push `1` if the first is less than
+    /// or equal to the second, `0` otherwise.
+    FLe,
+    /// Pop two floating-point values and push `1` if the first is greater
+    /// than the second, `0` otherwise.
+    FGt,
+    /// Pop two floating-point values and push `1` if the first is greater
+    /// than or equal to the second, `0` otherwise.
+    FGe,
+    /// Pop a value and push `1` if it is zero, `0` otherwise.
+    Not,
+    /// Marks a position in the function which can be jumped to.
+    Label(usize),
+    /// Continue execution at the label.
+    Jump(usize),
+    /// Pop a value and continue execution at the label if it is zero.
+    JumpIfZero(usize),
+    /// Pop a value and continue execution at the label if it is non-zero.
+    JumpIfNotZero(usize),
+    /// Push the value of the local variable slot.
+    Load(usize),
+    /// Pop a value and store it in the local variable slot.
+    Store(usize),
+    /// Pop the top of the value stack and return it from the function.
+    Return,
+}
+
+impl Display for Module {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, string) in self.strings.iter().enumerate() {
+            writeln!(f, "str {index} = {string:?}")?;
+        }
+        for function in &self.functions {
+            write!(f, "{function}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "fn {} [params: {}, locals: {}]:",
+            self.name, self.params, self.locals
+        )?;
+        for op in &self.body {
+            writeln!(f, "    {op}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Op {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Op::*;
+
+        match self {
+            Const(value) => write!(f, "const {value}"),
+            Float(value) => write!(f, "float {value:?}"),
+            Str(index) => write!(f, "str {index}"),
+            Call { name, args } => write!(f, "call {name} {args}"),
+            Pop => write!(f, "pop"),
+            Add => write!(f, "add"),
+            Sub => write!(f, "sub"),
+            Mul => write!(f, "mul"),
+            Div => write!(f, "div"),
+            Rem => write!(f, "rem"),
+            UDiv => write!(f, "udiv"),
+            URem => write!(f, "urem"),
+            Neg => write!(f, "neg"),
+            FAdd => write!(f, "fadd"),
+            FSub => write!(f, "fsub"),
+            FMul => write!(f, "fmul"),
+            FDiv => write!(f, "fdiv"),
+            FNeg => write!(f, "fneg"),
+            IntToFloat => write!(f, "itof"),
+            UIntToFloat => write!(f, "uitof"),
+            FloatToInt => write!(f, "ftoi"),
+            FloatToSingle => write!(f, "ftos"),
+            Wrap { size, signed } => {
+                write!(f, "wrap {}{}", if *signed { "i" } else { "u" }, size * 8)
+            }
+            FEq => write!(f, "feq"),
+            FNe => write!(f, "fne"),
+            FLt => write!(f, "flt"),
+            FLe => write!(f, "fle"),
+            FGt => write!(f, "fgt"),
+            FGe => write!(f, "fge"),
+            Eq => write!(f, "eq"),
+            Ne => write!(f, "ne"),
+            Lt => write!(f, "lt"),
+            Le => write!(f, "le"),
+            Gt => write!(f, "gt"),
+            Ge => write!(f, "ge"),
+            ULt => write!(f, "ult"),
+            ULe => write!(f, "ule"),
+            UGt => write!(f, "ugt"),
+            UGe => write!(f, "uge"),
+            Not => write!(f, "not"),
+            Label(label) => write!(f, "L{label}:"),
+            Jump(label) => write!(f, "jmp L{label}"),
+            JumpIfZero(label) => write!(f, "jz L{label}"),
+            JumpIfNotZero(label) => write!(f, "jnz L{label}"),
+            Load(slot) => write!(f, "load {slot}"),
+            Store(slot) => write!(f, "store {slot}"),
+            Return => write!(f, "ret"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_display_module() {
+        let module = Module {
+            functions: vec![Function {
+                name: "main".to_string(),
+                params: 0,
+                locals: 1,
+                body: vec![
+                    Op::Const(7),
+                    Op::Neg,
+                    Op::Store(0),
+                    Op::Load(0),
+                    Op::Const(2),
+                    Op::Rem,
+                    Op::Return,
+                ],
+            }],
+            strings: vec!["hi\n".to_string()],
+        };
+
+        assert_eq!(Op::Label(3).to_string(), "L3:");
+        assert_eq!(Op::Str(0).to_string(), "str 0");
+        assert_eq!(Op::Float(1.0).to_string(), "float 1.0");
+        assert_eq!(Op::IntToFloat.to_string(), "itof");
+        assert_eq!(Op::UIntToFloat.to_string(), "uitof");
+        assert_eq!(Op::UGe.to_string(), "uge");
+        assert_eq!(Op::JumpIfZero(3).to_string(), "jz L3");
+        assert_eq!(
+            Op::Call {
+                name: "add".to_string(),
+                args: 2
+            }
+            .to_string(),
+            "call add 2"
+        );
+        assert_eq!(
+            module.to_string(),
+            "str 0 = \"hi\\n\"\nfn main [params: 0, locals: 1]:\n    const 7\n    neg\n    store 0\n    load 0\n    const 2\n    rem\n    ret\n"
+        );
+    }
+}
@@ -1,8 +1,14 @@
 pub mod token;
 
-use token::{ColumnOffset, Keywords, Literals, Symbols, Token, TokenType, Types};
+use token::{Keywords, Literals, Radix, Symbols, Token, TokenType, Types, float_value};
 
-use crate::error::LexerError;
+use crate::{
+    error::LexerError,
+    span::{Position, Span},
+};
+
+/// The number of columns between tab stops unless configured otherwise.
+pub const DEFAULT_TAB_WIDTH: usize = 4;
 
 /// A representation of the current state of the lexer.
 #[derive(Debug, PartialEq, Eq)]
@@ -11,121 +17,464 @@ pub struct LexerState {
     line: usize,
     /// Contains the current column number in the file.
     column: usize,
+    /// Contains the current byte offset in the file.
+    offset: usize,
+    /// Contains the number of columns between tab stops.
+    tab_width: usize,
 }
 
 impl LexerState {
-    /// Create a new [`LexerState`] with default line and column.
+    /// Create a new [`LexerState`] with default line, column, offset and tab
+    /// width.
     pub fn new() -> Self {
-        Self { line: 1, column: 1 }
+        Self::with_tab_width(DEFAULT_TAB_WIDTH)
+    }
+
+    /// Create a new [`LexerState`] with default line, column and offset where
+    /// tabs move the column to the next multiple of `tab_width` columns.
+    pub fn with_tab_width(tab_width: usize) -> Self {
+        Self {
+            line: 1,
+            column: 1,
+            offset: 0,
+            tab_width: tab_width.max(1),
+        }
     }
-}
 
-/// Tokenizes a given input assumving the input is a string representation of
-/// a line in a o2 file.
-pub fn tokenize(
-    content: &str,
-    tokens: &mut Vec<Token>,
-    state: &mut LexerState,
-) -> Result<(), LexerError> {
-    let mut index: usize = 0;
-    let mut buffer = String::new();
-    let content_vec: Vec<_> = content.chars().collect();
-    let content_size: usize = content_vec.len();
-
-    while index < content_size {
-        let mut c = content_vec[index];
+    /// Get the [`Position`] the lexer is currently at.
+    fn position(&self) -> Position {
+        Position::new(self.line, self.column, self.offset)
+    }
 
+    /// Move the state past the given character.
+    fn advance(&mut self, c: char) {
+        self.offset += c.len_utf8();
         match c {
-            '(' => push_inc_col(tokens, state, Symbols::OpenParen),
-            ')' => push_inc_col(tokens, state, Symbols::CloseParen),
-            '{' => push_inc_col(tokens, state, Symbols::OpenCurly),
-            '}' => push_inc_col(tokens, state, Symbols::CloseCurly),
-            ';' => push_inc_col(tokens, state, Symbols::SemiColon),
-            ' ' => {
-                state.column += 1;
+            '\n' => {
+                self.line += 1;
+                self.column = 1;
             }
-            _ => {
-                if c.is_ascii_alphabetic() || c == '_' {
-                    loop {
-                        if !c.is_ascii_alphanumeric() && c != '_' {
-                            break;
-                        }
-                        buffer.push(c);
-                        index += 1;
-                        state.column += 1;
+            '\t' => {
+                let width = self.tab_width;
+                self.column = (self.column - 1) / width * width + width + 1;
+            }
+            _ => self.column += 1,
+        }
+    }
+}
 
-                        if index >= content_size {
-                            break;
-                        }
-                        c = content_vec[index];
-                    }
-                    match buffer.as_str() {
-                        "return" => push_col_offset(tokens, state, Keywords::Return),
-                        "int" => push_col_offset(tokens, state, Types::Int),
-                        some => {
-                            push_col_offset(tokens, state, TokenType::SomeName(some.to_string()))
+impl Default for LexerState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Splits the source of an o2 file into [`Token`]s.
+///
+/// Tokens are lexed lazily as the lexer is iterated, and iteration ends after
+/// the first [`LexerError`].
+#[derive(Debug)]
+pub struct Lexer<'a> {
+    source: &'a str,
+    state: LexerState,
+}
+
+impl<'a> Lexer<'a> {
+    /// Create a new [`Lexer`] at the start of the given source.
+    pub fn new(source: &'a str) -> Self {
+        Self::with_tab_width(source, DEFAULT_TAB_WIDTH)
+    }
+
+    /// Create a new [`Lexer`] at the start of the given source which places
+    /// tab stops every `tab_width` columns.
+    pub fn with_tab_width(source: &'a str, tab_width: usize) -> Self {
+        Self {
+            source,
+            state: LexerState::with_tab_width(tab_width),
+        }
+    }
+
+    /// Get the character `ahead` characters past the current one.
+    fn peek(&self, ahead: usize) -> Option<char> {
+        self.source[self.state.offset..].chars().nth(ahead)
+    }
+
+    /// Consume the current character.
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek(0)?;
+        self.state.advance(c);
+        Some(c)
+    }
+
+    /// Consume characters for as long as they match the predicate.
+    fn bump_while(&mut self, predicate: impl Fn(char) -> bool) {
+        while self.peek(0).is_some_and(&predicate) {
+            self.bump();
+        }
+    }
+
+    /// Skip any whitespace and comments before the next token.
+    fn skip_trivia(&mut self) -> Result<(), LexerError> {
+        loop {
+            match (self.peek(0), self.peek(1)) {
+                (Some(c), _) if c.is_whitespace() => {
+                    self.bump();
+                }
+                (Some('/'), Some('/')) => self.bump_while(|c| c != '\n'),
+                (Some('/'), Some('*')) => self.skip_block_comment()?,
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Skip a block comment, including any block comments nested inside it.
+    fn skip_block_comment(&mut self) -> Result<(), LexerError> {
+        let start = self.state.position();
+        let mut depth: usize = 0;
+
+        loop {
+            match (self.peek(0), self.peek(1)) {
+                (Some('/'), Some('*')) => depth += 1,
+                (Some('*'), Some('/')) => depth -= 1,
+                (Some(_), _) => {
+                    self.bump();
+                    continue;
+                }
+                (None, _) => {
+                    return Err(LexerError::UnterminatedComment {
+                        span: Span::on_line(start, 2),
+                    });
+                }
+            }
+            self.bump();
+            self.bump();
+
+            if depth == 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Lex the token starting at the current character, if there is one.
+    fn lex_token(&mut self) -> Result<Option<Token>, LexerError> {
+        let start = self.state.position();
+        let Some(c) = self.bump() else {
+            return Ok(None);
+        };
+
+        let token_type: TokenType = match c {
+            '(' => Symbols::OpenParen.into(),
+            ')' => Symbols::CloseParen.into(),
+            '{' => Symbols::OpenCurly.into(),
+            '}' => Symbols::CloseCurly.into(),
+            ';' => Symbols::SemiColon.into(),
+            ',' => Symbols::Comma.into(),
+            '+' => Symbols::Plus.into(),
+            '-' => Symbols::Minus.into(),
+            '*' => Symbols::Asterisk.into(),
+            '/' => Symbols::ForwardSlash.into(),
+            '%' => Symbols::Percent.into(),
+            '=' | '!' | '<' | '>' | '&' | '|' => {
+                if let Some(symbol) = self.peek(0).and_then(|next| double_symbol(c, next)) {
+                    self.bump();
+                    symbol.into()
+                } else {
+                    match c {
+                        '=' => Symbols::Equals.into(),
+                        '!' => Symbols::Exclamation.into(),
+                        '<' => Symbols::LessThan.into(),
+                        '>' => Symbols::GreaterThan.into(),
+                        _ => {
+                            return Err(LexerError::UnknownCharacter {
+                                the_char: c,
+                                span: Span::on_line(start, 1),
+                            });
                         }
                     }
-                } else if c.is_ascii_digit() {
-                    loop {
-                        if !c.is_ascii_digit() {
-                            break;
-                        }
-                        buffer.push(c);
-                        index += 1;
-                        state.column += 1;
+                }
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                self.bump_while(|c| c.is_ascii_alphanumeric() || c == '_');
+                match &self.source[start.offset..self.state.offset] {
+                    "return" => Keywords::Return.into(),
+                    "if" => Keywords::If.into(),
+                    "else" => Keywords::Else.into(),
+                    "while" => Keywords::While.into(),
+                    "for" => Keywords::For.into(),
+                    "break" => Keywords::Break.into(),
+                    "continue" => Keywords::Continue.into(),
+                    "true" => Literals::Bool(true).into(),
+                    "false" => Literals::Bool(false).into(),
+                    some => match Types::from_keyword(some) {
+                        Some(t) => t.into(),
+                        None => TokenType::SomeName(some.to_string()),
+                    },
+                }
+            }
+            '"' => Literals::String(self.lex_quoted('"', start)?).into(),
+            '\'' => {
+                let literal = self.lex_quoted('\'', start)?;
+                unescape_char(&literal, start)?;
+                Literals::Char(literal).into()
+            }
+            c if c.is_ascii_digit() => self.lex_number(c, start)?,
+            '.' if self.peek(0).is_some_and(|c| c.is_ascii_digit()) => self.lex_number(c, start)?,
+            c => {
+                return Err(LexerError::UnknownCharacter {
+                    the_char: c,
+                    span: Span::new(start, self.state.position()),
+                });
+            }
+        };
+
+        Ok(Some(Token::new(
+            token_type,
+            start.line,
+            start.column,
+            start.offset,
+        )))
+    }
 
-                        if index >= content_size {
-                            break;
-                        }
-                        c = content_vec[index];
+    /// Lex the rest of a literal starting at `start` whose opening `quote`
+    /// has already been consumed, returning its spelling between the quotes.
+    ///
+    /// String literals may span several lines, as the lexer keeps its place
+    /// across a newline wherever it is, but a character literal must be
+    /// closed on the line it starts on. Every escape sequence in a literal
+    /// must be valid.
+    fn lex_quoted(&mut self, quote: char, start: Position) -> Result<String, LexerError> {
+        let contents = self.state.position();
+        let allowed = |c: char| c != '\n' || quote == '"';
+
+        loop {
+            match self.peek(0) {
+                Some(c) if c == quote => break,
+                Some('\\') => {
+                    self.bump();
+                    // An escaped quote does not close the literal.
+                    if self.peek(0).is_some_and(allowed) {
+                        self.bump();
                     }
-                    push_col_offset(tokens, state, Literals::Integer("99".to_string()));
-                } else {
-                    return Err(LexerError::UnknownCharacter {
-                        the_char: c,
-                        at_line: state.line,
-                        at_column: state.column,
+                }
+                Some(c) if allowed(c) => {
+                    self.bump();
+                }
+                _ => {
+                    let span = Span::on_line(start, 1);
+                    return Err(match quote {
+                        '"' => LexerError::UnterminatedString { span },
+                        _ => LexerError::UnterminatedChar { span },
                     });
                 }
             }
         }
 
-        if buffer.is_empty() {
-            index += 1;
-        } else {
-            buffer.clear();
+        let literal = self.source[contents.offset..self.state.offset].to_string();
+        self.bump();
+        unescape(&literal, contents)?;
+
+        Ok(literal)
+    }
+
+    /// Lex the rest of an integer or floating-point literal starting at
+    /// `start` whose first character has already been consumed.
+    fn lex_number(&mut self, first: char, start: Position) -> Result<TokenType, LexerError> {
+        let radix = Radix::from_prefix(first, self.peek(0));
+        if radix != Radix::Decimal {
+            self.bump();
+        } else if self.lex_float_tail(first) {
+            self.bump_while(|c| c.is_ascii_alphanumeric() || c == '_');
+            let literal = &self.source[start.offset..self.state.offset];
+
+            return match float_value(literal) {
+                Some(_) => Ok(Literals::Float(literal.to_string()).into()),
+                None => Err(LexerError::MalformedFloat {
+                    literal: literal.to_string(),
+                    span: Span::new(start, self.state.position()),
+                }),
+            };
+        }
+
+        // Letters are consumed too so a bad digit is reported as part of the
+        // literal rather than starting a name.
+        self.bump_while(|c| c.is_ascii_alphanumeric() || c == '_');
+        let literal = &self.source[start.offset..self.state.offset];
+
+        if !radix.is_valid(&literal[radix.prefix().len()..]) {
+            return Err(LexerError::MalformedLiteral {
+                literal: literal.to_string(),
+                radix,
+                span: Span::new(start, self.state.position()),
+            });
+        }
+
+        Ok(Literals::Integer(literal.to_string(), radix).into())
+    }
+
+    /// Consume the digits of a decimal literal along with its fraction and
+    /// exponent, returning `true` if it had either and so is floating-point.
+    fn lex_float_tail(&mut self, first: char) -> bool {
+        let is_digit = |c: char| c.is_ascii_digit() || c == '_';
+        let mut float = first == '.';
+
+        self.bump_while(is_digit);
+        // 1.5
+        //  ^^
+        if !float && self.peek(0) == Some('.') && self.peek(1).is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+            self.bump_while(is_digit);
+            float = true;
+        }
+        // 1e-9
+        //  ^^^
+        let exponent = match (self.peek(0), self.peek(1), self.peek(2)) {
+            (Some('e' | 'E'), Some(c), _) if c.is_ascii_digit() => 1,
+            (Some('e' | 'E'), Some('+' | '-'), Some(c)) if c.is_ascii_digit() => 2,
+            _ => 0,
+        };
+        if exponent > 0 {
+            for _ in 0..exponent {
+                self.bump();
+            }
+            self.bump_while(is_digit);
+            float = true;
+        }
+
+        float
+    }
+}
+
+impl Iterator for Lexer<'_> {
+    type Item = Result<Token, LexerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = self
+            .skip_trivia()
+            .and_then(|()| self.lex_token())
+            .transpose();
+
+        // Nothing sensible can be lexed past an error.
+        if let Some(Err(_)) = token {
+            self.state.offset = self.source.len();
+        }
+
+        token
+    }
+}
+
+/// Tokenize the whole source of an o2 file.
+pub fn tokenize(source: &str) -> Result<Vec<Token>, LexerError> {
+    Lexer::new(source).collect()
+}
+
+/// Get the value of the spelling of a string literal between its quotes,
+/// which starts at `start`, replacing every escape sequence.
+pub fn unescape(literal: &str, start: Position) -> Result<String, LexerError> {
+    let mut value = String::new();
+    let mut chars = literal.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+
+        let escaped = match chars.next().map(|(_, c)| c) {
+            Some('n') => Some('\n'),
+            Some('t') => Some('\t'),
+            Some('\\') => Some('\\'),
+            Some('"') => Some('"'),
+            Some('\'') => Some('\''),
+            Some('0') => Some('\0'),
+            Some('x') => {
+                let digits = take_hex_digits(&mut chars, 2);
+                // Only ASCII may be written as a single byte.
+                u8::from_str_radix(&digits, 16)
+                    .ok()
+                    .filter(|byte| digits.len() == 2 && byte.is_ascii())
+                    .map(char::from)
+            }
+            Some('u') if chars.next_if(|(_, c)| *c == '{').is_some() => {
+                let digits = take_hex_digits(&mut chars, 6);
+                chars
+                    .next_if(|(_, c)| *c == '}')
+                    .and_then(|_| u32::from_str_radix(&digits, 16).ok())
+                    .and_then(char::from_u32)
+            }
+            _ => None,
+        };
+
+        match escaped {
+            Some(c) => value.push(c),
+            None => {
+                let end = chars.peek().map_or(literal.len(), |(end, _)| *end);
+                // A string literal may span several lines.
+                let position = |index: usize| {
+                    let before = &literal[..index];
+                    match before.rfind('\n') {
+                        Some(newline) => Position::new(
+                            start.line + before.matches('\n').count(),
+                            before[newline + 1..].chars().count() + 1,
+                            start.offset + index,
+                        ),
+                        None => Position::new(
+                            start.line,
+                            start.column + before.chars().count(),
+                            start.offset + index,
+                        ),
+                    }
+                };
+
+                return Err(LexerError::InvalidEscape {
+                    escape: literal[index..end].to_string(),
+                    span: Span::new(position(index), position(end)),
+                });
+            }
         }
     }
 
-    state.line += 1;
-    state.column = 1;
+    Ok(value)
+}
 
-    Ok(())
+/// Get the value of the spelling of a character literal between its quotes,
+/// where the literal including its quotes starts at `start`.
+pub fn unescape_char(literal: &str, start: Position) -> Result<char, LexerError> {
+    let contents = Position::new(start.line, start.column + 1, start.offset + 1);
+    let value = unescape(literal, contents)?;
+    let mut chars = value.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(LexerError::InvalidChar {
+            span: Span::on_line(start, literal.chars().count() + 2),
+        }),
+    }
 }
 
-/// Push a [`Token`] with the given [`TokenType`] into the `tokens` vec and
-/// increment the column by 1
-fn push_inc_col(
-    tokens: &mut Vec<Token>,
-    state: &mut LexerState,
-    token_type: impl std::convert::Into<TokenType>,
-) {
-    tokens.push(Token::new(token_type, state.line, state.column));
-    state.column += 1;
+/// Consume up to `max` hexadecimal digits returning them.
+fn take_hex_digits(chars: &mut std::iter::Peekable<std::str::CharIndices>, max: usize) -> String {
+    let mut digits = String::new();
+    while digits.len() < max {
+        match chars.next_if(|(_, c)| c.is_ascii_hexdigit()) {
+            Some((_, c)) => digits.push(c),
+            None => break,
+        }
+    }
+    digits
 }
 
-/// Push a [`Token`] with a given [`TokenType`] into the `tokens` vec and
-/// increment offset the set column by the given offset.
-fn push_col_offset(
-    tokens: &mut Vec<Token>,
-    state: &mut LexerState,
-    token_type: impl std::convert::Into<TokenType>,
-) {
-    let t: TokenType = token_type.into();
-    let offset = t.to_col_offset();
-
-    tokens.push(Token::new(t, state.line, state.column - offset))
+/// Get the two character symbol starting with `first` followed by `second`
+/// if there is one.
+fn double_symbol(first: char, second: char) -> Option<Symbols> {
+    match (first, second) {
+        ('=', '=') => Some(Symbols::DoubleEquals),
+        ('!', '=') => Some(Symbols::NotEquals),
+        ('<', '=') => Some(Symbols::LessThanOrEqual),
+        ('>', '=') => Some(Symbols::GreaterThanOrEqual),
+        ('&', '&') => Some(Symbols::DoubleAmpersand),
+        ('|', '|') => Some(Symbols::DoublePipe),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -134,238 +483,585 @@ mod tests {
 
     #[test]
     fn should_create_new_lexer_state() {
-        assert_eq!(LexerState { line: 1, column: 1 }, LexerState::new());
+        assert_eq!(
+            LexerState {
+                line: 1,
+                column: 1,
+                offset: 0,
+                tab_width: DEFAULT_TAB_WIDTH,
+            },
+            LexerState::new()
+        );
     }
 
     #[test]
-    fn should_push_and_inc_column() {
-        let mut tokens: Vec<Token> = Vec::new();
+    fn should_advance_state() {
         let mut state = LexerState::new();
 
-        push_inc_col(&mut tokens, &mut state, Symbols::OpenParen);
+        state.advance('a');
+        state.advance('⫯');
+        assert_eq!(state.position(), Position::new(1, 3, 4));
 
-        assert_eq!(
-            tokens.first().unwrap(),
-            &Token::new(Symbols::OpenParen, 1, 1)
-        );
-        assert_eq!(state.line, 1);
-        assert_eq!(state.column, 2);
+        state.advance('\n');
+        assert_eq!(state.position(), Position::new(2, 1, 5));
     }
 
     #[test]
-    fn should_push_col_offset() {
-        let mut tokens: Vec<Token> = Vec::new();
-        let mut state = LexerState { line: 1, column: 7 };
+    fn should_advance_to_tab_stops() {
+        let mut state = LexerState::with_tab_width(8);
+
+        state.advance('\t');
+        assert_eq!(state.position(), Position::new(1, 9, 1));
+
+        state.advance('a');
+        state.advance('\t');
+        assert_eq!(state.position(), Position::new(1, 17, 3));
 
-        push_col_offset(&mut tokens, &mut state, Keywords::Return);
+        assert_eq!(LexerState::with_tab_width(0).tab_width, 1);
+    }
+
+    #[test]
+    fn should_lex_lazily() {
+        let mut lexer = Lexer::new("( &");
 
-        assert_eq!(tokens.first().unwrap(), &Token::new(Keywords::Return, 1, 1));
-        assert_eq!(state.line, 1);
-        assert_eq!(state.column, 7);
+        assert_eq!(
+            lexer.next(),
+            Some(Ok(Token::new(Symbols::OpenParen, 1, 1, 0)))
+        );
+        assert!(matches!(lexer.next(), Some(Err(_))));
+        assert_eq!(lexer.next(), None);
     }
 
     #[test]
     fn should_tokenize_open_paren() {
         let content = "(";
-        let mut tokens: Vec<Token> = Vec::new();
-        let mut state = LexerState::new();
-
-        let res = tokenize(content, &mut tokens, &mut state);
+        let tokens = tokenize(content).unwrap();
 
-        assert!(res.is_ok());
         assert_eq!(
             tokens.first().unwrap(),
-            &Token::new(Symbols::OpenParen, 1, 1)
+            &Token::new(Symbols::OpenParen, 1, 1, 0)
         );
     }
 
     #[test]
     fn should_tokenize_close_paren() {
         let content = ")";
-        let mut tokens: Vec<Token> = Vec::new();
-        let mut state = LexerState::new();
-
-        let res = tokenize(content, &mut tokens, &mut state);
+        let tokens = tokenize(content).unwrap();
 
-        assert!(res.is_ok());
         assert_eq!(
             tokens.first().unwrap(),
-            &Token::new(Symbols::CloseParen, 1, 1)
+            &Token::new(Symbols::CloseParen, 1, 1, 0)
         );
     }
 
     #[test]
     fn should_tokenize_open_curly() {
         let content = "{";
-        let mut tokens: Vec<Token> = Vec::new();
-        let mut state = LexerState::new();
-
-        let res = tokenize(content, &mut tokens, &mut state);
+        let tokens = tokenize(content).unwrap();
 
-        assert!(res.is_ok());
         assert_eq!(
             tokens.first().unwrap(),
-            &Token::new(Symbols::OpenCurly, 1, 1)
+            &Token::new(Symbols::OpenCurly, 1, 1, 0)
         );
     }
 
     #[test]
     fn should_tokenize_close_curly() {
         let content = "}";
-        let mut tokens: Vec<Token> = Vec::new();
-        let mut state = LexerState::new();
+        let tokens = tokenize(content).unwrap();
 
-        let res = tokenize(content, &mut tokens, &mut state);
-
-        assert!(res.is_ok());
         assert_eq!(
             tokens.first().unwrap(),
-            &Token::new(Symbols::CloseCurly, 1, 1)
+            &Token::new(Symbols::CloseCurly, 1, 1, 0)
         );
     }
 
     #[test]
     fn should_tokenize_semi_colon() {
         let content = ";";
-        let mut tokens: Vec<Token> = Vec::new();
-        let mut state = LexerState::new();
+        let tokens = tokenize(content).unwrap();
 
-        let res = tokenize(content, &mut tokens, &mut state);
+        assert_eq!(
+            tokens.first().unwrap(),
+            &Token::new(Symbols::SemiColon, 1, 1, 0)
+        );
+    }
+
+    #[test]
+    fn should_tokenize_comma() {
+        let content = "a, b";
+        let tokens = tokenize(content).unwrap();
+
+        assert_eq!(tokens[1], Token::new(Symbols::Comma, 1, 2, 1));
+    }
+
+    #[test]
+    fn should_tokenize_arithmetic_symbols() {
+        let content = "+-*/%";
+        let tokens = tokenize(content).unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(Symbols::Plus, 1, 1, 0),
+                Token::new(Symbols::Minus, 1, 2, 1),
+                Token::new(Symbols::Asterisk, 1, 3, 2),
+                Token::new(Symbols::ForwardSlash, 1, 4, 3),
+                Token::new(Symbols::Percent, 1, 5, 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_tokenize_equals() {
+        let content = "=";
+        let tokens = tokenize(content).unwrap();
 
-        assert!(res.is_ok());
         assert_eq!(
             tokens.first().unwrap(),
-            &Token::new(Symbols::SemiColon, 1, 1)
+            &Token::new(Symbols::Equals, 1, 1, 0)
+        );
+    }
+
+    #[test]
+    fn should_tokenize_comparison_and_logical_symbols() {
+        let content = "== != < <= > >= && || !";
+        let tokens = tokenize(content).unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(Symbols::DoubleEquals, 1, 1, 0),
+                Token::new(Symbols::NotEquals, 1, 4, 3),
+                Token::new(Symbols::LessThan, 1, 7, 6),
+                Token::new(Symbols::LessThanOrEqual, 1, 9, 8),
+                Token::new(Symbols::GreaterThan, 1, 12, 11),
+                Token::new(Symbols::GreaterThanOrEqual, 1, 14, 13),
+                Token::new(Symbols::DoubleAmpersand, 1, 17, 16),
+                Token::new(Symbols::DoublePipe, 1, 20, 19),
+                Token::new(Symbols::Exclamation, 1, 23, 22),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_tokenize_adjacent_symbols() {
+        let content = "!!=<";
+        let tokens = tokenize(content).unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(Symbols::Exclamation, 1, 1, 0),
+                Token::new(Symbols::NotEquals, 1, 2, 1),
+                Token::new(Symbols::LessThan, 1, 4, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_tokenize_single_ampersand_to_err() {
+        let content = "&";
+        let res = tokenize(content);
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn should_skip_line_comment() {
+        let content = "1 / 2 // 3 / 4";
+        let tokens = tokenize(content).unwrap();
+
+        assert_eq!(tokens.len(), 3);
+        assert_eq!(tokens[1], Token::new(Symbols::ForwardSlash, 1, 3, 2));
+    }
+
+    #[test]
+    fn should_skip_nested_block_comment() {
+        let content = "( /* a /* b */ c */ )";
+        let tokens = tokenize(content).unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(Symbols::OpenParen, 1, 1, 0),
+                Token::new(Symbols::CloseParen, 1, 21, 20),
+            ]
         );
     }
 
+    #[test]
+    fn should_skip_block_comment_across_lines() {
+        let content = "( /* a\nb */ )";
+        let tokens = tokenize(content).unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(Symbols::OpenParen, 1, 1, 0),
+                Token::new(Symbols::CloseParen, 2, 6, 12),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_error_unterminated_block_comment() {
+        let content = "( /* a /* b */\n)";
+
+        assert_eq!(
+            tokenize(content),
+            Err(LexerError::UnterminatedComment {
+                span: Span::on_line(Position::new(1, 3, 2), 2)
+            })
+        );
+    }
+
+    #[test]
+    fn should_tokenize_keyword_if_else() {
+        let content = "if else";
+        let tokens = tokenize(content).unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(Keywords::If, 1, 1, 0),
+                Token::new(Keywords::Else, 1, 4, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_tokenize_keyword_loops() {
+        let content = "while for break continue";
+        let tokens = tokenize(content).unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(Keywords::While, 1, 1, 0),
+                Token::new(Keywords::For, 1, 7, 6),
+                Token::new(Keywords::Break, 1, 11, 10),
+                Token::new(Keywords::Continue, 1, 17, 16),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_tokenize_expression() {
+        let content = "1 + 2";
+        let tokens = tokenize(content).unwrap();
+
+        assert_eq!(tokens.len(), 3);
+        assert_eq!(tokens[1], Token::new(Symbols::Plus, 1, 3, 2));
+    }
+
     #[test]
     fn should_tokenize_keyword_return() {
         let content = "return";
-        let mut tokens: Vec<Token> = Vec::new();
-        let mut state = LexerState::new();
+        let tokens = tokenize(content).unwrap();
 
-        let res = tokenize(content, &mut tokens, &mut state);
-
-        assert!(res.is_ok());
         assert_eq!(tokens.len(), 1);
-        assert_eq!(tokens.first().unwrap(), &Token::new(Keywords::Return, 1, 1));
-        assert_eq!(state.line, 2);
-        assert_eq!(state.column, 1);
+        assert_eq!(
+            tokens.first().unwrap(),
+            &Token::new(Keywords::Return, 1, 1, 0)
+        );
     }
 
     #[test]
     fn should_tokenize_keyword_int() {
         let content = "int";
-        let mut tokens: Vec<Token> = Vec::new();
-        let mut state = LexerState::new();
+        let tokens = tokenize(content).unwrap();
 
-        let res = tokenize(content, &mut tokens, &mut state);
-
-        assert!(res.is_ok());
         assert_eq!(tokens.len(), 1);
-        assert_eq!(tokens.first().unwrap(), &Token::new(Types::Int, 1, 1));
-        assert_eq!(state.line, 2);
-        assert_eq!(state.column, 1);
+        assert_eq!(tokens.first().unwrap(), &Token::new(Types::Int, 1, 1, 0));
+    }
+
+    #[test]
+    fn should_tokenize_types() {
+        let content = "bool char void i8 u64 i128";
+        let tokens = tokenize(content).unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(Types::Bool, 1, 1, 0),
+                Token::new(Types::Char, 1, 6, 5),
+                Token::new(Types::Void, 1, 11, 10),
+                Token::new(Types::I8, 1, 16, 15),
+                Token::new(Types::U64, 1, 19, 18),
+                Token::new(TokenType::SomeName("i128".to_string()), 1, 23, 22),
+            ]
+        );
     }
 
     #[test]
     fn should_tokenize_some_name() {
         let content = "name";
-        let mut tokens: Vec<Token> = Vec::new();
-        let mut state = LexerState::new();
-
-        let res = tokenize(content, &mut tokens, &mut state);
+        let tokens = tokenize(content).unwrap();
 
-        assert!(res.is_ok());
         assert_eq!(tokens.len(), 1);
         assert_eq!(
             tokens.first().unwrap(),
-            &Token::new(TokenType::SomeName("name".to_string()), 1, 1)
+            &Token::new(TokenType::SomeName("name".to_string()), 1, 1, 0)
         );
-        assert_eq!(state.line, 2);
-        assert_eq!(state.column, 1);
     }
 
     #[test]
     fn should_tokenize_some_name_digit() {
         let content = "n9me";
-        let mut tokens: Vec<Token> = Vec::new();
-        let mut state = LexerState::new();
-
-        let res = tokenize(content, &mut tokens, &mut state);
+        let tokens = tokenize(content).unwrap();
 
-        assert!(res.is_ok());
         assert_eq!(tokens.len(), 1);
         assert_eq!(
             tokens.first().unwrap(),
-            &Token::new(TokenType::SomeName("n9me".to_string()), 1, 1)
+            &Token::new(TokenType::SomeName("n9me".to_string()), 1, 1, 0)
         );
-        assert_eq!(state.line, 2);
-        assert_eq!(state.column, 1);
     }
 
     #[test]
     fn should_tokenize_some_name_underscore_prefix() {
         let content = "_name";
-        let mut tokens: Vec<Token> = Vec::new();
-        let mut state = LexerState::new();
-
-        let res = tokenize(content, &mut tokens, &mut state);
+        let tokens = tokenize(content).unwrap();
 
-        assert!(res.is_ok());
         assert_eq!(tokens.len(), 1);
         assert_eq!(
             tokens.first().unwrap(),
-            &Token::new(TokenType::SomeName("_name".to_string()), 1, 1)
+            &Token::new(TokenType::SomeName("_name".to_string()), 1, 1, 0)
         );
-        assert_eq!(state.line, 2);
-        assert_eq!(state.column, 1);
     }
 
     #[test]
     fn should_tokenize_some_name_underscore() {
         let content = "n_ame";
-        let mut tokens: Vec<Token> = Vec::new();
-        let mut state = LexerState::new();
+        let tokens = tokenize(content).unwrap();
 
-        let res = tokenize(content, &mut tokens, &mut state);
-
-        assert!(res.is_ok());
         assert_eq!(tokens.len(), 1);
         assert_eq!(
             tokens.first().unwrap(),
-            &Token::new(TokenType::SomeName("n_ame".to_string()), 1, 1)
+            &Token::new(TokenType::SomeName("n_ame".to_string()), 1, 1, 0)
         );
-        assert_eq!(state.line, 2);
-        assert_eq!(state.column, 1);
     }
 
     #[test]
     fn should_tokenize_digit() {
         let content = "99";
-        let mut tokens: Vec<Token> = Vec::new();
-        let mut state = LexerState::new();
+        let tokens = tokenize(content).unwrap();
 
-        let res = tokenize(content, &mut tokens, &mut state);
-
-        assert!(res.is_ok());
         assert_eq!(tokens.len(), 1);
         assert_eq!(
             tokens.first().unwrap(),
-            &Token::new(Literals::Integer("99".to_string()), 1, 1)
+            &Token::new(Literals::Integer("99".to_string(), Radix::Decimal), 1, 1, 0)
+        );
+    }
+
+    #[test]
+    fn should_tokenize_digit_text() {
+        let content = "0 007 5000000000";
+        let tokens = tokenize(content).unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(Literals::Integer("0".to_string(), Radix::Decimal), 1, 1, 0),
+                Token::new(
+                    Literals::Integer("007".to_string(), Radix::Decimal),
+                    1,
+                    3,
+                    2
+                ),
+                Token::new(
+                    Literals::Integer("5000000000".to_string(), Radix::Decimal),
+                    1,
+                    7,
+                    6
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_tokenize_radix_digits() {
+        let content = "0xFF 0o755 0b1010 1_000_000 0x_ff_";
+        let tokens = tokenize(content).unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(
+                    Literals::Integer("0xFF".to_string(), Radix::Hexadecimal),
+                    1,
+                    1,
+                    0
+                ),
+                Token::new(
+                    Literals::Integer("0o755".to_string(), Radix::Octal),
+                    1,
+                    6,
+                    5
+                ),
+                Token::new(
+                    Literals::Integer("0b1010".to_string(), Radix::Binary),
+                    1,
+                    12,
+                    11
+                ),
+                Token::new(
+                    Literals::Integer("1_000_000".to_string(), Radix::Decimal),
+                    1,
+                    19,
+                    18
+                ),
+                Token::new(
+                    Literals::Integer("0x_ff_".to_string(), Radix::Hexadecimal),
+                    1,
+                    29,
+                    28
+                ),
+            ]
         );
-        assert_eq!(state.line, 2);
-        assert_eq!(state.column, 1);
+    }
+
+    #[test]
+    fn should_tokenize_malformed_digit_to_err() {
+        assert_eq!(
+            tokenize("x = 0xG1;"),
+            Err(LexerError::MalformedLiteral {
+                literal: "0xG1".to_string(),
+                radix: Radix::Hexadecimal,
+                span: Span::on_line(Position::new(1, 5, 4), 4),
+            })
+        );
+        assert!(tokenize("0b102").is_err());
+        assert!(tokenize("0x").is_err());
+        assert!(tokenize("12ab").is_err());
+    }
+
+    #[test]
+    fn should_tokenize_floats() {
+        let content = "3.14 1e-9 .5 1_000.5E+3";
+        let tokens = tokenize(content).unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(Literals::Float("3.14".to_string()), 1, 1, 0),
+                Token::new(Literals::Float("1e-9".to_string()), 1, 6, 5),
+                Token::new(Literals::Float(".5".to_string()), 1, 11, 10),
+                Token::new(Literals::Float("1_000.5E+3".to_string()), 1, 14, 13),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_tokenize_malformed_float_to_err() {
+        assert_eq!(
+            tokenize("x = 1.5x;"),
+            Err(LexerError::MalformedFloat {
+                literal: "1.5x".to_string(),
+                span: Span::on_line(Position::new(1, 5, 4), 4),
+            })
+        );
+        assert!(tokenize("1e").is_err());
+        assert!(tokenize("1.5e+").is_err());
+    }
+
+    #[test]
+    fn should_tokenize_string_and_char() {
+        let content = r#""a\"b" '\n'"#;
+        let tokens = tokenize(content).unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(Literals::String(r#"a\"b"#.to_string()), 1, 1, 0),
+                Token::new(Literals::Char(r"\n".to_string()), 1, 8, 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_tokenize_bool() {
+        let tokens = tokenize("true false truest").unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(Literals::Bool(true), 1, 1, 0),
+                Token::new(Literals::Bool(false), 1, 6, 5),
+                Token::new(TokenType::SomeName("truest".to_string()), 1, 12, 11),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_tokenize_string_over_several_lines() {
+        let content = "\"a\nb\" x";
+        let tokens = tokenize(content).unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(Literals::String("a\nb".to_string()), 1, 1, 0),
+                Token::new(TokenType::SomeName("x".to_string()), 2, 4, 6),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_unescape() {
+        let start = Position::new(1, 1, 0);
+
+        assert_eq!(
+            unescape(r#"\n\t\\\"\'\0"#, start),
+            Ok("\n\t\\\"'\0".to_string())
+        );
+        assert_eq!(unescape(r"\x41\u{e9}", start), Ok("Aé".to_string()));
+        assert_eq!(
+            unescape(r"ab\q", start),
+            Err(LexerError::InvalidEscape {
+                escape: r"\q".to_string(),
+                span: Span::on_line(Position::new(1, 3, 2), 2),
+            })
+        );
+        assert_eq!(
+            unescape("a\n b\\q", start),
+            Err(LexerError::InvalidEscape {
+                escape: r"\q".to_string(),
+                span: Span::on_line(Position::new(2, 3, 4), 2),
+            })
+        );
+        assert!(unescape(r"\xFF", start).is_err());
+        assert!(unescape(r"\u{110000}", start).is_err());
+    }
+
+    #[test]
+    fn should_tokenize_string_and_char_to_err() {
+        assert_eq!(
+            tokenize("x = \"abc\n;"),
+            Err(LexerError::UnterminatedString {
+                span: Span::on_line(Position::new(1, 5, 4), 1),
+            })
+        );
+        assert_eq!(
+            tokenize("x = 'a\n';"),
+            Err(LexerError::UnterminatedChar {
+                span: Span::on_line(Position::new(1, 5, 4), 1),
+            })
+        );
+        assert_eq!(
+            tokenize("'ab'"),
+            Err(LexerError::InvalidChar {
+                span: Span::on_line(Position::new(1, 1, 0), 4),
+            })
+        );
+        assert!(tokenize("'").is_err());
+        assert!(tokenize("''").is_err());
     }
 
     #[test]
     fn should_tokenize_to_err() {
         let content = "⫯";
-        let mut tokens: Vec<Token> = Vec::new();
-        let mut state = LexerState::new();
-
-        let res = tokenize(content, &mut tokens, &mut state);
+        let res = tokenize(content);
 
         assert!(res.is_err());
     }
@@ -373,21 +1069,70 @@ mod tests {
     #[test]
     fn should_tokenize() {
         let content = "int main() {\n    return 0;\n}";
-        let mut tokens: Vec<Token> = Vec::new();
-        let mut state = LexerState::new();
+        let tokens = tokenize(content).unwrap();
 
-        for line in content.split('\n') {
-            let res = tokenize(line, &mut tokens, &mut state);
-            assert!(res.is_ok());
+        assert_eq!(tokens.len(), 9);
+        assert_eq!(tokens.first().unwrap(), &Token::new(Types::Int, 1, 1, 0));
+        assert_eq!(
+            tokens.last().unwrap(),
+            &Token::new(Symbols::CloseCurly, 3, 1, 27)
+        );
+    }
+
+    #[test]
+    fn should_tokenize_unicode_whitespace() {
+        let content = "int\u{a0}x\u{2003};\u{b}\u{c}\r";
+        let tokens = tokenize(content).unwrap();
+
+        assert_eq!(tokens.len(), 3);
+        assert_eq!(tokens[2], Token::new(Symbols::SemiColon, 1, 7, 9));
+    }
+
+    #[test]
+    fn should_tokenize_mixed_indentation_fixture() {
+        let content = include_str!("fixtures/mixed_indentation.o2");
+        let lex = |tab_width: usize| -> Vec<Token> {
+            Lexer::with_tab_width(content, tab_width)
+                .collect::<Result<_, _>>()
+                .unwrap()
+        };
+
+        let tokens = lex(4);
+        assert!(tokens.contains(&Token::new(Types::Int, 2, 5, 14)));
+        assert!(tokens.contains(&Token::new(Types::Int, 3, 9, 30)));
+        assert!(tokens.contains(&Token::new(Keywords::Return, 4, 9, 46)));
+        assert!(tokens.contains(&Token::new(Symbols::Plus, 4, 21, 55)));
+
+        let tokens = lex(8);
+        assert!(tokens.contains(&Token::new(Types::Int, 2, 9, 14)));
+        assert!(tokens.contains(&Token::new(Types::Int, 3, 9, 30)));
+        assert!(tokens.contains(&Token::new(Keywords::Return, 4, 13, 46)));
+        assert!(tokens.contains(&Token::new(Symbols::Plus, 4, 25, 55)));
+    }
+
+    #[test]
+    fn should_tokenize_crlf_fixture() {
+        let content = include_str!("fixtures/crlf.o2");
+        let tokens = tokenize(content).unwrap();
+        let expected = tokenize(&content.replace("\r\n", "\n")).unwrap();
+
+        // Only the offsets differ, by one for every line ending before.
+        assert_eq!(tokens.len(), expected.len());
+        for (token, expected) in tokens.iter().zip(&expected) {
+            assert_eq!(token.token_type, expected.token_type);
+            assert_eq!((token.line, token.column), (expected.line, expected.column));
+            assert_eq!(token.offset, expected.offset + token.line - 1);
         }
+    }
+
+    #[test]
+    fn should_tokenize_crlf_line_endings() {
+        let content = "int x;\r\n}\r\n";
+        let tokens = tokenize(content).unwrap();
 
-        assert_eq!(tokens.len(), 9);
-        assert_eq!(tokens.first().unwrap(), &Token::new(Types::Int, 1, 1));
         assert_eq!(
             tokens.last().unwrap(),
-            &Token::new(Symbols::CloseCurly, 3, 1)
+            &Token::new(Symbols::CloseCurly, 2, 1, 8)
         );
-        assert_eq!(state.line, 4);
-        assert_eq!(state.column, 1);
     }
 }
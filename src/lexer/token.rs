@@ -1,12 +1,130 @@
+use crate::span::{Position, Span};
+
 pub trait ColumnOffset {
     fn to_col_offset(&self) -> usize;
 }
 
+/// A representation of the base an integer literal is written in.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Radix {
+    /// Represents a literal prefixed with `0b`.
+    Binary,
+    /// Represents a literal prefixed with `0o`.
+    Octal,
+    /// Represents a literal without a prefix.
+    Decimal,
+    /// Represents a literal prefixed with `0x`.
+    Hexadecimal,
+}
+
+impl Radix {
+    /// Get the radix of the literal starting with `first` followed by
+    /// `second`.
+    pub fn from_prefix(first: char, second: Option<char>) -> Self {
+        match (first, second) {
+            ('0', Some('b')) => Radix::Binary,
+            ('0', Some('o')) => Radix::Octal,
+            ('0', Some('x')) => Radix::Hexadecimal,
+            _ => Radix::Decimal,
+        }
+    }
+
+    /// Get the prefix written before literals of the radix.
+    pub fn prefix(&self) -> &'static str {
+        use Radix::*;
+
+        match self {
+            Binary => "0b",
+            Octal => "0o",
+            Decimal => "",
+            Hexadecimal => "0x",
+        }
+    }
+
+    /// Get the number of values a single digit of the radix can represent.
+    pub fn base(&self) -> u32 {
+        use Radix::*;
+
+        match self {
+            Binary => 2,
+            Octal => 8,
+            Decimal => 10,
+            Hexadecimal => 16,
+        }
+    }
+
+    /// `true` if the spelling of a literal after its prefix is made up of
+    /// digits of the radix and `_` separators with at least one digit,
+    /// `false` otherwise.
+    pub fn is_valid(&self, digits: &str) -> bool {
+        digits.chars().any(|c| c != '_')
+            && digits.chars().all(|c| c == '_' || c.is_digit(self.base()))
+    }
+
+    /// Get the value of a valid literal of the radix, or [`None`] if it is
+    /// too large to be represented.
+    pub fn value(&self, literal: &str) -> Option<u64> {
+        let digits: String = literal[self.prefix().len()..]
+            .chars()
+            .filter(|c| *c != '_')
+            .collect();
+
+        u64::from_str_radix(&digits, self.base()).ok()
+    }
+}
+
+/// Get the value of the spelling of a decimal floating-point literal if it is
+/// well formed.
+pub fn float_value(literal: &str) -> Option<f64> {
+    if !literal
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '_' | '.' | 'e' | 'E' | '+' | '-'))
+    {
+        return None;
+    }
+
+    literal.replace('_', "").parse().ok()
+}
+
+impl std::fmt::Display for Radix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Radix::*;
+
+        match self {
+            Binary => write!(f, "binary"),
+            Octal => write!(f, "octal"),
+            Decimal => write!(f, "decimal"),
+            Hexadecimal => write!(f, "hexadecimal"),
+        }
+    }
+}
+
 /// A representation of any literals.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Literals {
-    /// Contains the value of the integer literal.
-    Integer(String),
+    /// Contains the spelling of the integer literal and its [`Radix`].
+    Integer(String, Radix),
+    /// Contains the spelling of the floating-point literal.
+    Float(String),
+    /// Contains the spelling of the string literal between its quotes.
+    String(String),
+    /// Contains the spelling of the character literal between its quotes.
+    Char(String),
+    /// Contains the value of the boolean literal `true` or `false`.
+    Bool(bool),
+}
+
+impl std::fmt::Display for Literals {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Literals::*;
+
+        match self {
+            Integer(literal, _) | Float(literal) => write!(f, "{literal}"),
+            String(string) => write!(f, "\"{string}\""),
+            Char(char) => write!(f, "'{char}'"),
+            Bool(bool) => write!(f, "{bool}"),
+        }
+    }
 }
 
 impl ColumnOffset for Literals {
@@ -14,7 +132,9 @@ impl ColumnOffset for Literals {
         use Literals::*;
 
         match self {
-            Integer(int) => int.len(),
+            Integer(int, _) | Float(int) => int.len(),
+            String(string) | Char(string) => string.chars().count() + 2,
+            Bool(bool) => bool.to_string().len(),
         }
     }
 }
@@ -32,26 +152,257 @@ pub enum Symbols {
     CloseCurly,
     /// Represents an `;`.
     SemiColon,
+    /// Represents an `,`.
+    Comma,
+    /// Represents an `+`.
+    Plus,
+    /// Represents an `-`.
+    Minus,
+    /// Represents an `*`.
+    Asterisk,
+    /// Represents an `/`.
+    ForwardSlash,
+    /// Represents an `%`.
+    Percent,
+    /// Represents an `=`.
+    Equals,
+    /// Represents an `==`.
+    DoubleEquals,
+    /// Represents an `!=`.
+    NotEquals,
+    /// Represents an `<`.
+    LessThan,
+    /// Represents an `<=`.
+    LessThanOrEqual,
+    /// Represents an `>`.
+    GreaterThan,
+    /// Represents an `>=`.
+    GreaterThanOrEqual,
+    /// Represents an `&&`.
+    DoubleAmpersand,
+    /// Represents an `||`.
+    DoublePipe,
+    /// Represents an `!`.
+    Exclamation,
+}
+
+impl std::fmt::Display for Symbols {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Symbols::*;
+
+        let symbol = match self {
+            OpenParen => "(",
+            CloseParen => ")",
+            OpenCurly => "{",
+            CloseCurly => "}",
+            SemiColon => ";",
+            Comma => ",",
+            Plus => "+",
+            Minus => "-",
+            Asterisk => "*",
+            ForwardSlash => "/",
+            Percent => "%",
+            Equals => "=",
+            DoubleEquals => "==",
+            NotEquals => "!=",
+            LessThan => "<",
+            LessThanOrEqual => "<=",
+            GreaterThan => ">",
+            GreaterThanOrEqual => ">=",
+            DoubleAmpersand => "&&",
+            DoublePipe => "||",
+            Exclamation => "!",
+        };
+
+        write!(f, "{symbol}")
+    }
 }
 
 impl ColumnOffset for Symbols {
     fn to_col_offset(&self) -> usize {
-        1
+        use Symbols::*;
+
+        match self {
+            DoubleEquals | NotEquals | LessThanOrEqual | GreaterThanOrEqual | DoubleAmpersand
+            | DoublePipe => 2,
+            _ => 1,
+        }
     }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Types {
-    /// Represents the keyword `int`.
+    /// Represents the keyword `int`, a signed 32-bit integer.
     Int,
+    /// Represents the keyword `bool`.
+    Bool,
+    /// Represents the keyword `char`, a Unicode scalar value.
+    Char,
+    /// Represents the keyword `void`, the return type of functions without a
+    /// return value.
+    Void,
+    /// Represents the keyword `float`, a single precision floating-point
+    /// number.
+    Float,
+    /// Represents the keyword `double`, a double precision floating-point
+    /// number.
+    Double,
+    /// Represents the keyword `i8`.
+    I8,
+    /// Represents the keyword `i16`.
+    I16,
+    /// Represents the keyword `i32`.
+    I32,
+    /// Represents the keyword `i64`.
+    I64,
+    /// Represents the keyword `u8`.
+    U8,
+    /// Represents the keyword `u16`.
+    U16,
+    /// Represents the keyword `u32`.
+    U32,
+    /// Represents the keyword `u64`.
+    U64,
+    /// Represents the type of string literals, the address of the string,
+    /// which has no keyword.
+    Str,
+}
+
+impl Types {
+    /// Get the type spelled by the keyword if there is one.
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        use Types::*;
+
+        match keyword {
+            "int" => Some(Int),
+            "bool" => Some(Bool),
+            "char" => Some(Char),
+            "void" => Some(Void),
+            "float" => Some(Float),
+            "double" => Some(Double),
+            "i8" => Some(I8),
+            "i16" => Some(I16),
+            "i32" => Some(I32),
+            "i64" => Some(I64),
+            "u8" => Some(U8),
+            "u16" => Some(U16),
+            "u32" => Some(U32),
+            "u64" => Some(U64),
+            _ => None,
+        }
+    }
+
+    /// Get the size in bytes of a value of the type.
+    pub fn size(&self) -> usize {
+        use Types::*;
+
+        match self {
+            Void => 0,
+            Bool | I8 | U8 => 1,
+            I16 | U16 => 2,
+            Int | Char | Float | I32 | U32 => 4,
+            Double | I64 | U64 | Str => 8,
+        }
+    }
+
+    /// `true` if values of the type may be negative, `false` otherwise.
+    pub fn is_signed(&self) -> bool {
+        use Types::*;
+
+        matches!(self, Int | Float | Double | I8 | I16 | I32 | I64)
+    }
+
+    /// `true` if the type is one of the integer types, `false` otherwise.
+    pub fn is_integer(&self) -> bool {
+        !matches!(
+            self,
+            Types::Bool | Types::Char | Types::Void | Types::Float | Types::Double | Types::Str
+        )
+    }
+
+    /// `true` if the type is one of the floating-point types, `false`
+    /// otherwise.
+    pub fn is_float(&self) -> bool {
+        matches!(self, Types::Float | Types::Double)
+    }
+
+    /// `true` if values of the type may be too large for an `i64`, so have
+    /// to be compared, divided and converted as unsigned, `false` otherwise.
+    pub fn exceeds_i64(&self) -> bool {
+        self.is_integer() && self.max_value() > i64::MAX as u64
+    }
+
+    /// Get the largest value an integer literal of the type can hold.
+    pub fn max_value(&self) -> u64 {
+        use Types::*;
+
+        match self {
+            Void | Str => 0,
+            Bool => 1,
+            Char => char::MAX as u64,
+            // Any integer converts to the nearest representable value.
+            Float | Double => u64::MAX,
+            _ => {
+                let bits = self.size() as u32 * 8 - self.is_signed() as u32;
+                ((1u128 << bits) - 1) as u64
+            }
+        }
+    }
+
+    /// Get the smallest value of the type.
+    pub fn min_value(&self) -> i64 {
+        if self.is_float() {
+            i64::MIN
+        } else if self.is_signed() {
+            -(self.max_value() as i64) - 1
+        } else {
+            0
+        }
+    }
+
+    /// Keep the lowest bytes of an integer that fit in the type, sign
+    /// extending them if the type is signed.
+    pub fn wrap(&self, value: i64) -> i64 {
+        let bits = self.size() as u32 * 8;
+        if bits >= 64 {
+            return value;
+        }
+        let value = value & ((1i64 << bits) - 1);
+        if self.is_signed() {
+            let sign = 1i64 << (bits - 1);
+            (value ^ sign) - sign
+        } else {
+            value
+        }
+    }
 }
 
 impl ColumnOffset for Types {
     fn to_col_offset(&self) -> usize {
+        self.to_string().len()
+    }
+}
+
+impl std::fmt::Display for Types {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         use Types::*;
 
         match self {
-            Int => 3,
+            Int => write!(f, "int"),
+            Bool => write!(f, "bool"),
+            Char => write!(f, "char"),
+            Void => write!(f, "void"),
+            Float => write!(f, "float"),
+            Double => write!(f, "double"),
+            I8 => write!(f, "i8"),
+            I16 => write!(f, "i16"),
+            I32 => write!(f, "i32"),
+            I64 => write!(f, "i64"),
+            U8 => write!(f, "u8"),
+            U16 => write!(f, "u16"),
+            U32 => write!(f, "u32"),
+            U64 => write!(f, "u64"),
+            Str => write!(f, "string"),
         }
     }
 }
@@ -61,6 +412,36 @@ impl ColumnOffset for Types {
 pub enum Keywords {
     /// Represents the keyword `return`.
     Return,
+    /// Represents the keyword `if`.
+    If,
+    /// Represents the keyword `else`.
+    Else,
+    /// Represents the keyword `while`.
+    While,
+    /// Represents the keyword `for`.
+    For,
+    /// Represents the keyword `break`.
+    Break,
+    /// Represents the keyword `continue`.
+    Continue,
+}
+
+impl std::fmt::Display for Keywords {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Keywords::*;
+
+        let keyword = match self {
+            Return => "return",
+            If => "if",
+            Else => "else",
+            While => "while",
+            For => "for",
+            Break => "break",
+            Continue => "continue",
+        };
+
+        write!(f, "{keyword}")
+    }
 }
 
 impl ColumnOffset for Keywords {
@@ -69,6 +450,12 @@ impl ColumnOffset for Keywords {
 
         match self {
             Return => 6,
+            If => 2,
+            Else => 4,
+            While => 5,
+            For => 3,
+            Break => 5,
+            Continue => 8,
         }
     }
 }
@@ -88,6 +475,61 @@ pub enum TokenType {
     SomeName(String),
 }
 
+impl TokenType {
+    /// `true` if the token type is accepted where `expected` is expected,
+    /// `false` otherwise.
+    ///
+    /// Types, names and literals accept every token of their kind, see
+    /// [`TokenType::description`].
+    pub fn is_accepted_as(&self, expected: &TokenType) -> bool {
+        use TokenType::*;
+
+        match (self, expected) {
+            (Type(_), Type(_)) | (SomeName(_), SomeName(_)) => true,
+            (Literal(got), Literal(expected)) => {
+                std::mem::discriminant(got) == std::mem::discriminant(expected)
+            }
+            _ => self == expected,
+        }
+    }
+
+    /// Get a description of the tokens accepted where this token type is
+    /// expected.
+    ///
+    /// Types, names and literals describe every token of their kind, so any
+    /// type stands for all of them.
+    pub fn description(&self) -> String {
+        use TokenType::*;
+
+        match self {
+            Type(_) => "a type".to_string(),
+            SomeName(_) => "a name".to_string(),
+            Literal(Literals::Integer(..)) => "an integer literal".to_string(),
+            Literal(Literals::Float(_)) => "a floating-point literal".to_string(),
+            Literal(Literals::String(_)) => "a string literal".to_string(),
+            Literal(Literals::Char(_)) => "a character literal".to_string(),
+            Literal(Literals::Bool(_)) => "a boolean literal".to_string(),
+            Symbol(_) | Keyword(_) => self.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for TokenType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use TokenType::*;
+
+        match self {
+            // String and character literals are already quoted.
+            Literal(literal @ (Literals::String(_) | Literals::Char(_))) => write!(f, "{literal}"),
+            Literal(literal) => write!(f, "'{literal}'"),
+            Symbol(symbol) => write!(f, "'{symbol}'"),
+            Keyword(keyword) => write!(f, "'{keyword}'"),
+            Type(t) => write!(f, "'{t}'"),
+            SomeName(name) => write!(f, "'{name}'"),
+        }
+    }
+}
+
 impl ColumnOffset for TokenType {
     fn to_col_offset(&self) -> usize {
         use TokenType::*;
@@ -135,15 +577,46 @@ pub struct Token {
     pub line: usize,
     /// Contains the column number where the token appears.
     pub column: usize,
+    /// Contains the byte offset in the file where the token appears.
+    pub offset: usize,
 }
 
 impl Token {
     /// Creates a new [`Token`].
-    pub fn new(token_type: impl std::convert::Into<TokenType>, line: usize, column: usize) -> Self {
+    pub fn new(
+        token_type: impl std::convert::Into<TokenType>,
+        line: usize,
+        column: usize,
+        offset: usize,
+    ) -> Self {
         Self {
             token_type: token_type.into(),
             line,
             column,
+            offset,
+        }
+    }
+
+    /// Get the [`Position`] of the start of the token.
+    pub fn position(&self) -> Position {
+        Position::new(self.line, self.column, self.offset)
+    }
+
+    /// Get the [`Span`] of source covered by the token.
+    pub fn span(&self) -> Span {
+        match &self.token_type {
+            // A string literal ends on a later line for every newline in it.
+            TokenType::Literal(Literals::String(string)) if string.contains('\n') => {
+                let last_line = string.rsplit('\n').next().unwrap_or_default();
+                let end = Position::new(
+                    self.line + string.matches('\n').count(),
+                    last_line.chars().count() + 2,
+                    self.offset + string.len() + 2,
+                );
+
+                Span::new(self.position(), end)
+            }
+            token_type => Span::on_line(self.position(), token_type.to_col_offset()),
         }
     }
 }
@@ -155,19 +628,50 @@ mod tests {
     #[test]
     fn should_return_new_token() {
         assert_eq!(
-            Token::new(Keywords::Return, 1, 1),
+            Token::new(Keywords::Return, 1, 1, 0),
             Token {
                 token_type: Keywords::Return.into(),
                 line: 1,
-                column: 1
+                column: 1,
+                offset: 0,
             }
         );
     }
 
+    #[test]
+    fn should_get_token_span() {
+        assert_eq!(
+            Token::new(Keywords::Return, 2, 5, 17).span(),
+            Span::new(Position::new(2, 5, 17), Position::new(2, 11, 23))
+        );
+        assert_eq!(
+            Token::new(Literals::String("a\nbc".to_string()), 1, 3, 2).span(),
+            Span::new(Position::new(1, 3, 2), Position::new(2, 4, 8))
+        );
+    }
+
+    #[test]
+    fn should_get_radix_value() {
+        assert_eq!(Radix::Hexadecimal.value("0xFF"), Some(255));
+        assert_eq!(Radix::Octal.value("0o755"), Some(493));
+        assert_eq!(Radix::Binary.value("0b1010"), Some(10));
+        assert_eq!(Radix::Decimal.value("1_000_000"), Some(1_000_000));
+        assert_eq!(Radix::Decimal.value("99999999999999999999"), None);
+    }
+
+    #[test]
+    fn should_get_float_value() {
+        assert_eq!(float_value("2.5"), Some(2.5));
+        assert_eq!(float_value(".5"), Some(0.5));
+        assert_eq!(float_value("1_000.5e-1"), Some(100.05));
+        assert_eq!(float_value("1e400"), Some(f64::INFINITY));
+        assert_eq!(float_value("inf"), None);
+    }
+
     #[test]
     fn should_get_col_offset_token_type() {
         assert_eq!(
-            TokenType::from(Literals::Integer("99".to_string())).to_col_offset(),
+            TokenType::from(Literals::Integer("99".to_string(), Radix::Decimal)).to_col_offset(),
             2
         );
         assert_eq!(TokenType::from(Symbols::OpenParen).to_col_offset(), 1);
@@ -175,18 +679,135 @@ mod tests {
         assert_eq!(TokenType::SomeName("name".to_string()).to_col_offset(), 4);
     }
 
+    #[test]
+    fn should_display_token_types() {
+        assert_eq!(TokenType::from(Symbols::CloseCurly).to_string(), "'}'");
+        assert_eq!(TokenType::from(Symbols::NotEquals).to_string(), "'!='");
+        assert_eq!(TokenType::from(Keywords::Return).to_string(), "'return'");
+        assert_eq!(TokenType::from(Types::U8).to_string(), "'u8'");
+        assert_eq!(TokenType::SomeName("x".to_string()).to_string(), "'x'");
+        assert_eq!(
+            TokenType::from(Literals::Integer("0x1F".to_string(), Radix::Hexadecimal)).to_string(),
+            "'0x1F'"
+        );
+        assert_eq!(
+            TokenType::from(Literals::String("a\\n".to_string())).to_string(),
+            "\"a\\n\""
+        );
+        assert_eq!(
+            TokenType::from(Literals::Char("a".to_string())).to_string(),
+            "'a'"
+        );
+    }
+
+    #[test]
+    fn should_accept_token_types_of_expected_kind() {
+        let name = TokenType::SomeName("x".to_string());
+
+        assert!(name.is_accepted_as(&TokenType::SomeName("any".to_string())));
+        assert!(TokenType::from(Types::U8).is_accepted_as(&Types::Int.into()));
+        assert!(
+            TokenType::from(Literals::Integer("1".to_string(), Radix::Binary))
+                .is_accepted_as(&Literals::Integer("any".to_string(), Radix::Decimal).into())
+        );
+        assert!(
+            !TokenType::from(Literals::Float("1.5".to_string()))
+                .is_accepted_as(&Literals::Integer("any".to_string(), Radix::Decimal).into())
+        );
+        assert!(!TokenType::from(Symbols::Comma).is_accepted_as(&Symbols::CloseParen.into()));
+        assert!(!name.is_accepted_as(&Types::Int.into()));
+    }
+
+    #[test]
+    fn should_describe_expected_token_types() {
+        assert_eq!(TokenType::from(Types::Int).description(), "a type");
+        assert_eq!(
+            TokenType::SomeName("any".to_string()).description(),
+            "a name"
+        );
+        assert_eq!(
+            TokenType::from(Literals::Float("any".to_string())).description(),
+            "a floating-point literal"
+        );
+        assert_eq!(TokenType::from(Symbols::SemiColon).description(), "';'");
+    }
+
     #[test]
     fn should_get_col_offset_literals() {
-        assert_eq!(Literals::Integer("99".to_string()).to_col_offset(), 2);
+        assert_eq!(
+            Literals::Integer("99".to_string(), Radix::Decimal).to_col_offset(),
+            2
+        );
     }
 
     #[test]
     fn should_get_col_offset_keywords() {
         assert_eq!(Keywords::Return.to_col_offset(), 6);
+        assert_eq!(Keywords::If.to_col_offset(), 2);
+        assert_eq!(Keywords::Else.to_col_offset(), 4);
+        assert_eq!(Keywords::While.to_col_offset(), 5);
+        assert_eq!(Keywords::For.to_col_offset(), 3);
+        assert_eq!(Keywords::Break.to_col_offset(), 5);
+        assert_eq!(Keywords::Continue.to_col_offset(), 8);
+    }
+
+    #[test]
+    fn should_get_col_offset_symbols() {
+        assert_eq!(Symbols::Equals.to_col_offset(), 1);
+        assert_eq!(Symbols::DoubleEquals.to_col_offset(), 2);
+        assert_eq!(Symbols::DoublePipe.to_col_offset(), 2);
+        assert_eq!(Symbols::Exclamation.to_col_offset(), 1);
     }
 
     #[test]
     fn should_get_col_offset_types() {
         assert_eq!(Types::Int.to_col_offset(), 3);
+        assert_eq!(Types::Bool.to_col_offset(), 4);
+        assert_eq!(Types::U16.to_col_offset(), 3);
+    }
+
+    #[test]
+    fn should_get_type_from_keyword() {
+        assert_eq!(Types::from_keyword("void"), Some(Types::Void));
+        assert_eq!(Types::from_keyword("i64"), Some(Types::I64));
+        assert_eq!(Types::from_keyword("i128"), None);
+    }
+
+    #[test]
+    fn should_get_type_size_and_signedness() {
+        assert_eq!(Types::Void.size(), 0);
+        assert_eq!(Types::Bool.size(), 1);
+        assert_eq!(Types::Int.size(), 4);
+        assert_eq!(Types::U16.size(), 2);
+        assert_eq!(Types::I64.size(), 8);
+        assert!(Types::Int.is_signed());
+        assert!(!Types::U8.is_signed());
+        assert!(!Types::Char.is_signed());
+        assert_eq!(Types::Float.size(), 4);
+        assert_eq!(Types::Double.size(), 8);
+        assert!(Types::Double.is_float());
+        assert!(!Types::Double.is_integer());
+    }
+
+    #[test]
+    fn should_get_type_range() {
+        assert_eq!(Types::Int.max_value(), i32::MAX as u64);
+        assert_eq!(Types::I8.max_value(), 127);
+        assert_eq!(Types::I8.min_value(), -128);
+        assert_eq!(Types::I64.min_value(), i64::MIN);
+        assert_eq!(Types::U8.max_value(), 255);
+        assert_eq!(Types::U64.max_value(), u64::MAX);
+        assert_eq!(Types::U64.min_value(), 0);
+        assert_eq!(Types::Bool.max_value(), 1);
+    }
+
+    #[test]
+    fn should_wrap_integers_to_type() {
+        assert_eq!(Types::I8.wrap(128), -128);
+        assert_eq!(Types::I8.wrap(-129), 127);
+        assert_eq!(Types::U8.wrap(-1), 255);
+        assert_eq!(Types::Int.wrap(i32::MAX as i64 + 1), i32::MIN as i64);
+        assert_eq!(Types::U32.wrap(-1), u32::MAX as i64);
+        assert_eq!(Types::I64.wrap(i64::MIN), i64::MIN);
     }
 }
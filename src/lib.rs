@@ -0,0 +1,36 @@
+pub mod cmdline;
+pub mod codegen;
+pub mod diagnostic;
+pub mod error;
+#[cfg(test)]
+mod fixtures;
+pub mod interpreter;
+pub mod ir;
+pub mod lexer;
+pub mod parser;
+pub mod semantic;
+pub mod span;
+
+use std::{fs, path::Path};
+
+use error::Error;
+use lexer::{Lexer, token::Token};
+use parser::{Parser, node::Statement};
+
+/// Tokenize the oxygen file at the given path, placing tab stops every
+/// `tab_width` columns.
+pub fn lex_file(path: &Path, tab_width: usize) -> Result<Vec<Token>, Error> {
+    let source = fs::read_to_string(path)?;
+
+    Ok(Lexer::with_tab_width(&source, tab_width).collect::<Result<_, _>>()?)
+}
+
+/// Parse the oxygen file at the given path into a program, lexing each token
+/// only once the parser needs it.
+pub fn parse_file(path: &Path, tab_width: usize) -> Result<Vec<Statement>, Error> {
+    let source = fs::read_to_string(path)?;
+    let mut prog: Vec<Statement> = Vec::new();
+    Parser::new(Lexer::with_tab_width(&source, tab_width), &mut prog).parse()?;
+
+    Ok(prog)
+}
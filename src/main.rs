@@ -1,53 +1,117 @@
-mod error;
-mod lexer;
-mod parser;
-
 use std::{
-    fs::File,
-    io::{BufRead, BufReader},
+    fs,
+    path::{Path, PathBuf},
     process::exit,
 };
 
 use clap::Parser;
-use error::Error;
-use lexer::{LexerState, token::Token, tokenize};
-use parser::node::Statement;
+use o2::{
+    cmdline::{self, EmitStage},
+    codegen::{Codegen, ENTRY_SYMBOL, elf, encode::encode, instruction::render},
+    diagnostic,
+    error::Error,
+    ir, lex_file, parse_file, semantic,
+};
 
-pub fn wrapper() -> Result<(), Error> {
-    let cli = cmdline::O2CCli::parse();
+/// Get the path a stage is written to given the path of the output.
+fn stage_path(output_file: &Path, stage: EmitStage) -> PathBuf {
+    match stage {
+        EmitStage::Exe => output_file.to_path_buf(),
+        stage => output_file.with_extension(stage.extension()),
+    }
+}
 
-    let oxygen_file = File::open(cli.oxygen_file)?;
+pub fn wrapper(cli: cmdline::O2CCli) -> Result<(), Error> {
+    let stages = if !cli.emit.is_empty() {
+        cli.emit
+    } else if cli.compile_only {
+        vec![EmitStage::Obj]
+    } else {
+        vec![EmitStage::Exe]
+    };
+    let last_stage = *stages.iter().max().unwrap_or(&EmitStage::Exe);
+    let output_file = cli.output_file.unwrap_or_else(|| {
+        let output_file = PathBuf::from(cli.oxygen_file.file_stem().unwrap_or_default());
+        if cli.compile_only {
+            output_file.with_extension("o")
+        } else {
+            output_file
+        }
+    });
+    let emit = |stage: EmitStage, contents: &[u8]| -> Result<(), Error> {
+        if stages.contains(&stage) {
+            let path = stage_path(&output_file, stage);
+            match stage {
+                EmitStage::Exe => elf::write_executable(&path, contents)?,
+                _ => fs::write(&path, contents)?,
+            }
+        }
+        Ok(())
+    };
 
-    let mut lexer_state = LexerState::new();
-    let mut tokens: Vec<Token> = Vec::new();
+    // The tokens are only collected when they are shown, the parser
+    // otherwise lexes them as it goes.
+    if cli.display_tokens || stages.contains(&EmitStage::Tokens) {
+        let tokens = lex_file(&cli.oxygen_file, cli.tab_width)?;
 
-    let file_reader = BufReader::new(oxygen_file);
-    for line in file_reader.lines() {
-        tokenize(&line?, &mut tokens, &mut lexer_state)?;
+        if cli.display_tokens {
+            for token in &tokens {
+                println!("{token:#?}");
+            }
+            return Ok(());
+        }
+        let tokens_text: String = tokens.iter().map(|t| format!("{t:#?}\n")).collect();
+        emit(EmitStage::Tokens, tokens_text.as_bytes())?;
+        if last_stage == EmitStage::Tokens {
+            return Ok(());
+        }
     }
 
-    if cli.display_tokens {
-        for token in &tokens {
-            println!("{token:#?}");
-        }
+    // The tree is shown as parsed, even if it fails the semantic checks.
+    let prog = parse_file(&cli.oxygen_file, cli.tab_width)?;
+    if cli.display_ast {
+        println!("{prog:#?}");
+        return Ok(());
+    }
+    emit(EmitStage::Ast, format!("{prog:#?}\n").as_bytes())?;
+    if last_stage == EmitStage::Ast {
         return Ok(());
     }
 
-    let mut prog: Vec<Statement> = Vec::new();
-    let mut parser = parser::Parser::new(tokens, &mut prog);
-    parser.parse()?;
+    semantic::entry::check(&prog, &cli.entry)?;
+    let symbols = semantic::resolve::resolve(&prog)?;
+    semantic::calls::check(&prog, &symbols)?;
+    let types = semantic::types::check(&prog, &symbols)?;
+    for warning in semantic::flow::check(&prog)? {
+        eprintln!("{}", diagnostic::warning(&cli.oxygen_file, &warning));
+    }
 
-    if cli.display_ast {
-        println!("{prog:#?}");
+    let module = ir::lower::lower(&prog, &symbols, &types)?;
+    emit(EmitStage::Ir, module.to_string().as_bytes())?;
+    if last_stage == EmitStage::Ir {
         return Ok(());
     }
 
+    let instructions = Codegen::with_entry(&cli.entry).generate(&module);
+    emit(EmitStage::Asm, render(&instructions).as_bytes())?;
+    if last_stage == EmitStage::Asm {
+        return Ok(());
+    }
+
+    let encoded = encode(&instructions).map_err(|e| e.locate(&symbols))?;
+    emit(EmitStage::Obj, &elf::object(&encoded))?;
+    let executable = elf::executable(&encoded, ENTRY_SYMBOL).map_err(|e| e.locate(&symbols))?;
+    emit(EmitStage::Exe, &executable)?;
+
     Ok(())
 }
 
 pub fn main() {
-    if let Err(e) = wrapper() {
-        eprintln!("\x1b[31;1merror:\x1b[0m {e}");
+    let cli = cmdline::O2CCli::parse();
+    let oxygen_file = cli.oxygen_file.clone();
+
+    if let Err(e) = wrapper(cli) {
+        eprintln!("{}", diagnostic::report(&oxygen_file, &e));
         exit(1);
     }
 }
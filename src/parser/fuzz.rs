@@ -0,0 +1,141 @@
+//! Checks that the [`Parser`] reports errors rather than panicking on
+//! arbitrary token streams.
+
+use crate::{
+    lexer::{
+        token::{Keywords, Literals, Radix, Symbols, Token, TokenType, Types},
+        tokenize,
+    },
+    parser::{Parser, node::Statement},
+};
+
+const CASES: usize = 2000;
+const MAX_TOKENS: usize = 48;
+
+/// A small xorshift generator so every run checks the same streams.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Get a number in `0..bound`.
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+}
+
+fn alphabet() -> Vec<TokenType> {
+    vec![
+        Symbols::OpenParen.into(),
+        Symbols::CloseParen.into(),
+        Symbols::OpenCurly.into(),
+        Symbols::CloseCurly.into(),
+        Symbols::SemiColon.into(),
+        Symbols::Comma.into(),
+        Symbols::Plus.into(),
+        Symbols::Minus.into(),
+        Symbols::Asterisk.into(),
+        Symbols::ForwardSlash.into(),
+        Symbols::Percent.into(),
+        Symbols::Equals.into(),
+        Symbols::DoubleEquals.into(),
+        Symbols::NotEquals.into(),
+        Symbols::LessThan.into(),
+        Symbols::LessThanOrEqual.into(),
+        Symbols::GreaterThan.into(),
+        Symbols::GreaterThanOrEqual.into(),
+        Symbols::DoubleAmpersand.into(),
+        Symbols::DoublePipe.into(),
+        Symbols::Exclamation.into(),
+        Keywords::Return.into(),
+        Keywords::If.into(),
+        Keywords::Else.into(),
+        Keywords::While.into(),
+        Keywords::For.into(),
+        Keywords::Break.into(),
+        Keywords::Continue.into(),
+        Types::Int.into(),
+        Types::Void.into(),
+        Types::U8.into(),
+        Types::Double.into(),
+        TokenType::SomeName("x".to_string()),
+        TokenType::SomeName("main".to_string()),
+        Literals::Integer("1".to_string(), Radix::Decimal).into(),
+        Literals::Float("1.5".to_string()).into(),
+        Literals::String("s".to_string()).into(),
+        Literals::Char("c".to_string()).into(),
+        Literals::Bool(true).into(),
+    ]
+}
+
+fn parse(tokens: Vec<Token>) {
+    let mut prog: Vec<Statement> = Vec::new();
+    let _ = Parser::new(tokens.into_iter().map(Ok), &mut prog).parse();
+}
+
+fn lex(content: &str) -> Vec<Token> {
+    tokenize(content).unwrap()
+}
+
+#[test]
+fn should_not_panic_on_random_tokens() {
+    let alphabet = alphabet();
+    let mut rng = Rng(0x5eed_0f0c_7a11);
+
+    for _ in 0..CASES {
+        let tokens = (0..rng.below(MAX_TOKENS))
+            .map(|i| Token::new(alphabet[rng.below(alphabet.len())].clone(), 1, i + 1, i))
+            .collect();
+
+        parse(tokens);
+    }
+}
+
+#[test]
+fn should_not_panic_on_mutated_program() {
+    let program = lex("int add(int a, int b) { return a + b; } \
+         int main() { int x = 0; \
+         for (int i = 0; i < 3; i = i + 1) { if (i == 1) { continue; } x = add(x, i); } \
+         while (x > 0) { x = x - 1; break; } \
+         return x; }");
+    let alphabet = alphabet();
+    let mut rng = Rng(0xc0ff_ee00_d15e_a5e5);
+
+    for _ in 0..CASES {
+        let mut tokens = program.clone();
+        for _ in 0..=rng.below(4) {
+            let at = rng.below(tokens.len());
+            match rng.below(3) {
+                0 => {
+                    tokens.remove(at);
+                }
+                1 => tokens.insert(
+                    at,
+                    Token::new(alphabet[rng.below(alphabet.len())].clone(), 1, 1, 0),
+                ),
+                _ => {
+                    let other = rng.below(tokens.len());
+                    tokens.swap(at, other);
+                }
+            }
+        }
+
+        parse(tokens);
+    }
+}
+
+#[test]
+fn should_not_panic_on_truncated_program() {
+    let program =
+        lex("int main() { if (1) { while (1) { break; } } else { return f(1, 2); } return 0; }");
+
+    for end in 0..program.len() {
+        parse(program[..end].to_vec());
+        parse(program[end..].to_vec());
+    }
+}
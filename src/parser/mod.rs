@@ -1,89 +1,438 @@
+#[cfg(test)]
+mod fuzz;
 pub mod node;
 pub mod parsers;
 
-use node::Statement;
+use node::{Statement, assign_ids};
 use parsers::statement::parse_statement;
 
-use crate::{error::ParserResult, lexer::token::Token};
+use std::collections::VecDeque;
+
+use crate::{
+    error::{LexerError, ParserError, ParserResult, StatementError, TokenTypeError},
+    lexer::token::{Symbols, Token, TokenType},
+    span::{Position, Span},
+};
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct Parser<'a> {
-    tokens: Box<[Token]>,
+pub struct Parser<'a, 't> {
+    /// Contains the tokens not yet pulled into the lookahead.
+    tokens: Box<dyn Iterator<Item = Result<Token, LexerError>> + 't>,
+    /// Contains the tokens pulled but not yet consumed, the next token first.
+    lookahead: VecDeque<Token>,
+    /// Contains the position just past the last token pulled.
+    end: Option<Position>,
+    /// Contains the error which ended the tokens, if any, and the number of
+    /// errors recovered from before it was reached.
+    lexer_error: Option<(LexerError, usize)>,
     prog: &'a mut Vec<Statement>,
-    index: usize,
+    /// Contains the number of loops enclosing the current statement.
+    loops: usize,
+    /// Contains every error recovered from so far.
+    diagnostics: Vec<ParserError>,
 }
 
-impl<'a> Parser<'a> {
-    pub fn new(tokens: Vec<Token>, prog: &'a mut Vec<Statement>) -> Self {
+impl<'a, 't> Parser<'a, 't> {
+    /// Create a new [`Parser`] which pulls tokens as it needs them, so a
+    /// [`crate::lexer::Lexer`] is only run as far as the parser gets.
+    pub fn new(
+        tokens: impl IntoIterator<Item = Result<Token, LexerError>> + 't,
+        prog: &'a mut Vec<Statement>,
+    ) -> Self {
         Self {
-            tokens: tokens.into_boxed_slice(),
+            tokens: Box::new(tokens.into_iter()),
+            lookahead: VecDeque::new(),
+            end: None,
+            lexer_error: None,
             prog,
-            index: 0,
+            loops: 0,
+            diagnostics: Vec::new(),
         }
     }
 
+    /// Parse every statement into the program.
+    ///
+    /// Statements that fail to parse are left out of the program and parsing
+    /// resumes at the next statement, so every error found is returned.
+    ///
+    /// The tokens end at a [`LexerError`], which is returned after the errors
+    /// found before it, as any found after it may only be caused by the
+    /// missing tokens.
     pub fn parse(&mut self) -> ParserResult {
-        while self.index < self.tokens.len() {
-            let statement = parse_statement(self)?;
-            self.prog.push(statement);
+        while self.peek(0).is_some() {
+            match parse_statement(self) {
+                Ok(statement @ Statement::FunctionDeclare { .. }) => self.prog.push(statement),
+                Ok(statement) => self.diagnostics.push(
+                    StatementError::OutsideFunction {
+                        span: statement.span(),
+                    }
+                    .into(),
+                ),
+                // There is no enclosing block for a stray `}` to close.
+                Err(e) => {
+                    if self.recover(e) {
+                        self.consume();
+                    }
+                }
+            }
+        }
+
+        assign_ids(self.prog);
+
+        if let Some((error, count)) = self.lexer_error.take() {
+            self.diagnostics.truncate(count);
+            self.diagnostics.push(error.into());
+        }
+
+        if self.diagnostics.is_empty() {
+            Ok(())
+        } else {
+            Err(std::mem::take(&mut self.diagnostics))
+        }
+    }
+
+    /// Record the error and skip to the start of the next statement.
+    ///
+    /// Tokens are skipped up to and including a `;` or the `}` closing a
+    /// block opened while skipping, or up to the `}` closing the enclosing
+    /// block in which case `true` is returned.
+    fn recover(&mut self, error: impl Into<ParserError>) -> bool {
+        self.diagnostics.push(error.into());
+
+        let mut depth: usize = 0;
+        while let Some(token) = self.peek(0) {
+            match token.token_type {
+                TokenType::Symbol(Symbols::SemiColon) if depth == 0 => {
+                    self.consume();
+                    return false;
+                }
+                TokenType::Symbol(Symbols::OpenCurly) => depth += 1,
+                TokenType::Symbol(Symbols::CloseCurly) if depth == 0 => return true,
+                TokenType::Symbol(Symbols::CloseCurly) => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.consume();
+                        return false;
+                    }
+                }
+                _ => {}
+            }
+            self.consume();
         }
 
-        Ok(())
+        false
     }
 
-    fn peek(&self, offset: usize) -> Option<Token> {
-        self.tokens.get(self.index + offset).cloned()
+    fn peek(&mut self, offset: usize) -> Option<Token> {
+        while self.lookahead.len() <= offset && self.pull() {}
+        self.lookahead.get(offset).cloned()
     }
 
     fn consume(&mut self) -> Option<Token> {
-        let t = self.tokens.get(self.index).cloned();
-        self.index += 1;
-        t
+        self.peek(0);
+        self.lookahead.pop_front()
+    }
+
+    /// Pull the next token into the lookahead returning `false` if there are
+    /// no tokens left.
+    fn pull(&mut self) -> bool {
+        if self.lexer_error.is_some() {
+            return false;
+        }
+        match self.tokens.next() {
+            Some(Ok(token)) => {
+                self.end = Some(token.span().end);
+                self.lookahead.push_back(token);
+                true
+            }
+            Some(Err(e)) => {
+                self.lexer_error = Some((e, self.diagnostics.len()));
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Consume the next token if it is accepted where the given type is
+    /// expected, returning its type and span.
+    ///
+    /// Any other token is left in place so error recovery can resume at it,
+    /// a missing `;` must not swallow the `}` after it.
+    fn consume_expecting(
+        &mut self,
+        expected: TokenType,
+    ) -> Result<(TokenType, Span), TokenTypeError> {
+        let Some(token) = self.peek(0) else {
+            return Err(TokenTypeError::ExpectedGotNone {
+                expected,
+                span: self.end_span(),
+            });
+        };
+        let span = token.span();
+        if !token.token_type.is_accepted_as(&expected) {
+            return Err(TokenTypeError::Expected {
+                expected,
+                got: token.token_type,
+                span,
+            });
+        }
+        self.consume();
+
+        Ok((token.token_type, span))
+    }
+
+    /// Consume the next token returning its type and span, or an error if
+    /// there are no tokens left.
+    fn consume_some(&mut self) -> Result<(TokenType, Span), TokenTypeError> {
+        let token = self
+            .consume()
+            .ok_or_else(|| TokenTypeError::ExpectedSomeGotNone {
+                span: self.end_span(),
+            })?;
+        let span = token.span();
+
+        Ok((token.token_type, span))
+    }
+
+    /// Get the [`Span`] of the next token, or of the end of the input if
+    /// there are no tokens left.
+    fn peek_span(&mut self) -> Span {
+        self.peek(0).map_or_else(|| self.end_span(), |t| t.span())
+    }
+
+    /// Get an empty [`Span`] just past the last token pulled.
+    fn end_span(&self) -> Span {
+        match self.end {
+            Some(end) => Span::new(end, end),
+            None => Span::on_line(Position::new(1, 1, 0), 0),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::lexer::token::Keywords;
+    use crate::{
+        error::{StatementError, TermError},
+        lexer::{Lexer, token::Keywords},
+    };
 
     use super::*;
 
+    fn lex(content: &str) -> Lexer<'_> {
+        Lexer::new(content)
+    }
+
+    #[test]
+    fn should_collect_every_error() {
+        let mut prog: Vec<Statement> = Vec::new();
+        let mut parser = Parser::new(
+            lex("int main() { int x = ; return *; return 0; } int f() { return 1; }"),
+            &mut prog,
+        );
+
+        let res = parser.parse();
+
+        assert_eq!(
+            res,
+            Err(vec![
+                StatementError::Term(TermError::NoTerm {
+                    span: Span::on_line(Position::new(1, 22, 21), 1)
+                })
+                .into(),
+                StatementError::Term(TermError::NoTerm {
+                    span: Span::on_line(Position::new(1, 31, 30), 1)
+                })
+                .into(),
+            ])
+        );
+        assert_eq!(prog.len(), 2);
+        assert!(matches!(
+            &prog[0],
+            Statement::FunctionDeclare { body, .. } if body.len() == 1
+        ));
+    }
+
+    #[test]
+    fn should_recover_past_block() {
+        let mut prog: Vec<Statement> = Vec::new();
+        let mut parser = Parser::new(
+            lex("int main() { if (1 +) { return 1; } return 0; }"),
+            &mut prog,
+        );
+
+        let res = parser.parse();
+
+        assert!(res.is_err_and(|e| e.len() == 1));
+        assert!(matches!(
+            &prog[..],
+            [Statement::FunctionDeclare { body, .. }]
+                if matches!(&body[..], [Statement::Return { .. }])
+        ));
+    }
+
+    #[test]
+    fn should_error_stray_tokens_at_top_level() {
+        let mut prog: Vec<Statement> = Vec::new();
+        let mut parser = Parser::new(lex("; } int main() { return 0; }"), &mut prog);
+
+        let res = parser.parse();
+
+        assert!(res.is_err_and(|e| e.len() == 2
+            && e.iter().all(|e| matches!(
+                e,
+                ParserError::Statement(StatementError::UnexpectedToken { .. })
+            ))));
+        assert_eq!(prog.len(), 1);
+    }
+
+    #[test]
+    fn should_recover_at_top_level() {
+        let mut prog: Vec<Statement> = Vec::new();
+        let mut parser = Parser::new(
+            lex("int f(void x) { return 1; } int main() { return 0; }"),
+            &mut prog,
+        );
+
+        let res = parser.parse();
+
+        assert!(res.is_err_and(|e| matches!(
+            &e[..],
+            [ParserError::Statement(StatementError::VoidVariable { .. })]
+        )));
+        assert!(matches!(
+            &prog[..],
+            [Statement::FunctionDeclare { name, .. }] if name == "main"
+        ));
+    }
+
+    #[test]
+    fn should_leave_close_curly_after_missing_semicolon() {
+        let mut prog: Vec<Statement> = Vec::new();
+        let mut parser = Parser::new(lex("int main() { return 0 }"), &mut prog);
+
+        let res = parser.parse();
+
+        assert_eq!(
+            res,
+            Err(vec![
+                StatementError::TokenType(TokenTypeError::Expected {
+                    expected: Symbols::SemiColon.into(),
+                    got: Symbols::CloseCurly.into(),
+                    span: Span::on_line(Position::new(1, 23, 22), 1),
+                })
+                .into()
+            ])
+        );
+        assert!(matches!(
+            &prog[..],
+            [Statement::FunctionDeclare { name, body, .. }] if name == "main" && body.is_empty()
+        ));
+    }
+
+    #[test]
+    fn should_end_errors_at_lexer_error() {
+        let mut prog: Vec<Statement> = Vec::new();
+        let mut parser = Parser::new(
+            lex("int main() { int x = ; return @; } int f() { return 1; }"),
+            &mut prog,
+        );
+
+        let res = parser.parse();
+
+        assert_eq!(
+            res,
+            Err(vec![
+                StatementError::Term(TermError::NoTerm {
+                    span: Span::on_line(Position::new(1, 22, 21), 1)
+                })
+                .into(),
+                LexerError::UnknownCharacter {
+                    the_char: '@',
+                    span: Span::on_line(Position::new(1, 31, 30), 1)
+                }
+                .into(),
+            ])
+        );
+    }
+
+    #[test]
+    fn should_error_statement_outside_function() {
+        let mut prog: Vec<Statement> = Vec::new();
+        let mut parser = Parser::new(lex("return 1; int main() { return 0; }"), &mut prog);
+
+        let res = parser.parse();
+
+        assert_eq!(
+            res,
+            Err(vec![
+                StatementError::OutsideFunction {
+                    span: Span::on_line(Position::new(1, 1, 0), 9)
+                }
+                .into()
+            ])
+        );
+        assert_eq!(prog.len(), 1);
+    }
+
+    #[test]
+    fn should_pull_tokens_as_needed() {
+        let tokens: Vec<Token> = vec![
+            Token::new(Keywords::Return, 1, 1, 0),
+            Token::new(Symbols::SemiColon, 1, 7, 6),
+        ];
+        let mut prog: Vec<Statement> = Vec::new();
+        let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+        assert_eq!(
+            parser.peek(1),
+            Some(Token::new(Symbols::SemiColon, 1, 7, 6))
+        );
+        assert_eq!(parser.lookahead.len(), 2);
+        assert_eq!(
+            parser.consume(),
+            Some(Token::new(Keywords::Return, 1, 1, 0))
+        );
+        assert_eq!(parser.lookahead.len(), 1);
+    }
+
     #[test]
     fn should_peek_some() {
-        let tokens: Vec<Token> = vec![Token::new(Keywords::Return, 1, 1)];
+        let tokens: Vec<Token> = vec![Token::new(Keywords::Return, 1, 1, 0)];
         let mut prog: Vec<Statement> = Vec::new();
-        let parser = Parser::new(tokens, &mut prog);
+        let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
         assert!(parser.peek(0).is_some());
-        assert_eq!(parser.peek(0).unwrap(), Token::new(Keywords::Return, 1, 1));
+        assert_eq!(
+            parser.peek(0).unwrap(),
+            Token::new(Keywords::Return, 1, 1, 0)
+        );
     }
 
     #[test]
     fn should_peek_none() {
         let tokens: Vec<Token> = Vec::new();
         let mut prog: Vec<Statement> = Vec::new();
-        let parser = Parser::new(tokens, &mut prog);
+        let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
         assert!(parser.peek(0).is_none());
     }
 
     #[test]
     fn should_consume_some() {
-        let tokens: Vec<Token> = vec![Token::new(Keywords::Return, 1, 1)];
+        let tokens: Vec<Token> = vec![Token::new(Keywords::Return, 1, 1, 0)];
         let mut prog: Vec<Statement> = Vec::new();
-        let mut parser = Parser::new(tokens, &mut prog);
+        let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
         let c = parser.consume();
         assert!(c.is_some());
-        assert_eq!(c.unwrap(), Token::new(Keywords::Return, 1, 1));
-        assert_eq!(parser.index, 1);
+        assert_eq!(c.unwrap(), Token::new(Keywords::Return, 1, 1, 0));
+        assert!(parser.peek(0).is_none());
     }
 
     #[test]
     fn should_consume_none() {
         let tokens: Vec<Token> = Vec::new();
         let mut prog: Vec<Statement> = Vec::new();
-        let mut parser = Parser::new(tokens, &mut prog);
+        let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
         let c = parser.consume();
         assert!(c.is_none());
@@ -1,18 +1,479 @@
-use crate::lexer::token::Types;
+use crate::{lexer::token::Types, span::Span};
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+/// A unique identifier of a node in a parsed program.
+///
+/// The parser creates every node with [`NodeId::DUMMY`] and numbers them
+/// once the whole program is parsed, see [`assign_ids`].
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub struct NodeId(pub usize);
+
+impl NodeId {
+    /// The id of a node which has not been numbered yet.
+    pub const DUMMY: NodeId = NodeId(usize::MAX);
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum Statement {
     FunctionDeclare {
         name: String,
         return_type: Types,
+        params: Vec<Parameter>,
         body: Vec<Statement>,
+        id: NodeId,
+        span: Span,
     },
+    /// Returns from the function with the value of the expression, or
+    /// without a value from a `void` function.
     Return {
-        term: Term,
+        expression: Option<Expression>,
+        span: Span,
+    },
+    /// Declares a new variable initialised to the expression.
+    Declare {
+        name: String,
+        var_type: Types,
+        expression: Expression,
+        id: NodeId,
+        span: Span,
+    },
+    /// Assigns the expression to an already declared variable.
+    Assign {
+        name: String,
+        expression: Expression,
+        id: NodeId,
+        span: Span,
+    },
+    /// Runs the body if the condition is non-zero, otherwise runs the else
+    /// body if there is one.
+    ///
+    /// An `else if` is represented as an else body containing a single
+    /// [`Statement::If`].
+    If {
+        condition: Expression,
+        body: Vec<Statement>,
+        else_body: Option<Vec<Statement>>,
+        span: Span,
     },
+    /// Runs the body for as long as the condition is non-zero.
+    While {
+        condition: Expression,
+        body: Vec<Statement>,
+        span: Span,
+    },
+    /// Runs the initialiser once, then the body followed by the step for as
+    /// long as the condition is non-zero.
+    ///
+    /// A missing condition is always true. The initialiser is scoped to the
+    /// loop.
+    For {
+        init: Option<Box<Statement>>,
+        condition: Option<Expression>,
+        step: Option<Box<Statement>>,
+        body: Vec<Statement>,
+        span: Span,
+    },
+    /// Evaluates the expression discarding its value.
+    Expression { expression: Expression, span: Span },
+    /// Exits the innermost enclosing loop.
+    Break { span: Span },
+    /// Skips to the next iteration of the innermost enclosing loop.
+    Continue { span: Span },
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+impl Statement {
+    /// Get the [`Span`] of source the statement was parsed from.
+    pub fn span(&self) -> Span {
+        use Statement::*;
+
+        match self {
+            FunctionDeclare { span, .. }
+            | Return { span, .. }
+            | Declare { span, .. }
+            | Assign { span, .. }
+            | If { span, .. }
+            | While { span, .. }
+            | For { span, .. }
+            | Expression { span, .. }
+            | Break { span }
+            | Continue { span } => *span,
+        }
+    }
+}
+
+/// A representation of a typed function parameter.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Parameter {
+    /// Contains the name of the parameter.
+    pub name: String,
+    /// Contains the type of the parameter.
+    pub param_type: Types,
+    /// Contains the id of the parameter.
+    pub id: NodeId,
+    /// Contains the location of the parameter's type and name.
+    pub span: Span,
+}
+
+/// A representation of an expression built from terms and operators.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Expression {
+    /// Contains a single [`Term`].
+    Term(Term),
+    /// Contains an operator applied to a single operand.
+    Unary {
+        op: UnaryOp,
+        operand: Box<Expression>,
+        id: NodeId,
+        span: Span,
+    },
+    /// Contains an operator applied to a left and right operand.
+    Binary {
+        op: BinaryOp,
+        lhs: Box<Expression>,
+        rhs: Box<Expression>,
+        id: NodeId,
+        span: Span,
+    },
+}
+
+impl Expression {
+    /// Get the [`Span`] of source the expression was parsed from.
+    pub fn span(&self) -> Span {
+        match self {
+            Expression::Term(term) => term.span(),
+            Expression::Unary { span, .. } | Expression::Binary { span, .. } => *span,
+        }
+    }
+
+    /// Get the [`NodeId`] of the expression, which is that of its term if it
+    /// is a single term.
+    pub fn id(&self) -> NodeId {
+        match self {
+            Expression::Term(term) => term.id(),
+            Expression::Unary { id, .. } | Expression::Binary { id, .. } => *id,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum Term {
-    LiteralInteger(String),
+    /// Contains the value of an integer literal.
+    LiteralInteger { value: u64, id: NodeId, span: Span },
+    /// Contains the value of a string literal with its escapes replaced.
+    LiteralString {
+        value: String,
+        id: NodeId,
+        span: Span,
+    },
+    /// Contains the value of a character literal.
+    LiteralChar { value: char, id: NodeId, span: Span },
+    /// Contains the value of a floating-point literal.
+    LiteralFloat { value: f64, id: NodeId, span: Span },
+    /// Contains the value of a boolean literal.
+    LiteralBool { value: bool, id: NodeId, span: Span },
+    /// Contains the name of a declared variable.
+    Identifier {
+        name: String,
+        id: NodeId,
+        span: Span,
+    },
+    /// Contains an [`Expression`] wrapped in `(` and `)`.
+    Parenthesised {
+        expression: Box<Expression>,
+        id: NodeId,
+        span: Span,
+    },
+    /// Contains a call to the named function with the argument expressions.
+    Call {
+        name: String,
+        args: Vec<Expression>,
+        id: NodeId,
+        span: Span,
+    },
+}
+
+impl Term {
+    /// Get the [`Span`] of source the term was parsed from.
+    pub fn span(&self) -> Span {
+        match self {
+            Term::LiteralInteger { span, .. }
+            | Term::LiteralString { span, .. }
+            | Term::LiteralChar { span, .. }
+            | Term::LiteralFloat { span, .. }
+            | Term::LiteralBool { span, .. }
+            | Term::Identifier { span, .. }
+            | Term::Parenthesised { span, .. }
+            | Term::Call { span, .. } => *span,
+        }
+    }
+
+    /// Get the [`NodeId`] of the term.
+    pub fn id(&self) -> NodeId {
+        match self {
+            Term::LiteralInteger { id, .. }
+            | Term::LiteralString { id, .. }
+            | Term::LiteralChar { id, .. }
+            | Term::LiteralFloat { id, .. }
+            | Term::LiteralBool { id, .. }
+            | Term::Identifier { id, .. }
+            | Term::Parenthesised { id, .. }
+            | Term::Call { id, .. } => *id,
+        }
+    }
+}
+
+/// A representation of the prefix operators.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum UnaryOp {
+    /// Represents `-`.
+    Negate,
+    /// Represents `!`.
+    Not,
+}
+
+/// A representation of the infix operators.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BinaryOp {
+    /// Represents `+`.
+    Add,
+    /// Represents `-`.
+    Subtract,
+    /// Represents `*`.
+    Multiply,
+    /// Represents `/`.
+    Divide,
+    /// Represents `%`.
+    Modulo,
+    /// Represents `==`.
+    Equal,
+    /// Represents `!=`.
+    NotEqual,
+    /// Represents `<`.
+    Less,
+    /// Represents `<=`.
+    LessEqual,
+    /// Represents `>`.
+    Greater,
+    /// Represents `>=`.
+    GreaterEqual,
+    /// Represents `&&`, only evaluating the right operand if the left is
+    /// non-zero.
+    And,
+    /// Represents `||`, only evaluating the right operand if the left is
+    /// zero.
+    Or,
+}
+
+impl BinaryOp {
+    /// Get the binding power of the operator, higher binds tighter.
+    pub fn precedence(&self) -> u8 {
+        use BinaryOp::*;
+
+        match self {
+            Or => 1,
+            And => 2,
+            Equal | NotEqual => 3,
+            Less | LessEqual | Greater | GreaterEqual => 4,
+            Add | Subtract => 5,
+            Multiply | Divide | Modulo => 6,
+        }
+    }
+}
+
+impl std::fmt::Display for UnaryOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnaryOp::Negate => write!(f, "-"),
+            UnaryOp::Not => write!(f, "!"),
+        }
+    }
+}
+
+impl std::fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use BinaryOp::*;
+
+        match self {
+            Add => write!(f, "+"),
+            Subtract => write!(f, "-"),
+            Multiply => write!(f, "*"),
+            Divide => write!(f, "/"),
+            Modulo => write!(f, "%"),
+            Equal => write!(f, "=="),
+            NotEqual => write!(f, "!="),
+            Less => write!(f, "<"),
+            LessEqual => write!(f, "<="),
+            Greater => write!(f, ">"),
+            GreaterEqual => write!(f, ">="),
+            And => write!(f, "&&"),
+            Or => write!(f, "||"),
+        }
+    }
+}
+
+impl std::convert::From<Term> for Expression {
+    fn from(value: Term) -> Self {
+        Expression::Term(value)
+    }
+}
+
+/// Give every node of the program a [`NodeId`] unique within the program.
+pub fn assign_ids(prog: &mut [Statement]) {
+    let mut numberer = Numberer { next: 0 };
+    numberer.number_block(prog);
+}
+
+/// A representation of the state while numbering the nodes of a program.
+struct Numberer {
+    /// Contains the id the next node is given.
+    next: usize,
+}
+
+impl Numberer {
+    fn next(&mut self, id: &mut NodeId) {
+        *id = NodeId(self.next);
+        self.next += 1;
+    }
+
+    fn number_block(&mut self, body: &mut [Statement]) {
+        body.iter_mut().for_each(|s| self.number_statement(s));
+    }
+
+    fn number_statement(&mut self, statement: &mut Statement) {
+        match statement {
+            Statement::FunctionDeclare {
+                params, body, id, ..
+            } => {
+                self.next(id);
+                params.iter_mut().for_each(|p| self.next(&mut p.id));
+                self.number_block(body);
+            }
+            Statement::Return { expression, .. } => {
+                if let Some(expression) = expression {
+                    self.number_expression(expression);
+                }
+            }
+            Statement::Declare { expression, id, .. }
+            | Statement::Assign { expression, id, .. } => {
+                self.number_expression(expression);
+                self.next(id);
+            }
+            Statement::If {
+                condition,
+                body,
+                else_body,
+                ..
+            } => {
+                self.number_expression(condition);
+                self.number_block(body);
+                if let Some(else_body) = else_body {
+                    self.number_block(else_body);
+                }
+            }
+            Statement::While {
+                condition, body, ..
+            } => {
+                self.number_expression(condition);
+                self.number_block(body);
+            }
+            Statement::For {
+                init,
+                condition,
+                step,
+                body,
+                ..
+            } => {
+                if let Some(init) = init {
+                    self.number_statement(init);
+                }
+                if let Some(condition) = condition {
+                    self.number_expression(condition);
+                }
+                if let Some(step) = step {
+                    self.number_statement(step);
+                }
+                self.number_block(body);
+            }
+            Statement::Expression { expression, .. } => self.number_expression(expression),
+            Statement::Break { .. } | Statement::Continue { .. } => {}
+        }
+    }
+
+    fn number_expression(&mut self, expression: &mut Expression) {
+        match expression {
+            Expression::Term(term) => self.number_term(term),
+            Expression::Unary { operand, id, .. } => {
+                self.number_expression(operand);
+                self.next(id);
+            }
+            Expression::Binary { lhs, rhs, id, .. } => {
+                self.number_expression(lhs);
+                self.number_expression(rhs);
+                self.next(id);
+            }
+        }
+    }
+
+    fn number_term(&mut self, term: &mut Term) {
+        match term {
+            Term::Parenthesised { expression, id, .. } => {
+                self.number_expression(expression);
+                self.next(id);
+            }
+            Term::Call { args, id, .. } => {
+                args.iter_mut().for_each(|arg| self.number_expression(arg));
+                self.next(id);
+            }
+            Term::LiteralInteger { id, .. }
+            | Term::LiteralString { id, .. }
+            | Term::LiteralChar { id, .. }
+            | Term::LiteralFloat { id, .. }
+            | Term::LiteralBool { id, .. }
+            | Term::Identifier { id, .. } => self.next(id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::span::Position;
+
+    #[test]
+    fn should_get_precedence() {
+        assert_eq!(BinaryOp::Add.precedence(), BinaryOp::Subtract.precedence());
+        assert!(BinaryOp::Multiply.precedence() > BinaryOp::Add.precedence());
+        assert_eq!(BinaryOp::Divide.precedence(), BinaryOp::Modulo.precedence());
+    }
+
+    #[test]
+    fn should_display_operators() {
+        assert_eq!(UnaryOp::Not.to_string(), "!");
+        assert_eq!(BinaryOp::Modulo.to_string(), "%");
+        assert_eq!(BinaryOp::GreaterEqual.to_string(), ">=");
+        assert_eq!(BinaryOp::Or.to_string(), "||");
+    }
+
+    #[test]
+    fn should_convert_term_to_expression() {
+        let term = Term::LiteralInteger {
+            id: NodeId::DUMMY,
+            value: 1,
+            span: Span::default(),
+        };
+
+        assert_eq!(Expression::from(term.clone()), Expression::Term(term));
+    }
+
+    #[test]
+    fn should_get_expression_span() {
+        let span = Span::on_line(Position::new(1, 5, 4), 1);
+        let expression: Expression = Term::Identifier {
+            id: NodeId::DUMMY,
+            name: "x".to_string(),
+            span,
+        }
+        .into();
+
+        assert_eq!(expression.span(), span);
+    }
 }
@@ -0,0 +1,416 @@
+use crate::{
+    error::ExpressionResult,
+    lexer::token::{Literals, Symbols, TokenType},
+    parser::{
+        Parser,
+        node::{BinaryOp, Expression, NodeId, UnaryOp},
+        parsers::term::{parse_term, parse_term_literal_int},
+    },
+};
+
+/// Parse some tokens into an expression respecting operator precedence.
+pub fn parse_expression(parser: &mut Parser) -> ExpressionResult {
+    parse_expression_precedence(parser, 0)
+}
+
+/// Parse an expression using precedence climbing, only consuming binary
+/// operators which bind at least as tightly as `min_precedence`.
+fn parse_expression_precedence(parser: &mut Parser, min_precedence: u8) -> ExpressionResult {
+    let mut lhs = parse_expression_unary(parser)?;
+
+    while let Some(op) = parser.peek(0).and_then(|t| binary_op(&t.token_type)) {
+        if op.precedence() < min_precedence {
+            break;
+        }
+        parser.consume();
+
+        // Binding the right hand side tighter makes operators left associative.
+        let rhs = parse_expression_precedence(parser, op.precedence() + 1)?;
+        lhs = Expression::Binary {
+            id: NodeId::DUMMY,
+            op,
+            span: lhs.span().to(&rhs.span()),
+            lhs: Box::new(lhs),
+            rhs: Box::new(rhs),
+        };
+    }
+
+    Ok(lhs)
+}
+
+/// Parse a term with any number of prefix operators.
+fn parse_expression_unary(parser: &mut Parser) -> ExpressionResult {
+    // -...
+    // ^
+    if let Some(op) = parser.peek(0).and_then(|t| unary_op(&t.token_type)) {
+        let (_, span) = parser.consume_some()?;
+        let operand = match op {
+            // The smallest value of a type is only in range once negated.
+            UnaryOp::Negate
+                if parser.peek(0).is_some_and(|t| {
+                    matches!(t.token_type, TokenType::Literal(Literals::Integer(..)))
+                }) =>
+            {
+                parse_term_literal_int(parser, true)?.into()
+            }
+            _ => parse_expression_unary(parser)?,
+        };
+        return Ok(Expression::Unary {
+            id: NodeId::DUMMY,
+            op,
+            span: span.to(&operand.span()),
+            operand: Box::new(operand),
+        });
+    }
+
+    Ok(parse_term(parser)?.into())
+}
+
+/// Get the [`UnaryOp`] represented by the token type if there is one.
+fn unary_op(token_type: &TokenType) -> Option<UnaryOp> {
+    match token_type {
+        TokenType::Symbol(Symbols::Minus) => Some(UnaryOp::Negate),
+        TokenType::Symbol(Symbols::Exclamation) => Some(UnaryOp::Not),
+        _ => None,
+    }
+}
+
+/// Get the [`BinaryOp`] represented by the token type if there is one.
+fn binary_op(token_type: &TokenType) -> Option<BinaryOp> {
+    match token_type {
+        TokenType::Symbol(Symbols::Plus) => Some(BinaryOp::Add),
+        TokenType::Symbol(Symbols::Minus) => Some(BinaryOp::Subtract),
+        TokenType::Symbol(Symbols::Asterisk) => Some(BinaryOp::Multiply),
+        TokenType::Symbol(Symbols::ForwardSlash) => Some(BinaryOp::Divide),
+        TokenType::Symbol(Symbols::Percent) => Some(BinaryOp::Modulo),
+        TokenType::Symbol(Symbols::DoubleEquals) => Some(BinaryOp::Equal),
+        TokenType::Symbol(Symbols::NotEquals) => Some(BinaryOp::NotEqual),
+        TokenType::Symbol(Symbols::LessThan) => Some(BinaryOp::Less),
+        TokenType::Symbol(Symbols::LessThanOrEqual) => Some(BinaryOp::LessEqual),
+        TokenType::Symbol(Symbols::GreaterThan) => Some(BinaryOp::Greater),
+        TokenType::Symbol(Symbols::GreaterThanOrEqual) => Some(BinaryOp::GreaterEqual),
+        TokenType::Symbol(Symbols::DoubleAmpersand) => Some(BinaryOp::And),
+        TokenType::Symbol(Symbols::DoublePipe) => Some(BinaryOp::Or),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::TermError,
+        fixtures::at,
+        lexer::token::{Radix, Token, Types},
+        parser::node::{Statement, Term},
+    };
+
+    fn int(value: u64, column: usize) -> Expression {
+        Term::LiteralInteger {
+            id: NodeId::DUMMY,
+            value,
+            span: at(column, value.to_string().len()),
+        }
+        .into()
+    }
+
+    fn binary(op: BinaryOp, lhs: Expression, rhs: Expression) -> Expression {
+        Expression::Binary {
+            id: NodeId::DUMMY,
+            op,
+            span: lhs.span().to(&rhs.span()),
+            lhs: Box::new(lhs),
+            rhs: Box::new(rhs),
+        }
+    }
+
+    fn unary(op: UnaryOp, column: usize, operand: Expression) -> Expression {
+        Expression::Unary {
+            id: NodeId::DUMMY,
+            op,
+            span: at(column, 1).to(&operand.span()),
+            operand: Box::new(operand),
+        }
+    }
+
+    fn int_token(value: &str, column: usize) -> Token {
+        Token::new(
+            Literals::Integer(value.to_string(), Radix::Decimal),
+            1,
+            column,
+            column - 1,
+        )
+    }
+
+    mod test_parse_expression {
+        use super::*;
+
+        #[test]
+        fn should_parse_term() {
+            let tokens: Vec<Token> = vec![int_token("1", 1)];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_expression(&mut parser);
+
+            assert_eq!(res, Ok(int(1, 1)));
+        }
+
+        #[test]
+        fn should_parse_precedence() {
+            // 1 + 2 * 3
+            let tokens: Vec<Token> = vec![
+                int_token("1", 1),
+                Token::new(Symbols::Plus, 1, 3, 2),
+                int_token("2", 5),
+                Token::new(Symbols::Asterisk, 1, 7, 6),
+                int_token("3", 9),
+            ];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_expression(&mut parser);
+
+            assert_eq!(
+                res,
+                Ok(binary(
+                    BinaryOp::Add,
+                    int(1, 1),
+                    binary(BinaryOp::Multiply, int(2, 5), int(3, 9))
+                ))
+            );
+        }
+
+        #[test]
+        fn should_parse_left_associative() {
+            // 1 - 2 - 3
+            let tokens: Vec<Token> = vec![
+                int_token("1", 1),
+                Token::new(Symbols::Minus, 1, 3, 2),
+                int_token("2", 5),
+                Token::new(Symbols::Minus, 1, 7, 6),
+                int_token("3", 9),
+            ];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_expression(&mut parser);
+
+            assert_eq!(
+                res,
+                Ok(binary(
+                    BinaryOp::Subtract,
+                    binary(BinaryOp::Subtract, int(1, 1), int(2, 5)),
+                    int(3, 9)
+                ))
+            );
+        }
+
+        #[test]
+        fn should_parse_parenthesised() {
+            // (1 + 2) % 3
+            let tokens: Vec<Token> = vec![
+                Token::new(Symbols::OpenParen, 1, 1, 0),
+                int_token("1", 2),
+                Token::new(Symbols::Plus, 1, 4, 3),
+                int_token("2", 6),
+                Token::new(Symbols::CloseParen, 1, 7, 6),
+                Token::new(Symbols::Percent, 1, 9, 8),
+                int_token("3", 11),
+            ];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_expression(&mut parser);
+
+            assert_eq!(
+                res,
+                Ok(binary(
+                    BinaryOp::Modulo,
+                    Term::Parenthesised {
+                        id: NodeId::DUMMY,
+                        expression: Box::new(binary(BinaryOp::Add, int(1, 2), int(2, 6))),
+                        span: at(1, 7)
+                    }
+                    .into(),
+                    int(3, 11)
+                ))
+            );
+        }
+
+        #[test]
+        fn should_parse_unary_minus() {
+            // --1 * 2
+            let tokens: Vec<Token> = vec![
+                Token::new(Symbols::Minus, 1, 1, 0),
+                Token::new(Symbols::Minus, 1, 2, 1),
+                int_token("1", 3),
+                Token::new(Symbols::Asterisk, 1, 5, 4),
+                int_token("2", 7),
+            ];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_expression(&mut parser);
+
+            assert_eq!(
+                res,
+                Ok(binary(
+                    BinaryOp::Multiply,
+                    unary(UnaryOp::Negate, 1, unary(UnaryOp::Negate, 2, int(1, 3))),
+                    int(2, 7)
+                ))
+            );
+        }
+
+        #[test]
+        fn should_parse_negated_smallest_int() {
+            // -9223372036854775808
+            let tokens: Vec<Token> = vec![
+                Token::new(Symbols::Minus, 1, 1, 0),
+                int_token("9223372036854775808", 2),
+            ];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_expression(&mut parser);
+
+            assert_eq!(
+                res,
+                Ok(unary(UnaryOp::Negate, 1, int(9223372036854775808, 2)))
+            );
+        }
+
+        #[test]
+        fn should_error_negated_int_out_of_range() {
+            // 1 - -9223372036854775809
+            let tokens: Vec<Token> = vec![
+                int_token("1", 1),
+                Token::new(Symbols::Minus, 1, 3, 2),
+                Token::new(Symbols::Minus, 1, 5, 4),
+                int_token("9223372036854775809", 6),
+            ];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_expression(&mut parser);
+
+            assert_eq!(
+                res,
+                Err(TermError::LiteralOutOfRange {
+                    value: "-9223372036854775809".to_string(),
+                    target: Types::I64,
+                    span: at(6, 19),
+                })
+            );
+        }
+
+        #[test]
+        fn should_parse_logical_and_comparison_precedence() {
+            // !1 || 2 < 3 && 4 == 5
+            let tokens: Vec<Token> = vec![
+                Token::new(Symbols::Exclamation, 1, 1, 0),
+                int_token("1", 2),
+                Token::new(Symbols::DoublePipe, 1, 4, 3),
+                int_token("2", 7),
+                Token::new(Symbols::LessThan, 1, 9, 8),
+                int_token("3", 11),
+                Token::new(Symbols::DoubleAmpersand, 1, 13, 12),
+                int_token("4", 16),
+                Token::new(Symbols::DoubleEquals, 1, 18, 17),
+                int_token("5", 21),
+            ];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_expression(&mut parser);
+
+            assert_eq!(
+                res,
+                Ok(binary(
+                    BinaryOp::Or,
+                    unary(UnaryOp::Not, 1, int(1, 2)),
+                    binary(
+                        BinaryOp::And,
+                        binary(BinaryOp::Less, int(2, 7), int(3, 11)),
+                        binary(BinaryOp::Equal, int(4, 16), int(5, 21))
+                    )
+                ))
+            );
+        }
+
+        #[test]
+        fn should_stop_at_non_operator() {
+            let tokens: Vec<Token> =
+                vec![int_token("1", 1), Token::new(Symbols::SemiColon, 1, 2, 1)];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_expression(&mut parser);
+
+            assert_eq!(res, Ok(int(1, 1)));
+            assert_eq!(
+                parser.peek(0),
+                Some(Token::new(Symbols::SemiColon, 1, 2, 1))
+            );
+        }
+
+        #[test]
+        fn should_error_missing_rhs() {
+            let tokens: Vec<Token> = vec![int_token("1", 1), Token::new(Symbols::Plus, 1, 3, 2)];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_expression(&mut parser);
+
+            assert_eq!(res, Err(TermError::NoTerm { span: at(4, 0) }));
+        }
+
+        #[test]
+        fn should_error_missing_operand_after_minus() {
+            let tokens: Vec<Token> = vec![Token::new(Symbols::Minus, 1, 1, 0)];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_expression(&mut parser);
+
+            assert_eq!(res, Err(TermError::NoTerm { span: at(2, 0) }));
+        }
+    }
+
+    mod test_binary_op {
+        use super::*;
+
+        #[test]
+        fn should_map_symbols() {
+            assert_eq!(binary_op(&Symbols::Plus.into()), Some(BinaryOp::Add));
+            assert_eq!(binary_op(&Symbols::Minus.into()), Some(BinaryOp::Subtract));
+            assert_eq!(
+                binary_op(&Symbols::Asterisk.into()),
+                Some(BinaryOp::Multiply)
+            );
+            assert_eq!(
+                binary_op(&Symbols::ForwardSlash.into()),
+                Some(BinaryOp::Divide)
+            );
+            assert_eq!(binary_op(&Symbols::Percent.into()), Some(BinaryOp::Modulo));
+            assert_eq!(
+                binary_op(&Symbols::GreaterThanOrEqual.into()),
+                Some(BinaryOp::GreaterEqual)
+            );
+            assert_eq!(binary_op(&Symbols::DoublePipe.into()), Some(BinaryOp::Or));
+            assert_eq!(binary_op(&Symbols::SemiColon.into()), None);
+        }
+    }
+
+    mod test_unary_op {
+        use super::*;
+
+        #[test]
+        fn should_map_symbols() {
+            assert_eq!(unary_op(&Symbols::Minus.into()), Some(UnaryOp::Negate));
+            assert_eq!(unary_op(&Symbols::Exclamation.into()), Some(UnaryOp::Not));
+            assert_eq!(unary_op(&Symbols::Plus.into()), None);
+        }
+    }
+}
@@ -1,2 +1,3 @@
+pub mod expression;
 pub mod statement;
 pub mod term;
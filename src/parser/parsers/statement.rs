@@ -1,7 +1,12 @@
 use crate::{
     error::{StatementError, StatementResult, TokenTypeError},
     lexer::token::{Keywords, Symbols, TokenType, Types},
-    parser::{Parser, node::Statement, parsers::term::parse_term},
+    parser::{
+        Parser,
+        node::{NodeId, Parameter, Statement},
+        parsers::expression::parse_expression,
+    },
+    span::Span,
 };
 
 pub fn parse_statement(parser: &mut Parser) -> StatementResult {
@@ -16,168 +21,552 @@ pub fn parse_statement(parser: &mut Parser) -> StatementResult {
             .is_some_and(|t| t.token_type == Symbols::OpenParen.into())
     {
         return parse_statement_function_declare(parser);
+    } else if parser
+        .peek(0)
+        .is_some_and(|t| matches!(t.token_type, TokenType::Type(_)))
+        && parser
+            .peek(1)
+            .is_some_and(|t| matches!(t.token_type, TokenType::SomeName(_)))
+        && parser
+            .peek(2)
+            .is_some_and(|t| t.token_type == Symbols::Equals.into())
+    {
+        return parse_statement_declare(parser);
+    } else if parser
+        .peek(0)
+        .is_some_and(|t| matches!(t.token_type, TokenType::SomeName(_)))
+        && parser
+            .peek(1)
+            .is_some_and(|t| t.token_type == Symbols::Equals.into())
+    {
+        return parse_statement_assign(parser);
+    } else if parser
+        .peek(0)
+        .is_some_and(|t| matches!(t.token_type, TokenType::SomeName(_)))
+        && parser
+            .peek(1)
+            .is_some_and(|t| t.token_type == Symbols::OpenParen.into())
+    {
+        return parse_statement_expression(parser);
     } else if parser
         .peek(0)
         .is_some_and(|t| t.token_type == Keywords::Return.into())
     {
         return parse_statement_return(parser);
+    } else if parser
+        .peek(0)
+        .is_some_and(|t| t.token_type == Keywords::If.into())
+    {
+        return parse_statement_if(parser);
+    } else if parser
+        .peek(0)
+        .is_some_and(|t| t.token_type == Keywords::While.into())
+    {
+        return parse_statement_while(parser);
+    } else if parser
+        .peek(0)
+        .is_some_and(|t| t.token_type == Keywords::For.into())
+    {
+        return parse_statement_for(parser);
+    } else if parser
+        .peek(0)
+        .is_some_and(|t| t.token_type == Keywords::Break.into())
+    {
+        return parse_statement_loop_control(parser, Keywords::Break);
+    } else if parser
+        .peek(0)
+        .is_some_and(|t| t.token_type == Keywords::Continue.into())
+    {
+        return parse_statement_loop_control(parser, Keywords::Continue);
     }
 
-    // TODO: add Err return if no statement can be parsed.
-    unreachable!("Shouldn't get to statement parser unreachable");
+    Err(unexpected_token(parser))
+}
+
+/// Get the error for tokens that do not start any statement, pointing at the
+/// first token that could not be accepted.
+fn unexpected_token(parser: &mut Parser) -> StatementError {
+    let (offset, expected): (usize, Vec<TokenType>) = match parser.peek(0).map(|t| t.token_type) {
+        // int x
+        //     ^
+        Some(TokenType::Type(_))
+            if parser
+                .peek(1)
+                .is_some_and(|t| matches!(t.token_type, TokenType::SomeName(_))) =>
+        {
+            (2, vec![Symbols::OpenParen.into(), Symbols::Equals.into()])
+        }
+        // int
+        //    ^
+        Some(TokenType::Type(_)) => (1, vec![TokenType::SomeName("any".to_string())]),
+        // x
+        //  ^
+        Some(TokenType::SomeName(_)) => {
+            (1, vec![Symbols::Equals.into(), Symbols::OpenParen.into()])
+        }
+        // Any type stands for every type, see `TokenType::description`.
+        _ => (
+            0,
+            vec![
+                Types::Int.into(),
+                TokenType::SomeName("any".to_string()),
+                Keywords::Return.into(),
+                Keywords::If.into(),
+                Keywords::While.into(),
+                Keywords::For.into(),
+                Keywords::Break.into(),
+                Keywords::Continue.into(),
+            ],
+        ),
+    };
+
+    match parser.peek(offset) {
+        Some(token) => StatementError::UnexpectedToken {
+            span: token.span(),
+            token: token.token_type,
+            expected,
+        },
+        None => TokenTypeError::ExpectedSomeGotNone {
+            span: parser.end_span(),
+        }
+        .into(),
+    }
 }
 
 /// Parse some tokens into a function declaration.
 fn parse_statement_function_declare(parser: &mut Parser) -> StatementResult {
     // int main() {...}
     // ^^^
-    let return_type: Types = match parser
-        .consume()
-        .ok_or(TokenTypeError::ExpectedGotNone {
-            expected: Keywords::Return.into(),
-        })?
-        .token_type
-    {
-        TokenType::Type(t) => Ok(t),
-        t => Err(TokenTypeError::Expected {
-            expected: Keywords::Return.into(),
-            got: t,
-        }),
-    }?;
+    let (return_type, start) = parse_type(parser)?;
     // int main() {...}
     //     ^^^^
-    let name: String = match parser
-        .consume()
-        .ok_or(TokenTypeError::ExpectedGotNone {
-            expected: TokenType::SomeName("any".to_string()),
-        })?
-        .token_type
-    {
-        TokenType::SomeName(name) => Ok(name),
-        t => Err(TokenTypeError::Expected {
-            expected: TokenType::SomeName("any".to_string()),
-            got: t,
-        }),
-    }?;
+    let (name, _) = parse_name(parser)?;
     // int main() {...}
     //         ^
-    match parser
-        .consume()
-        .ok_or(TokenTypeError::ExpectedGotNone {
-            expected: Symbols::OpenParen.into(),
-        })?
-        .token_type
-    {
-        TokenType::Symbol(Symbols::OpenParen) => Ok(()),
-        t => Err(TokenTypeError::Expected {
-            expected: Symbols::OpenParen.into(),
-            got: t,
-        }),
-    }?;
+    parse_symbol(parser, Symbols::OpenParen)?;
     // int main() {...}
     //          ^
-    match parser
-        .consume()
-        .ok_or(TokenTypeError::ExpectedGotNone {
-            expected: Symbols::CloseParen.into(),
-        })?
-        .token_type
-    {
-        TokenType::Symbol(Symbols::CloseParen) => Ok(()),
-        t => Err(TokenTypeError::Expected {
-            expected: Symbols::CloseParen.into(),
-            got: t,
-        }),
-    }?;
+    let params = parse_params(parser)?;
     // int main() {...}
     //            ^
-    match parser
-        .consume()
-        .ok_or(TokenTypeError::ExpectedGotNone {
-            expected: Symbols::OpenCurly.into(),
-        })?
-        .token_type
-    {
-        TokenType::Symbol(Symbols::OpenCurly) => Ok(()),
-        t => Err(TokenTypeError::Expected {
-            expected: Symbols::OpenCurly.into(),
-            got: t,
-        }),
-    }?;
+    parse_symbol(parser, Symbols::OpenCurly)?;
     // int main() {...}
     //             ^^^
+    // Whether every path returns is left to `semantic::flow`.
+    let body = parse_block_body(parser)?;
+
+    // No need to check as the while loop above handles until the '}'
+    // int main() {...}
+    //                ^
+    let (_, end) = parser.consume_some()?;
+
+    Ok(Statement::FunctionDeclare {
+        id: NodeId::DUMMY,
+        name,
+        return_type,
+        params,
+        body,
+        span: start.to(&end),
+    })
+}
+
+/// Parse a comma separated list of typed parameters up to and including the
+/// closing `)`.
+fn parse_params(parser: &mut Parser) -> Result<Vec<Parameter>, StatementError> {
+    let mut params: Vec<Parameter> = Vec::new();
+    // int add() {...}
+    //         ^
+    if parser
+        .peek(0)
+        .is_some_and(|t| t.token_type == Symbols::CloseParen.into())
+    {
+        parser.consume();
+        return Ok(params);
+    }
+
+    loop {
+        // int add(int a, int b) {...}
+        //         ^^^
+        let (param_type, start) = parse_type(parser)?;
+        // int add(int a, int b) {...}
+        //             ^
+        let (name, span) = parse_name(parser)?;
+        if param_type == Types::Void {
+            return Err(StatementError::VoidVariable { name, span: start });
+        }
+        params.push(Parameter {
+            id: NodeId::DUMMY,
+            name,
+            param_type,
+            span: start.to(&span),
+        });
+        // int add(int a, int b) {...}
+        //              ^       ^
+        if parser
+            .peek(0)
+            .is_some_and(|t| t.token_type == Symbols::Comma.into())
+        {
+            parser.consume();
+            continue;
+        }
+        parse_symbol(parser, Symbols::CloseParen)?;
+        break;
+    }
+
+    Ok(params)
+}
+
+/// Parse statements up to, but not including, the closing `}`.
+fn parse_block_body(parser: &mut Parser) -> Result<Vec<Statement>, StatementError> {
     let mut body: Vec<Statement> = Vec::new();
     while parser
         .peek(0)
-        .ok_or(TokenTypeError::ExpectedSomeGotNone)?
+        .ok_or_else(|| TokenTypeError::ExpectedSomeGotNone {
+            span: parser.end_span(),
+        })?
         .token_type
         != Symbols::CloseCurly.into()
     {
-        body.push(parse_statement(parser)?);
+        match parse_statement(parser) {
+            Ok(statement) => body.push(statement),
+            Err(e) => {
+                parser.recover(e);
+            }
+        }
     }
-    if !body
-        .last()
-        .is_some_and(|t| matches!(t, Statement::Return { .. }))
+
+    Ok(body)
+}
+
+/// Parse some tokens into a conditional with optional `else if` and `else`
+/// branches.
+fn parse_statement_if(parser: &mut Parser) -> StatementResult {
+    // if (...) {...} else ...
+    // ^^
+    let start = parse_keyword(parser, Keywords::If)?;
+    // if (...) {...} else ...
+    //    ^^^^^
+    parse_symbol(parser, Symbols::OpenParen)?;
+    let condition = parse_expression(parser)?;
+    parse_symbol(parser, Symbols::CloseParen)?;
+    // if (...) {...} else ...
+    //          ^^^^^
+    parse_symbol(parser, Symbols::OpenCurly)?;
+    let body = parse_block_body(parser)?;
+    let end = parse_symbol(parser, Symbols::CloseCurly)?;
+    // if (...) {...} else ...
+    //                ^^^^
+    if !parser
+        .peek(0)
+        .is_some_and(|t| t.token_type == Keywords::Else.into())
     {
-        return Err(StatementError::MissingReturn);
+        return Ok(Statement::If {
+            condition,
+            body,
+            else_body: None,
+            span: start.to(&end),
+        });
     }
-
-    // No need to check as the while loop above handles until the '}'
-    // int main() {...}
-    //                ^
     parser.consume();
+    // if (...) {...} else if ...
+    //                     ^^^^^^
+    if parser
+        .peek(0)
+        .is_some_and(|t| t.token_type == Keywords::If.into())
+    {
+        let else_if = parse_statement_if(parser)?;
+        return Ok(Statement::If {
+            condition,
+            body,
+            span: start.to(&else_if.span()),
+            else_body: Some(vec![else_if]),
+        });
+    }
+    // if (...) {...} else {...}
+    //                     ^^^^^
+    parse_symbol(parser, Symbols::OpenCurly)?;
+    let else_body = parse_block_body(parser)?;
+    let end = parse_symbol(parser, Symbols::CloseCurly)?;
+
+    Ok(Statement::If {
+        condition,
+        body,
+        else_body: Some(else_body),
+        span: start.to(&end),
+    })
+}
 
-    Ok(Statement::FunctionDeclare {
-        name,
-        return_type,
+/// Parse some tokens into a `while` loop.
+fn parse_statement_while(parser: &mut Parser) -> StatementResult {
+    // while (...) {...}
+    // ^^^^^
+    let start = parse_keyword(parser, Keywords::While)?;
+    // while (...) {...}
+    //       ^^^^^
+    parse_symbol(parser, Symbols::OpenParen)?;
+    let condition = parse_expression(parser)?;
+    parse_symbol(parser, Symbols::CloseParen)?;
+    // while (...) {...}
+    //             ^^^^^
+    let (body, end) = parse_loop_body(parser)?;
+
+    Ok(Statement::While {
+        condition,
         body,
+        span: start.to(&end),
     })
 }
 
+/// Parse some tokens into a C-style `for` loop.
+fn parse_statement_for(parser: &mut Parser) -> StatementResult {
+    // for (...; ...; ...) {...}
+    // ^^^
+    let start = parse_keyword(parser, Keywords::For)?;
+    // for (...; ...; ...) {...}
+    //     ^
+    parse_symbol(parser, Symbols::OpenParen)?;
+    parse_for_clauses(parser, start)
+}
+
+/// Parse the clauses and body of a `for` loop after the `(`.
+fn parse_for_clauses(parser: &mut Parser, start: Span) -> StatementResult {
+    // for (...; ...; ...) {...}
+    //      ^^^^
+    let init = if parser
+        .peek(0)
+        .is_some_and(|t| t.token_type == Symbols::SemiColon.into())
+    {
+        parser.consume();
+        None
+    } else if parser
+        .peek(0)
+        .is_some_and(|t| matches!(t.token_type, TokenType::Type(_)))
+    {
+        Some(Box::new(parse_statement_declare(parser)?))
+    } else {
+        Some(Box::new(parse_statement_assign(parser)?))
+    };
+    // for (...; ...; ...) {...}
+    //           ^^^^
+    let condition = if parser
+        .peek(0)
+        .is_some_and(|t| t.token_type == Symbols::SemiColon.into())
+    {
+        None
+    } else {
+        Some(parse_expression(parser)?)
+    };
+    parse_symbol(parser, Symbols::SemiColon)?;
+    // for (...; ...; ...) {...}
+    //                ^^^^
+    let step = if parser
+        .peek(0)
+        .is_some_and(|t| t.token_type == Symbols::CloseParen.into())
+    {
+        None
+    } else {
+        Some(Box::new(parse_assignment(parser)?))
+    };
+    parse_symbol(parser, Symbols::CloseParen)?;
+    // for (...; ...; ...) {...}
+    //                     ^^^^^
+    let (body, end) = parse_loop_body(parser)?;
+
+    Ok(Statement::For {
+        init,
+        condition,
+        step,
+        body,
+        span: start.to(&end),
+    })
+}
+
+/// Parse a `{...}` block as the body of a loop returning the span of the
+/// closing `}` with it.
+fn parse_loop_body(parser: &mut Parser) -> Result<(Vec<Statement>, Span), StatementError> {
+    parse_symbol(parser, Symbols::OpenCurly)?;
+    parser.loops += 1;
+    let body = parse_block_body(parser);
+    parser.loops -= 1;
+    let body = body?;
+    let end = parse_symbol(parser, Symbols::CloseCurly)?;
+
+    Ok((body, end))
+}
+
+/// Parse some tokens into a `break` or `continue` of the enclosing loop.
+fn parse_statement_loop_control(parser: &mut Parser, keyword: Keywords) -> StatementResult {
+    // break;
+    // ^^^^^
+    let span = parse_keyword(parser, keyword.clone())?;
+    if parser.loops == 0 {
+        return Err(match keyword {
+            Keywords::Break => StatementError::BreakOutsideLoop { span },
+            _ => StatementError::ContinueOutsideLoop { span },
+        });
+    }
+    // break;
+    //      ^
+    let span = span.to(&parse_symbol(parser, Symbols::SemiColon)?);
+
+    Ok(match keyword {
+        Keywords::Break => Statement::Break { span },
+        _ => Statement::Continue { span },
+    })
+}
+
+/// Parse some tokens into an expression evaluated for its side effects.
+fn parse_statement_expression(parser: &mut Parser) -> StatementResult {
+    // f(...);
+    // ^^^^^^
+    let expression = parse_expression(parser)?;
+    // f(...);
+    //       ^
+    let end = parse_symbol(parser, Symbols::SemiColon)?;
+
+    Ok(Statement::Expression {
+        span: expression.span().to(&end),
+        expression,
+    })
+}
+
+/// Parse some tokens into a variable declaration.
+fn parse_statement_declare(parser: &mut Parser) -> StatementResult {
+    // int x = ...;
+    // ^^^
+    let (var_type, start) = parse_type(parser)?;
+    // int x = ...;
+    //     ^
+    let (name, _) = parse_name(parser)?;
+    // int x = ...;
+    //       ^
+    parse_symbol(parser, Symbols::Equals)?;
+    // int x = ...;
+    //         ^^^
+    let expression = parse_expression(parser)?;
+    // Checked before the `;` so recovery resumes after this statement.
+    if var_type == Types::Void {
+        return Err(StatementError::VoidVariable { name, span: start });
+    }
+    // int x = ...;
+    //            ^
+    let end = parse_symbol(parser, Symbols::SemiColon)?;
+
+    Ok(Statement::Declare {
+        id: NodeId::DUMMY,
+        name,
+        var_type,
+        expression,
+        span: start.to(&end),
+    })
+}
+
+/// Parse some tokens into an assignment statement.
+fn parse_statement_assign(parser: &mut Parser) -> StatementResult {
+    // x = ...;
+    // ^^^^^^^
+    let mut assign = parse_assignment(parser)?;
+    // x = ...;
+    //        ^
+    let end = parse_symbol(parser, Symbols::SemiColon)?;
+    if let Statement::Assign { span, .. } = &mut assign {
+        *span = span.to(&end);
+    }
+
+    Ok(assign)
+}
+
+/// Parse some tokens into an assignment to a declared variable without the
+/// trailing `;`.
+fn parse_assignment(parser: &mut Parser) -> StatementResult {
+    // x = ...
+    // ^
+    let (name, start) = parse_name(parser)?;
+    // x = ...
+    //   ^
+    parse_symbol(parser, Symbols::Equals)?;
+    // x = ...
+    //     ^^^
+    let expression = parse_expression(parser)?;
+
+    Ok(Statement::Assign {
+        id: NodeId::DUMMY,
+        name,
+        span: start.to(&expression.span()),
+        expression,
+    })
+}
+
+/// Consume a name token returning the name and its span.
+fn parse_name(parser: &mut Parser) -> Result<(String, Span), StatementError> {
+    match parser.consume_expecting(TokenType::SomeName("any".to_string()))? {
+        (TokenType::SomeName(name), span) => Ok((name, span)),
+        _ => unreachable!("only names are accepted where a name is expected"),
+    }
+}
+
+/// Consume a type token returning the type and its span.
+fn parse_type(parser: &mut Parser) -> Result<(Types, Span), StatementError> {
+    match parser.consume_expecting(Types::Int.into())? {
+        (TokenType::Type(t), span) => Ok((t, span)),
+        _ => unreachable!("only types are accepted where a type is expected"),
+    }
+}
+
+/// Consume the given keyword returning its span.
+fn parse_keyword(parser: &mut Parser, keyword: Keywords) -> Result<Span, StatementError> {
+    parse_token_type(parser, keyword.into())
+}
+
+/// Consume the given symbol returning its span.
+fn parse_symbol(parser: &mut Parser, symbol: Symbols) -> Result<Span, StatementError> {
+    parse_token_type(parser, symbol.into())
+}
+
+/// Consume a token of exactly the expected type returning its span.
+fn parse_token_type(parser: &mut Parser, expected: TokenType) -> Result<Span, StatementError> {
+    let (_, span) = parser.consume_expecting(expected)?;
+
+    Ok(span)
+}
+
 fn parse_statement_return(parser: &mut Parser) -> StatementResult {
     // return ...;
     // ^^^^^^
-    match parser
-        .consume()
-        .ok_or(TokenTypeError::ExpectedGotNone {
-            expected: Keywords::Return.into(),
-        })?
-        .token_type
-    {
-        TokenType::Keyword(Keywords::Return) => Ok(()),
-        t => Err(TokenTypeError::Expected {
-            expected: Keywords::Return.into(),
-            got: t,
-        }),
-    }?;
+    let start = parse_keyword(parser, Keywords::Return)?;
     // return ...;
     //        ^^^
-    let term = parse_term(parser)?;
+    let expression = match parser.peek(0) {
+        Some(t) if t.token_type == Symbols::SemiColon.into() => None,
+        _ => Some(parse_expression(parser)?),
+    };
     // return ...;
     //           ^
-    match parser
-        .consume()
-        .ok_or(TokenTypeError::ExpectedGotNone {
-            expected: Symbols::SemiColon.into(),
-        })?
-        .token_type
-    {
-        TokenType::Symbol(Symbols::SemiColon) => Ok(()),
-        t => Err(TokenTypeError::Expected {
-            expected: Symbols::SemiColon.into(),
-            got: t,
-        }),
-    }?;
-
-    Ok(Statement::Return { term })
+    let end = parse_symbol(parser, Symbols::SemiColon)?;
+
+    Ok(Statement::Return {
+        expression,
+        span: start.to(&end),
+    })
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::lexer::token::{Literals, Token, Types};
+    use crate::{
+        lexer::{
+            Lexer,
+            token::{Literals, Radix, Token, Types},
+        },
+        parser::node::{Expression, Term},
+        span::Position,
+    };
 
     use super::*;
 
+    fn lex(content: &str) -> Lexer<'_> {
+        Lexer::new(content)
+    }
+
     mod test_parse_statement {
         use crate::parser::node::Term;
 
@@ -186,18 +575,18 @@ mod tests {
         #[test]
         fn should_parse_function_declare() {
             let tokens: Vec<Token> = vec![
-                Token::new(Types::Int, 1, 1),
-                Token::new(TokenType::SomeName("main".to_string()), 1, 5),
-                Token::new(Symbols::OpenParen, 1, 9),
-                Token::new(Symbols::CloseParen, 1, 10),
-                Token::new(Symbols::OpenCurly, 1, 12),
-                Token::new(Keywords::Return, 2, 5),
-                Token::new(Literals::Integer("0".to_string()), 2, 7),
-                Token::new(Symbols::SemiColon, 2, 8),
-                Token::new(Symbols::CloseCurly, 3, 1),
+                Token::new(Types::Int, 1, 1, 0),
+                Token::new(TokenType::SomeName("main".to_string()), 1, 5, 4),
+                Token::new(Symbols::OpenParen, 1, 9, 8),
+                Token::new(Symbols::CloseParen, 1, 10, 9),
+                Token::new(Symbols::OpenCurly, 1, 12, 11),
+                Token::new(Keywords::Return, 2, 5, 17),
+                Token::new(Literals::Integer("0".to_string(), Radix::Decimal), 2, 7, 19),
+                Token::new(Symbols::SemiColon, 2, 8, 20),
+                Token::new(Symbols::CloseCurly, 3, 1, 24),
             ];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_statement(&mut parser);
 
@@ -205,11 +594,22 @@ mod tests {
             assert_eq!(
                 res.ok().unwrap(),
                 Statement::FunctionDeclare {
+                    id: NodeId::DUMMY,
                     name: "main".to_string(),
                     return_type: Types::Int,
+                    params: Vec::new(),
                     body: vec![Statement::Return {
-                        term: Term::LiteralInteger("0".to_string())
+                        expression: Some(
+                            Term::LiteralInteger {
+                                id: NodeId::DUMMY,
+                                value: 0,
+                                span: Span::on_line(Position::new(2, 7, 19), 1)
+                            }
+                            .into()
+                        ),
+                        span: Span::on_line(Position::new(2, 5, 17), 4)
                     }],
+                    span: Span::new(Position::new(1, 1, 0), Position::new(3, 2, 25))
                 }
             );
         }
@@ -217,12 +617,12 @@ mod tests {
         #[test]
         fn should_parse_return() {
             let tokens: Vec<Token> = vec![
-                Token::new(Keywords::Return, 2, 5),
-                Token::new(Literals::Integer("0".to_string()), 2, 7),
-                Token::new(Symbols::SemiColon, 2, 8),
+                Token::new(Keywords::Return, 2, 5, 5),
+                Token::new(Literals::Integer("0".to_string(), Radix::Decimal), 2, 7, 7),
+                Token::new(Symbols::SemiColon, 2, 8, 8),
             ];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_statement(&mut parser);
 
@@ -230,10 +630,82 @@ mod tests {
             assert_eq!(
                 res.ok().unwrap(),
                 Statement::Return {
-                    term: Term::LiteralInteger("0".to_string())
+                    expression: Some(
+                        Term::LiteralInteger {
+                            id: NodeId::DUMMY,
+                            value: 0,
+                            span: Span::on_line(Position::new(2, 7, 7), 1)
+                        }
+                        .into()
+                    ),
+                    span: Span::on_line(Position::new(2, 5, 5), 4)
                 }
             );
         }
+
+        #[test]
+        fn should_error_unexpected_token() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(lex(";"), &mut prog);
+
+            let res = parse_statement(&mut parser);
+
+            assert!(res.is_err_and(|e| matches!(
+                e,
+                StatementError::UnexpectedToken { token, expected, span }
+                    if token == Symbols::SemiColon.into()
+                        && expected.contains(&Keywords::Return.into())
+                        && span == Span::on_line(Position::new(1, 1, 0), 1)
+            )));
+        }
+
+        #[test]
+        fn should_describe_unexpected_token() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(lex("}"), &mut prog);
+
+            let res = parse_statement(&mut parser);
+
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                "unexpected '}', expected a type, a name, 'return', 'if', 'while', 'for', \
+                 'break' or 'continue'"
+            );
+        }
+
+        #[test]
+        fn should_error_unexpected_token_after_name() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(lex("x + 1;"), &mut prog);
+
+            let res = parse_statement(&mut parser);
+
+            assert_eq!(
+                res,
+                Err(StatementError::UnexpectedToken {
+                    token: Symbols::Plus.into(),
+                    expected: vec![Symbols::Equals.into(), Symbols::OpenParen.into()],
+                    span: Span::on_line(Position::new(1, 3, 2), 1),
+                })
+            );
+        }
+
+        #[test]
+        fn should_error_unexpected_token_after_declare_name() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(lex("int x;"), &mut prog);
+
+            let res = parse_statement(&mut parser);
+
+            assert_eq!(
+                res,
+                Err(StatementError::UnexpectedToken {
+                    token: Symbols::SemiColon.into(),
+                    expected: vec![Symbols::OpenParen.into(), Symbols::Equals.into()],
+                    span: Span::on_line(Position::new(1, 6, 5), 1),
+                })
+            );
+        }
     }
 
     mod test_parse_statement_function_declare {
@@ -244,18 +716,18 @@ mod tests {
         #[test]
         fn should_parse() {
             let tokens: Vec<Token> = vec![
-                Token::new(Types::Int, 1, 1),
-                Token::new(TokenType::SomeName("main".to_string()), 1, 5),
-                Token::new(Symbols::OpenParen, 1, 9),
-                Token::new(Symbols::CloseParen, 1, 10),
-                Token::new(Symbols::OpenCurly, 1, 12),
-                Token::new(Keywords::Return, 2, 5),
-                Token::new(Literals::Integer("0".to_string()), 2, 7),
-                Token::new(Symbols::SemiColon, 2, 8),
-                Token::new(Symbols::CloseCurly, 3, 1),
+                Token::new(Types::Int, 1, 1, 0),
+                Token::new(TokenType::SomeName("main".to_string()), 1, 5, 4),
+                Token::new(Symbols::OpenParen, 1, 9, 8),
+                Token::new(Symbols::CloseParen, 1, 10, 9),
+                Token::new(Symbols::OpenCurly, 1, 12, 11),
+                Token::new(Keywords::Return, 2, 5, 17),
+                Token::new(Literals::Integer("0".to_string(), Radix::Decimal), 2, 7, 19),
+                Token::new(Symbols::SemiColon, 2, 8, 20),
+                Token::new(Symbols::CloseCurly, 3, 1, 24),
             ];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_statement_function_declare(&mut parser);
 
@@ -263,11 +735,22 @@ mod tests {
             assert_eq!(
                 res.ok().unwrap(),
                 Statement::FunctionDeclare {
+                    id: NodeId::DUMMY,
                     name: "main".to_string(),
                     return_type: Types::Int,
+                    params: Vec::new(),
                     body: vec![Statement::Return {
-                        term: Term::LiteralInteger("0".to_string())
+                        expression: Some(
+                            Term::LiteralInteger {
+                                id: NodeId::DUMMY,
+                                value: 0,
+                                span: Span::on_line(Position::new(2, 7, 19), 1)
+                            }
+                            .into()
+                        ),
+                        span: Span::on_line(Position::new(2, 5, 17), 4)
                     }],
+                    span: Span::new(Position::new(1, 1, 0), Position::new(3, 2, 25))
                 }
             );
         }
@@ -276,7 +759,7 @@ mod tests {
         fn should_error_try_type_but_none() {
             let tokens: Vec<Token> = Vec::new();
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_statement_function_declare(&mut parser);
 
@@ -287,9 +770,9 @@ mod tests {
         }
         #[test]
         fn should_error_try_type_expected() {
-            let tokens: Vec<Token> = vec![Token::new(Keywords::Return, 2, 5)];
+            let tokens: Vec<Token> = vec![Token::new(Keywords::Return, 2, 5, 5)];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_statement_function_declare(&mut parser);
 
@@ -301,9 +784,9 @@ mod tests {
 
         #[test]
         fn should_error_try_name_but_none() {
-            let tokens: Vec<Token> = vec![Token::new(Types::Int, 1, 1)];
+            let tokens: Vec<Token> = vec![Token::new(Types::Int, 1, 1, 0)];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_statement_function_declare(&mut parser);
 
@@ -315,11 +798,11 @@ mod tests {
         #[test]
         fn should_error_try_name_expected() {
             let tokens: Vec<Token> = vec![
-                Token::new(Types::Int, 1, 1),
-                Token::new(Keywords::Return, 1, 1),
+                Token::new(Types::Int, 1, 1, 0),
+                Token::new(Keywords::Return, 1, 1, 0),
             ];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_statement_function_declare(&mut parser);
 
@@ -332,11 +815,11 @@ mod tests {
         #[test]
         fn should_error_try_open_paren_but_none() {
             let tokens: Vec<Token> = vec![
-                Token::new(Types::Int, 1, 1),
-                Token::new(TokenType::SomeName("main".to_string()), 1, 5),
+                Token::new(Types::Int, 1, 1, 0),
+                Token::new(TokenType::SomeName("main".to_string()), 1, 5, 4),
             ];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_statement_function_declare(&mut parser);
 
@@ -348,12 +831,12 @@ mod tests {
         #[test]
         fn should_error_try_open_paren_expected() {
             let tokens: Vec<Token> = vec![
-                Token::new(Types::Int, 1, 1),
-                Token::new(TokenType::SomeName("main".to_string()), 1, 5),
-                Token::new(Keywords::Return, 1, 1),
+                Token::new(Types::Int, 1, 1, 0),
+                Token::new(TokenType::SomeName("main".to_string()), 1, 5, 4),
+                Token::new(Keywords::Return, 1, 1, 0),
             ];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_statement_function_declare(&mut parser);
 
@@ -366,12 +849,12 @@ mod tests {
         #[test]
         fn should_error_try_close_paren_but_none() {
             let tokens: Vec<Token> = vec![
-                Token::new(Types::Int, 1, 1),
-                Token::new(TokenType::SomeName("main".to_string()), 1, 5),
-                Token::new(Symbols::OpenParen, 1, 9),
+                Token::new(Types::Int, 1, 1, 0),
+                Token::new(TokenType::SomeName("main".to_string()), 1, 5, 4),
+                Token::new(Symbols::OpenParen, 1, 9, 8),
             ];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_statement_function_declare(&mut parser);
 
@@ -383,13 +866,13 @@ mod tests {
         #[test]
         fn should_error_try_close_paren_expected() {
             let tokens: Vec<Token> = vec![
-                Token::new(Types::Int, 1, 1),
-                Token::new(TokenType::SomeName("main".to_string()), 1, 5),
-                Token::new(Symbols::OpenParen, 1, 9),
-                Token::new(Keywords::Return, 1, 1),
+                Token::new(Types::Int, 1, 1, 0),
+                Token::new(TokenType::SomeName("main".to_string()), 1, 5, 4),
+                Token::new(Symbols::OpenParen, 1, 9, 8),
+                Token::new(Keywords::Return, 1, 1, 0),
             ];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_statement_function_declare(&mut parser);
 
@@ -402,13 +885,13 @@ mod tests {
         #[test]
         fn should_error_try_open_curly_but_none() {
             let tokens: Vec<Token> = vec![
-                Token::new(Types::Int, 1, 1),
-                Token::new(TokenType::SomeName("main".to_string()), 1, 5),
-                Token::new(Symbols::OpenParen, 1, 9),
-                Token::new(Symbols::CloseParen, 1, 10),
+                Token::new(Types::Int, 1, 1, 0),
+                Token::new(TokenType::SomeName("main".to_string()), 1, 5, 4),
+                Token::new(Symbols::OpenParen, 1, 9, 8),
+                Token::new(Symbols::CloseParen, 1, 10, 9),
             ];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_statement_function_declare(&mut parser);
 
@@ -420,14 +903,14 @@ mod tests {
         #[test]
         fn should_error_try_open_curly_expected() {
             let tokens: Vec<Token> = vec![
-                Token::new(Types::Int, 1, 1),
-                Token::new(TokenType::SomeName("main".to_string()), 1, 5),
-                Token::new(Symbols::OpenParen, 1, 9),
-                Token::new(Symbols::CloseParen, 1, 10),
-                Token::new(Keywords::Return, 1, 1),
+                Token::new(Types::Int, 1, 1, 0),
+                Token::new(TokenType::SomeName("main".to_string()), 1, 5, 4),
+                Token::new(Symbols::OpenParen, 1, 9, 8),
+                Token::new(Symbols::CloseParen, 1, 10, 9),
+                Token::new(Keywords::Return, 1, 1, 0),
             ];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_statement_function_declare(&mut parser);
 
@@ -438,21 +921,623 @@ mod tests {
         }
 
         #[test]
-        fn should_error_missing_final_return() {
+        fn should_parse_missing_final_return() {
             let tokens: Vec<Token> = vec![
-                Token::new(Types::Int, 1, 1),
-                Token::new(TokenType::SomeName("main".to_string()), 1, 5),
-                Token::new(Symbols::OpenParen, 1, 9),
-                Token::new(Symbols::CloseParen, 1, 10),
-                Token::new(Symbols::OpenCurly, 1, 12),
-                Token::new(Symbols::CloseCurly, 3, 1),
+                Token::new(Types::Int, 1, 1, 0),
+                Token::new(TokenType::SomeName("main".to_string()), 1, 5, 4),
+                Token::new(Symbols::OpenParen, 1, 9, 8),
+                Token::new(Symbols::CloseParen, 1, 10, 9),
+                Token::new(Symbols::OpenCurly, 1, 12, 11),
+                Token::new(Symbols::CloseCurly, 3, 1, 14),
             ];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_statement_function_declare(&mut parser);
+
+            assert!(res.is_ok_and(|s| matches!(
+                s,
+                Statement::FunctionDeclare { body, .. } if body.is_empty()
+            )));
+        }
+
+        #[test]
+        fn should_parse_void_without_final_return() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(lex("void f() { return; }\nvoid g() {}"), &mut prog);
+
+            let f = parse_statement_function_declare(&mut parser);
+            let g = parse_statement_function_declare(&mut parser);
+
+            assert!(f.is_ok_and(|f| matches!(
+                f,
+                Statement::FunctionDeclare { return_type: Types::Void, body, .. }
+                    if matches!(&body[..], [Statement::Return { expression: None, .. }])
+            )));
+            assert!(g.is_ok());
+        }
+
+        #[test]
+        fn should_error_void_param() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(lex("int f(void a) { return 0; }"), &mut prog);
 
             let res = parse_statement_function_declare(&mut parser);
 
-            assert!(res.is_err_and(|e| matches!(e, StatementError::MissingReturn)));
+            assert_eq!(
+                res,
+                Err(StatementError::VoidVariable {
+                    name: "a".to_string(),
+                    span: Span::on_line(Position::new(1, 7, 6), 4)
+                })
+            );
+        }
+
+        #[test]
+        fn should_parse_params() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(lex("int add(int a, int b) { return a; }"), &mut prog);
+
+            let res = parse_statement_function_declare(&mut parser);
+
+            assert!(matches!(
+                res.ok().unwrap(),
+                Statement::FunctionDeclare { params, .. } if params == vec![
+                    Parameter { id: NodeId::DUMMY, name: "a".to_string(), param_type: Types::Int, span: Span::on_line(Position::new(1, 9, 8), 5) },
+                    Parameter { id: NodeId::DUMMY, name: "b".to_string(), param_type: Types::Int, span: Span::on_line(Position::new(1, 16, 15), 5) },
+                ]
+            ));
+        }
+
+        #[test]
+        fn should_error_param_without_type() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(lex("int add(a) { return a; }"), &mut prog);
+
+            let res = parse_statement_function_declare(&mut parser);
+
+            assert_eq!(
+                res,
+                Err(TokenTypeError::Expected {
+                    expected: Types::Int.into(),
+                    got: TokenType::SomeName("a".to_string()),
+                    span: Span::on_line(Position::new(1, 9, 8), 1),
+                }
+                .into())
+            );
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                "expected a type but found 'a'"
+            );
+        }
+
+        #[test]
+        fn should_error_trailing_comma_in_params() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(lex("int add(int a,) { return a; }"), &mut prog);
+
+            let res = parse_statement_function_declare(&mut parser);
+
+            assert!(res.is_err_and(|e| matches!(
+                e,
+                StatementError::TokenType(TokenTypeError::Expected { .. })
+            )));
+        }
+    }
+
+    mod test_parse_statement_expression {
+        use super::*;
+
+        #[test]
+        fn should_parse_call() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(lex("f(1);"), &mut prog);
+
+            let res = parse_statement(&mut parser);
+
+            assert!(matches!(
+                res.ok().unwrap(),
+                Statement::Expression {
+                    expression: Expression::Term(Term::Call { name, args, .. }),
+                    ..
+                } if name == "f" && args.len() == 1
+            ));
+        }
+
+        #[test]
+        fn should_error_try_semi_colon_expected() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(lex("f() return"), &mut prog);
+
+            let res = parse_statement(&mut parser);
+
+            assert!(res.is_err_and(|e| matches!(
+                e,
+                StatementError::TokenType(TokenTypeError::Expected { .. })
+            )));
+        }
+    }
+
+    mod test_parse_statement_if {
+        use crate::parser::node::{BinaryOp, Expression, Term};
+
+        use super::*;
+
+        fn if_tokens() -> Vec<Token> {
+            // if (1 < 2) { return 1; }
+            vec![
+                Token::new(Keywords::If, 1, 1, 0),
+                Token::new(Symbols::OpenParen, 1, 4, 3),
+                Token::new(Literals::Integer("1".to_string(), Radix::Decimal), 1, 5, 4),
+                Token::new(Symbols::LessThan, 1, 7, 6),
+                Token::new(Literals::Integer("2".to_string(), Radix::Decimal), 1, 9, 8),
+                Token::new(Symbols::CloseParen, 1, 10, 9),
+                Token::new(Symbols::OpenCurly, 1, 12, 11),
+                Token::new(Keywords::Return, 1, 14, 13),
+                Token::new(
+                    Literals::Integer("1".to_string(), Radix::Decimal),
+                    1,
+                    21,
+                    20,
+                ),
+                Token::new(Symbols::SemiColon, 1, 22, 21),
+                Token::new(Symbols::CloseCurly, 1, 24, 23),
+            ]
+        }
+
+        fn else_tokens(value: &str) -> Vec<Token> {
+            // else { return ...; }
+            vec![
+                Token::new(Keywords::Else, 1, 26, 25),
+                Token::new(Symbols::OpenCurly, 1, 31, 30),
+                Token::new(Keywords::Return, 1, 33, 32),
+                Token::new(
+                    Literals::Integer(value.to_string(), Radix::Decimal),
+                    1,
+                    40,
+                    39,
+                ),
+                Token::new(Symbols::SemiColon, 1, 41, 40),
+                Token::new(Symbols::CloseCurly, 1, 43, 42),
+            ]
+        }
+
+        fn condition() -> Expression {
+            Expression::Binary {
+                id: NodeId::DUMMY,
+                op: BinaryOp::Less,
+                lhs: Box::new(
+                    Term::LiteralInteger {
+                        id: NodeId::DUMMY,
+                        value: 1,
+                        span: Span::on_line(Position::new(1, 5, 4), 1),
+                    }
+                    .into(),
+                ),
+                rhs: Box::new(
+                    Term::LiteralInteger {
+                        id: NodeId::DUMMY,
+                        value: 2,
+                        span: Span::on_line(Position::new(1, 9, 8), 1),
+                    }
+                    .into(),
+                ),
+                span: Span::on_line(Position::new(1, 5, 4), 5),
+            }
+        }
+
+        fn ret(value: u64, column: usize) -> Statement {
+            Statement::Return {
+                expression: Some(
+                    Term::LiteralInteger {
+                        id: NodeId::DUMMY,
+                        value,
+                        span: Span::on_line(Position::new(1, column + 7, column + 6), 1),
+                    }
+                    .into(),
+                ),
+                span: Span::on_line(Position::new(1, column, column - 1), 9),
+            }
+        }
+
+        #[test]
+        fn should_parse_if() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(if_tokens().into_iter().map(Ok), &mut prog);
+
+            let res = parse_statement(&mut parser);
+
+            assert_eq!(
+                res.ok().unwrap(),
+                Statement::If {
+                    condition: condition(),
+                    body: vec![ret(1, 14)],
+                    else_body: None,
+                    span: Span::on_line(Position::new(1, 1, 0), 24)
+                }
+            );
+        }
+
+        #[test]
+        fn should_parse_if_else() {
+            let mut tokens = if_tokens();
+            tokens.extend(else_tokens("2"));
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_statement_if(&mut parser);
+
+            assert_eq!(
+                res.ok().unwrap(),
+                Statement::If {
+                    condition: condition(),
+                    body: vec![ret(1, 14)],
+                    else_body: Some(vec![ret(2, 33)]),
+                    span: Span::on_line(Position::new(1, 1, 0), 43)
+                }
+            );
+        }
+
+        #[test]
+        fn should_parse_else_if() {
+            let mut tokens = if_tokens();
+            tokens.push(Token::new(Keywords::Else, 1, 26, 25));
+            tokens.extend(if_tokens());
+            tokens.extend(else_tokens("3"));
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_statement_if(&mut parser);
+
+            assert_eq!(
+                res.ok().unwrap(),
+                Statement::If {
+                    condition: condition(),
+                    body: vec![ret(1, 14)],
+                    else_body: Some(vec![Statement::If {
+                        condition: condition(),
+                        body: vec![ret(1, 14)],
+                        else_body: Some(vec![ret(3, 33)]),
+                        span: Span::on_line(Position::new(1, 1, 0), 43)
+                    }]),
+                    span: Span::on_line(Position::new(1, 1, 0), 43)
+                }
+            );
+        }
+
+        #[test]
+        fn should_error_try_open_paren_expected() {
+            let mut tokens = if_tokens();
+            tokens.remove(1);
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_statement_if(&mut parser);
+
+            assert!(res.is_err_and(|e| matches!(
+                e,
+                StatementError::TokenType(TokenTypeError::Expected { .. })
+            )));
+        }
+
+        #[test]
+        fn should_error_try_close_curly_but_none() {
+            let mut tokens = if_tokens();
+            tokens.pop();
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_statement_if(&mut parser);
+
+            assert!(res.is_err_and(|e| matches!(
+                e,
+                StatementError::TokenType(TokenTypeError::ExpectedSomeGotNone { .. })
+            )));
+        }
+
+        #[test]
+        fn should_error_else_without_body() {
+            let mut tokens = if_tokens();
+            tokens.push(Token::new(Keywords::Else, 1, 26, 25));
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_statement_if(&mut parser);
+
+            assert!(res.is_err_and(|e| matches!(
+                e,
+                StatementError::TokenType(TokenTypeError::ExpectedGotNone { .. })
+            )));
+        }
+    }
+
+    mod test_parse_statement_loops {
+        use crate::error::TermError;
+
+        use super::*;
+
+        #[test]
+        fn should_parse_while() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(lex("while (1) { break; continue; }"), &mut prog);
+
+            let res = parse_statement(&mut parser);
+
+            assert!(matches!(
+                res.ok().unwrap(),
+                Statement::While { body, .. } if body == vec![
+                    Statement::Break { span: Span::on_line(Position::new(1, 13, 12), 6) },
+                    Statement::Continue { span: Span::on_line(Position::new(1, 20, 19), 9) },
+                ]
+            ));
+            assert_eq!(parser.loops, 0);
+        }
+
+        #[test]
+        fn should_parse_for() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(
+                lex("for (int i = 0; i < 10; i = i + 1) { break; }"),
+                &mut prog,
+            );
+
+            let res = parse_statement(&mut parser);
+
+            assert!(matches!(
+                res.ok().unwrap(),
+                Statement::For {
+                    init: Some(init),
+                    condition: Some(_),
+                    step: Some(step),
+                    body, ..
+                } if matches!(*init, Statement::Declare { .. })
+                    && matches!(*step, Statement::Assign { .. })
+                    && body == vec![Statement::Break {
+                        span: Span::on_line(Position::new(1, 38, 37), 6)
+                    }]
+            ));
+        }
+
+        #[test]
+        fn should_parse_for_without_clauses() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(lex("for (;;) {}"), &mut prog);
+
+            let res = parse_statement(&mut parser);
+
+            assert_eq!(
+                res.ok().unwrap(),
+                Statement::For {
+                    init: None,
+                    condition: None,
+                    step: None,
+                    body: Vec::new(),
+                    span: Span::on_line(Position::new(1, 1, 0), 11)
+                }
+            );
+        }
+
+        #[test]
+        fn should_parse_nested_loop_control() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(
+                lex("while (1) { if (1) { while (1) {} continue; } }"),
+                &mut prog,
+            );
+
+            let res = parse_statement(&mut parser);
+
+            assert!(res.is_ok());
+        }
+
+        #[test]
+        fn should_error_break_outside_loop() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(lex("break;"), &mut prog);
+
+            let res = parse_statement(&mut parser);
+
+            assert!(res.is_err_and(|e| matches!(
+                e,
+                StatementError::BreakOutsideLoop { span } if span == Span::on_line(Position::new(1, 1, 0), 5)
+            )));
+        }
+
+        #[test]
+        fn should_error_continue_after_loop() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(lex("while (1) {} continue;"), &mut prog);
+
+            assert!(parse_statement(&mut parser).is_ok());
+            let res = parse_statement(&mut parser);
+
+            assert!(res.is_err_and(|e| matches!(e, StatementError::ContinueOutsideLoop { .. })));
+        }
+
+        #[test]
+        fn should_error_for_missing_semi_colon() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(lex("for (; 1) {}"), &mut prog);
+
+            let res = parse_statement(&mut parser);
+
+            assert!(res.is_err_and(|e| matches!(
+                e,
+                StatementError::TokenType(TokenTypeError::Expected { .. })
+            )));
+        }
+
+        #[test]
+        fn should_error_while_without_condition() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(lex("while () {}"), &mut prog);
+
+            let res = parse_statement(&mut parser);
+
+            assert!(
+                res.is_err_and(|e| matches!(e, StatementError::Term(TermError::NoTerm { .. })))
+            );
+        }
+    }
+
+    mod test_parse_statement_declare {
+        use crate::parser::node::Term;
+
+        use super::*;
+
+        #[test]
+        fn should_error_void_variable() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(lex("void x = 1;"), &mut prog);
+
+            let res = parse_statement(&mut parser);
+
+            assert_eq!(
+                res,
+                Err(StatementError::VoidVariable {
+                    name: "x".to_string(),
+                    span: Span::on_line(Position::new(1, 1, 0), 4)
+                })
+            );
+        }
+
+        #[test]
+        fn should_parse() {
+            let tokens: Vec<Token> = vec![
+                Token::new(Types::Int, 1, 1, 0),
+                Token::new(TokenType::SomeName("x".to_string()), 1, 5, 4),
+                Token::new(Symbols::Equals, 1, 7, 6),
+                Token::new(Literals::Integer("5".to_string(), Radix::Decimal), 1, 9, 8),
+                Token::new(Symbols::SemiColon, 1, 10, 9),
+            ];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_statement(&mut parser);
+
+            assert_eq!(
+                res.ok().unwrap(),
+                Statement::Declare {
+                    id: NodeId::DUMMY,
+                    name: "x".to_string(),
+                    var_type: Types::Int,
+                    expression: Term::LiteralInteger {
+                        id: NodeId::DUMMY,
+                        value: 5,
+                        span: Span::on_line(Position::new(1, 9, 8), 1)
+                    }
+                    .into(),
+                    span: Span::on_line(Position::new(1, 1, 0), 10)
+                }
+            );
+        }
+
+        #[test]
+        fn should_error_try_equals_expected() {
+            let tokens: Vec<Token> = vec![
+                Token::new(Types::Int, 1, 1, 0),
+                Token::new(TokenType::SomeName("x".to_string()), 1, 5, 4),
+                Token::new(Symbols::SemiColon, 1, 7, 6),
+            ];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_statement_declare(&mut parser);
+
+            assert!(res.is_err_and(|e| matches!(
+                e,
+                StatementError::TokenType(TokenTypeError::Expected { .. })
+            )));
+        }
+
+        #[test]
+        fn should_error_try_semi_colon_but_none() {
+            let tokens: Vec<Token> = vec![
+                Token::new(Types::Int, 1, 1, 0),
+                Token::new(TokenType::SomeName("x".to_string()), 1, 5, 4),
+                Token::new(Symbols::Equals, 1, 7, 6),
+                Token::new(Literals::Integer("5".to_string(), Radix::Decimal), 1, 9, 8),
+            ];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_statement_declare(&mut parser);
+
+            assert!(res.is_err_and(|e| matches!(
+                e,
+                StatementError::TokenType(TokenTypeError::ExpectedGotNone { .. })
+            )));
+        }
+    }
+
+    mod test_parse_statement_assign {
+        use crate::parser::node::{BinaryOp, Expression, Term};
+
+        use super::*;
+
+        #[test]
+        fn should_parse() {
+            let tokens: Vec<Token> = vec![
+                Token::new(TokenType::SomeName("x".to_string()), 1, 1, 0),
+                Token::new(Symbols::Equals, 1, 3, 2),
+                Token::new(TokenType::SomeName("x".to_string()), 1, 5, 4),
+                Token::new(Symbols::Plus, 1, 7, 6),
+                Token::new(Literals::Integer("1".to_string(), Radix::Decimal), 1, 9, 8),
+                Token::new(Symbols::SemiColon, 1, 10, 9),
+            ];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_statement(&mut parser);
+
+            assert_eq!(
+                res.ok().unwrap(),
+                Statement::Assign {
+                    id: NodeId::DUMMY,
+                    name: "x".to_string(),
+                    expression: Expression::Binary {
+                        id: NodeId::DUMMY,
+                        op: BinaryOp::Add,
+                        lhs: Box::new(
+                            Term::Identifier {
+                                id: NodeId::DUMMY,
+                                name: "x".to_string(),
+                                span: Span::on_line(Position::new(1, 5, 4), 1)
+                            }
+                            .into()
+                        ),
+                        rhs: Box::new(
+                            Term::LiteralInteger {
+                                id: NodeId::DUMMY,
+                                value: 1,
+                                span: Span::on_line(Position::new(1, 9, 8), 1)
+                            }
+                            .into()
+                        ),
+                        span: Span::on_line(Position::new(1, 5, 4), 5)
+                    },
+                    span: Span::on_line(Position::new(1, 1, 0), 10)
+                }
+            );
+        }
+
+        #[test]
+        fn should_error_try_semi_colon_expected() {
+            let tokens: Vec<Token> = vec![
+                Token::new(TokenType::SomeName("x".to_string()), 1, 1, 0),
+                Token::new(Symbols::Equals, 1, 3, 2),
+                Token::new(Literals::Integer("1".to_string(), Radix::Decimal), 1, 5, 4),
+                Token::new(Symbols::CloseParen, 1, 6, 5),
+            ];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_statement_assign(&mut parser);
+
+            assert!(res.is_err_and(|e| matches!(
+                e,
+                StatementError::TokenType(TokenTypeError::Expected { .. })
+            )));
         }
     }
 
@@ -465,12 +1550,12 @@ mod tests {
         #[test]
         fn should_parse() {
             let tokens: Vec<Token> = vec![
-                Token::new(Keywords::Return, 1, 5),
-                Token::new(Literals::Integer("0".to_string()), 1, 7),
-                Token::new(Symbols::SemiColon, 1, 8),
+                Token::new(Keywords::Return, 1, 5, 4),
+                Token::new(Literals::Integer("0".to_string(), Radix::Decimal), 1, 7, 6),
+                Token::new(Symbols::SemiColon, 1, 8, 7),
             ];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_statement_return(&mut parser);
 
@@ -478,16 +1563,40 @@ mod tests {
             assert_eq!(
                 res.ok().unwrap(),
                 Statement::Return {
-                    term: Term::LiteralInteger("0".to_string())
+                    expression: Some(
+                        Term::LiteralInteger {
+                            id: NodeId::DUMMY,
+                            value: 0,
+                            span: Span::on_line(Position::new(1, 7, 6), 1)
+                        }
+                        .into()
+                    ),
+                    span: Span::on_line(Position::new(1, 5, 4), 4)
                 }
             );
         }
 
+        #[test]
+        fn should_parse_without_value() {
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(lex("return;"), &mut prog);
+
+            let res = parse_statement_return(&mut parser);
+
+            assert_eq!(
+                res,
+                Ok(Statement::Return {
+                    expression: None,
+                    span: Span::on_line(Position::new(1, 1, 0), 7)
+                })
+            );
+        }
+
         #[test]
         fn should_error_try_return_but_none() {
             let tokens: Vec<Token> = Vec::new();
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_statement_return(&mut parser);
 
@@ -496,9 +1605,9 @@ mod tests {
 
         #[test]
         fn should_error_try_return_expected() {
-            let tokens: Vec<Token> = vec![Token::new(Symbols::SemiColon, 0, 0)];
+            let tokens: Vec<Token> = vec![Token::new(Symbols::SemiColon, 0, 0, 0)];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_statement_return(&mut parser);
 
@@ -507,9 +1616,9 @@ mod tests {
 
         #[test]
         fn should_error_try_term_but_none() {
-            let tokens: Vec<Token> = vec![Token::new(Keywords::Return, 1, 5)];
+            let tokens: Vec<Token> = vec![Token::new(Keywords::Return, 1, 5, 4)];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_statement_return(&mut parser);
 
@@ -519,11 +1628,11 @@ mod tests {
         #[test]
         fn should_error_try_term_expected() {
             let tokens: Vec<Token> = vec![
-                Token::new(Keywords::Return, 1, 5),
-                Token::new(Symbols::OpenParen, 0, 0),
+                Token::new(Keywords::Return, 1, 5, 4),
+                Token::new(Symbols::OpenParen, 0, 0, 0),
             ];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_statement_return(&mut parser);
 
@@ -533,11 +1642,11 @@ mod tests {
         #[test]
         fn should_error_try_semi_colon_but_none() {
             let tokens: Vec<Token> = vec![
-                Token::new(Keywords::Return, 1, 5),
-                Token::new(Literals::Integer("0".to_string()), 1, 7),
+                Token::new(Keywords::Return, 1, 5, 4),
+                Token::new(Literals::Integer("0".to_string(), Radix::Decimal), 1, 7, 6),
             ];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_statement_return(&mut parser);
 
@@ -547,12 +1656,12 @@ mod tests {
         #[test]
         fn should_error_try_semi_colon_expected() {
             let tokens: Vec<Token> = vec![
-                Token::new(Keywords::Return, 1, 5),
-                Token::new(Literals::Integer("0".to_string()), 1, 7),
-                Token::new(Symbols::OpenParen, 1, 8),
+                Token::new(Keywords::Return, 1, 5, 4),
+                Token::new(Literals::Integer("0".to_string(), Radix::Decimal), 1, 7, 6),
+                Token::new(Symbols::OpenParen, 1, 8, 7),
             ];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_statement_return(&mut parser);
 
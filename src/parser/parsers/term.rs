@@ -1,49 +1,287 @@
 use crate::{
     error::{TermError, TermResult, TokenTypeError},
-    lexer::token::{Literals, TokenType},
-    parser::{Parser, node::Term},
+    lexer::{
+        token::{Literals, Radix, Symbols, TokenType, Types, float_value},
+        unescape, unescape_char,
+    },
+    parser::{
+        Parser,
+        node::{Expression, NodeId, Term},
+        parsers::expression::parse_expression,
+    },
+    span::Position,
 };
 
 pub fn parse_term(parser: &mut Parser) -> TermResult {
     if parser
         .peek(0)
-        .is_some_and(|t| matches!(t.token_type, TokenType::Literal(Literals::Integer(_))))
+        .is_some_and(|t| matches!(t.token_type, TokenType::Literal(Literals::Integer(..))))
     {
-        return parse_term_literal_int(parser);
+        return parse_term_literal_int(parser, false);
+    } else if parser
+        .peek(0)
+        .is_some_and(|t| matches!(t.token_type, TokenType::Literal(Literals::Float(_))))
+    {
+        return parse_term_literal_float(parser);
+    } else if parser.peek(0).is_some_and(|t| {
+        matches!(
+            t.token_type,
+            TokenType::Literal(Literals::String(_) | Literals::Char(_))
+        )
+    }) {
+        return parse_term_literal_text(parser);
+    } else if parser
+        .peek(0)
+        .is_some_and(|t| matches!(t.token_type, TokenType::Literal(Literals::Bool(_))))
+    {
+        return parse_term_literal_bool(parser);
+    } else if parser
+        .peek(0)
+        .is_some_and(|t| matches!(t.token_type, TokenType::SomeName(_)))
+        && parser
+            .peek(1)
+            .is_some_and(|t| t.token_type == Symbols::OpenParen.into())
+    {
+        return parse_term_call(parser);
+    } else if parser
+        .peek(0)
+        .is_some_and(|t| matches!(t.token_type, TokenType::SomeName(_)))
+    {
+        return parse_term_identifier(parser);
+    } else if parser
+        .peek(0)
+        .is_some_and(|t| t.token_type == Symbols::OpenParen.into())
+    {
+        return parse_term_parenthesised(parser);
     }
 
-    Err(TermError::NoTerm)
+    Err(TermError::NoTerm {
+        span: parser.peek_span(),
+    })
 }
 
-fn parse_term_literal_int(parser: &mut Parser) -> TermResult {
-    let value: String = match parser
-        .consume()
-        .ok_or(TokenTypeError::ExpectedSomeGotNone)?
-        .token_type
-    {
-        TokenType::Literal(Literals::Integer(int)) => Ok(int),
+/// Parse an integer literal, which may be as large as the magnitude of the
+/// smallest `i64` if it is `negated` and the largest `u64` otherwise.
+pub fn parse_term_literal_int(parser: &mut Parser, negated: bool) -> TermResult {
+    let (token_type, span) = parser.consume_some()?;
+    let (int, radix) = match token_type {
+        TokenType::Literal(Literals::Integer(int, radix)) => Ok((int, radix)),
+        t => Err(TokenTypeError::Expected {
+            expected: Literals::Integer("any".to_string(), Radix::Decimal).into(),
+            got: t,
+            span,
+        }),
+    }?;
+
+    // Whether the literal fits the type it is used as is checked once the
+    // types are known.
+    let target = if negated { Types::I64 } else { Types::U64 };
+    let max = if negated {
+        target.min_value().unsigned_abs()
+    } else {
+        target.max_value()
+    };
+    let value = radix
+        .value(&int)
+        .filter(|value| *value <= max)
+        .ok_or_else(|| TermError::LiteralOutOfRange {
+            value: if negated { format!("-{int}") } else { int },
+            target,
+            span,
+        })?;
+
+    Ok(Term::LiteralInteger {
+        id: NodeId::DUMMY,
+        value,
+        span,
+    })
+}
+
+fn parse_term_literal_float(parser: &mut Parser) -> TermResult {
+    let (token_type, span) = parser.consume_some()?;
+    let float = match token_type {
+        TokenType::Literal(Literals::Float(float)) => Ok(float),
         t => Err(TokenTypeError::Expected {
-            expected: Literals::Integer("any".to_string()).into(),
+            expected: Literals::Float("any".to_string()).into(),
             got: t,
+            span,
         }),
     }?;
 
-    Ok(Term::LiteralInteger(value))
+    // Every floating-point literal is a `double`, too large ones are infinite.
+    let value = float_value(&float)
+        .filter(|value| value.is_finite())
+        .ok_or(TermError::LiteralOutOfRange {
+            value: float,
+            target: Types::Double,
+            span,
+        })?;
+
+    Ok(Term::LiteralFloat {
+        id: NodeId::DUMMY,
+        value,
+        span,
+    })
+}
+
+fn parse_term_literal_bool(parser: &mut Parser) -> TermResult {
+    let (token_type, span) = parser.consume_some()?;
+    match token_type {
+        TokenType::Literal(Literals::Bool(value)) => Ok(Term::LiteralBool {
+            id: NodeId::DUMMY,
+            value,
+            span,
+        }),
+        t => Err(TokenTypeError::Expected {
+            expected: Literals::Bool(true).into(),
+            got: t,
+            span,
+        }
+        .into()),
+    }
+}
+
+/// Parse a string or character literal replacing its escape sequences.
+fn parse_term_literal_text(parser: &mut Parser) -> TermResult {
+    let (token_type, span) = parser.consume_some()?;
+    let contents = Position::new(
+        span.start.line,
+        span.start.column + 1,
+        span.start.offset + 1,
+    );
+
+    match token_type {
+        TokenType::Literal(Literals::String(string)) => Ok(Term::LiteralString {
+            id: NodeId::DUMMY,
+            value: unescape(&string, contents)?,
+            span,
+        }),
+        TokenType::Literal(Literals::Char(char)) => Ok(Term::LiteralChar {
+            id: NodeId::DUMMY,
+            value: unescape_char(&char, span.start)?,
+            span,
+        }),
+        t => Err(TokenTypeError::Expected {
+            expected: Literals::String("any".to_string()).into(),
+            got: t,
+            span,
+        }
+        .into()),
+    }
+}
+
+fn parse_term_identifier(parser: &mut Parser) -> TermResult {
+    let (token_type, span) = parser.consume_some()?;
+    let name: String = match token_type {
+        TokenType::SomeName(name) => Ok(name),
+        t => Err(TokenTypeError::Expected {
+            expected: TokenType::SomeName("any".to_string()),
+            got: t,
+            span,
+        }),
+    }?;
+
+    Ok(Term::Identifier {
+        id: NodeId::DUMMY,
+        name,
+        span,
+    })
+}
+
+/// Parse some tokens into a call of a function.
+///
+/// The callee is not checked here as functions may be declared after they
+/// are called.
+fn parse_term_call(parser: &mut Parser) -> TermResult {
+    // add(..., ...)
+    // ^^^
+    let (token_type, name_span) = parser.consume_some()?;
+    let name: String = match token_type {
+        TokenType::SomeName(name) => Ok(name),
+        t => Err(TokenTypeError::Expected {
+            expected: TokenType::SomeName("any".to_string()),
+            got: t,
+            span: name_span,
+        }),
+    }?;
+    // add(..., ...)
+    //    ^
+    parser.consume_expecting(Symbols::OpenParen.into())?;
+    // add(..., ...)
+    //     ^^^^^^^^^
+    let mut args: Vec<Expression> = Vec::new();
+    if parser
+        .peek(0)
+        .is_some_and(|t| t.token_type == Symbols::CloseParen.into())
+    {
+        let (_, span) = parser.consume_some()?;
+        return Ok(Term::Call {
+            id: NodeId::DUMMY,
+            name,
+            args,
+            span: name_span.to(&span),
+        });
+    }
+    loop {
+        args.push(parse_expression(parser)?);
+        if parser
+            .peek(0)
+            .is_some_and(|t| t.token_type == Symbols::Comma.into())
+        {
+            parser.consume();
+            continue;
+        }
+        let (_, span) = parser.consume_expecting(Symbols::CloseParen.into())?;
+
+        return Ok(Term::Call {
+            id: NodeId::DUMMY,
+            name,
+            args,
+            span: name_span.to(&span),
+        });
+    }
+}
+
+fn parse_term_parenthesised(parser: &mut Parser) -> TermResult {
+    // (...)
+    // ^
+    let (_, open_span) = parser.consume_expecting(Symbols::OpenParen.into())?;
+    // (...)
+    //  ^^^
+    let expression = parse_expression(parser)?;
+    // (...)
+    //     ^
+    let (_, span) = parser.consume_expecting(Symbols::CloseParen.into())?;
+
+    Ok(Term::Parenthesised {
+        id: NodeId::DUMMY,
+        expression: Box::new(expression),
+        span: open_span.to(&span),
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{lexer::token::Token, parser::node::Statement};
+    use crate::{
+        lexer::token::Token,
+        parser::node::Statement,
+        span::{Position, Span},
+    };
 
     mod test_parse_term {
         use super::*;
 
         #[test]
         fn should_parse() {
-            let tokens: Vec<Token> = vec![Token::new(Literals::Integer("0".to_string()), 1, 7)];
+            let tokens: Vec<Token> = vec![Token::new(
+                Literals::Integer("0".to_string(), Radix::Decimal),
+                1,
+                7,
+                6,
+            )];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_term(&mut parser);
 
@@ -54,12 +292,17 @@ mod tests {
         fn should_error() {
             let tokens: Vec<Token> = Vec::new();
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
             let res = parse_term(&mut parser);
 
             assert!(res.is_err());
-            assert_eq!(res.err().unwrap(), TermError::NoTerm);
+            assert_eq!(
+                res.err().unwrap(),
+                TermError::NoTerm {
+                    span: Span::on_line(Position::new(1, 1, 0), 0)
+                }
+            );
         }
     }
 
@@ -70,48 +313,442 @@ mod tests {
 
         #[test]
         fn should_parse() {
-            let tokens: Vec<Token> = vec![Token::new(Literals::Integer("0".to_string()), 1, 7)];
+            let tokens: Vec<Token> = vec![Token::new(
+                Literals::Integer("0".to_string(), Radix::Decimal),
+                1,
+                7,
+                6,
+            )];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
-            let res = parse_term_literal_int(&mut parser);
+            let res = parse_term_literal_int(&mut parser, false);
 
             assert!(res.is_ok());
-            assert_eq!(res.unwrap(), Term::LiteralInteger("0".to_string()));
+            assert_eq!(
+                res.unwrap(),
+                Term::LiteralInteger {
+                    id: NodeId::DUMMY,
+                    value: 0,
+                    span: Span::on_line(Position::new(1, 7, 6), 1)
+                }
+            );
+        }
+
+        #[test]
+        fn should_parse_largest_int() {
+            let tokens: Vec<Token> = vec![Token::new(
+                Literals::Integer("18446744073709551615".to_string(), Radix::Decimal),
+                1,
+                1,
+                0,
+            )];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_term_literal_int(&mut parser, false);
+
+            assert!(res.is_ok_and(|t| matches!(
+                t,
+                Term::LiteralInteger {
+                    value: 18446744073709551615,
+                    ..
+                }
+            )));
+        }
+
+        #[test]
+        fn should_error_out_of_range() {
+            let tokens: Vec<Token> = vec![Token::new(
+                Literals::Integer("18446744073709551616".to_string(), Radix::Decimal),
+                1,
+                8,
+                7,
+            )];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_term_literal_int(&mut parser, false);
+
+            assert!(res.as_ref().is_err_and(|e| e.to_string()
+                == "literal 18446744073709551616 does not fit in 'u64'"
+                && e.span() == Span::on_line(Position::new(1, 8, 7), 20)));
         }
 
         #[test]
         fn should_error_none() {
             let tokens: Vec<Token> = Vec::new();
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
-            let res = parse_term_literal_int(&mut parser);
+            let res = parse_term_literal_int(&mut parser, false);
 
             assert!(res.is_err());
             assert_eq!(
                 res.err().unwrap(),
-                TokenTypeError::ExpectedSomeGotNone.into()
+                TokenTypeError::ExpectedSomeGotNone {
+                    span: Span::on_line(Position::new(1, 1, 0), 0)
+                }
+                .into()
             )
         }
 
         #[test]
         fn should_error_expected() {
-            let tokens: Vec<Token> = vec![Token::new(Symbols::OpenParen, 0, 0)];
+            let tokens: Vec<Token> = vec![Token::new(Symbols::OpenParen, 0, 0, 0)];
             let mut prog: Vec<Statement> = Vec::new();
-            let mut parser = Parser::new(tokens, &mut prog);
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
 
-            let res = parse_term_literal_int(&mut parser);
+            let res = parse_term_literal_int(&mut parser, false);
 
             assert!(res.is_err());
             assert_eq!(
                 res.err().unwrap(),
                 TokenTypeError::Expected {
-                    expected: Literals::Integer("any".to_string()).into(),
+                    expected: Literals::Integer("any".to_string(), Radix::Decimal).into(),
                     got: Symbols::OpenParen.into(),
+                    span: Span::on_line(Position::new(0, 0, 0), 1),
                 }
                 .into()
             )
         }
     }
+
+    mod test_parse_term_literal_bool {
+        use super::*;
+
+        #[test]
+        fn should_parse() {
+            let tokens: Vec<Token> = vec![Token::new(Literals::Bool(false), 1, 1, 0)];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            assert_eq!(
+                parse_term(&mut parser),
+                Ok(Term::LiteralBool {
+                    id: NodeId::DUMMY,
+                    value: false,
+                    span: Span::on_line(Position::new(1, 1, 0), 5)
+                })
+            );
+        }
+    }
+
+    mod test_parse_term_literal_float {
+        use super::*;
+
+        #[test]
+        fn should_parse() {
+            let tokens: Vec<Token> =
+                vec![Token::new(Literals::Float("1.5e2".to_string()), 1, 1, 0)];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            assert_eq!(
+                parse_term(&mut parser),
+                Ok(Term::LiteralFloat {
+                    id: NodeId::DUMMY,
+                    value: 150.0,
+                    span: Span::on_line(Position::new(1, 1, 0), 5)
+                })
+            );
+        }
+
+        #[test]
+        fn should_error_infinite() {
+            let tokens: Vec<Token> =
+                vec![Token::new(Literals::Float("1e400".to_string()), 1, 1, 0)];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            assert_eq!(
+                parse_term_literal_float(&mut parser),
+                Err(TermError::LiteralOutOfRange {
+                    value: "1e400".to_string(),
+                    target: Types::Double,
+                    span: Span::on_line(Position::new(1, 1, 0), 5)
+                })
+            );
+        }
+    }
+
+    mod test_parse_term_literal_text {
+        use super::*;
+
+        #[test]
+        fn should_parse() {
+            let tokens: Vec<Token> = vec![
+                Token::new(Literals::String(r"a\tb".to_string()), 1, 1, 0),
+                Token::new(Literals::Char(r"\x41".to_string()), 1, 8, 7),
+            ];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            assert_eq!(
+                parse_term_literal_text(&mut parser),
+                Ok(Term::LiteralString {
+                    id: NodeId::DUMMY,
+                    value: "a\tb".to_string(),
+                    span: Span::on_line(Position::new(1, 1, 0), 6)
+                })
+            );
+            assert_eq!(
+                parse_term_literal_text(&mut parser),
+                Ok(Term::LiteralChar {
+                    id: NodeId::DUMMY,
+                    value: 'A',
+                    span: Span::on_line(Position::new(1, 8, 7), 6)
+                })
+            );
+        }
+    }
+
+    mod test_parse_term_identifier {
+        use super::*;
+
+        #[test]
+        fn should_parse() {
+            let tokens: Vec<Token> =
+                vec![Token::new(TokenType::SomeName("x".to_string()), 1, 1, 0)];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_term_identifier(&mut parser);
+
+            assert_eq!(
+                res,
+                Ok(Term::Identifier {
+                    id: NodeId::DUMMY,
+                    name: "x".to_string(),
+                    span: Span::on_line(Position::new(1, 1, 0), 1)
+                })
+            );
+        }
+
+        #[test]
+        fn should_error_expected() {
+            let tokens: Vec<Token> = vec![Token::new(
+                Literals::Integer("0".to_string(), Radix::Decimal),
+                1,
+                1,
+                0,
+            )];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_term_identifier(&mut parser);
+
+            assert!(res.is_err_and(|e| matches!(
+                e,
+                TermError::TokenType(TokenTypeError::Expected { .. })
+            )));
+        }
+    }
+
+    mod test_parse_term_parenthesised {
+        use crate::lexer::token::Symbols;
+
+        use super::*;
+
+        #[test]
+        fn should_parse() {
+            let tokens: Vec<Token> = vec![
+                Token::new(Symbols::OpenParen, 1, 1, 0),
+                Token::new(Literals::Integer("0".to_string(), Radix::Decimal), 1, 2, 1),
+                Token::new(Symbols::CloseParen, 1, 3, 2),
+            ];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_term_parenthesised(&mut parser);
+
+            assert_eq!(
+                res,
+                Ok(Term::Parenthesised {
+                    id: NodeId::DUMMY,
+                    expression: Box::new(
+                        Term::LiteralInteger {
+                            id: NodeId::DUMMY,
+                            value: 0,
+                            span: Span::on_line(Position::new(1, 2, 1), 1)
+                        }
+                        .into()
+                    ),
+                    span: Span::on_line(Position::new(1, 1, 0), 3)
+                })
+            );
+        }
+
+        #[test]
+        fn should_error_try_open_paren_expected() {
+            let tokens: Vec<Token> = vec![Token::new(Symbols::SemiColon, 1, 1, 0)];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_term_parenthesised(&mut parser);
+
+            assert!(res.is_err_and(|e| matches!(
+                e,
+                TermError::TokenType(TokenTypeError::Expected { .. })
+            )));
+        }
+
+        #[test]
+        fn should_error_try_close_paren_but_none() {
+            let tokens: Vec<Token> = vec![
+                Token::new(Symbols::OpenParen, 1, 1, 0),
+                Token::new(Literals::Integer("0".to_string(), Radix::Decimal), 1, 2, 1),
+            ];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_term_parenthesised(&mut parser);
+
+            assert!(res.is_err_and(|e| matches!(
+                e,
+                TermError::TokenType(TokenTypeError::ExpectedGotNone { .. })
+            )));
+        }
+
+        #[test]
+        fn should_error_try_close_paren_expected() {
+            let tokens: Vec<Token> = vec![
+                Token::new(Symbols::OpenParen, 1, 1, 0),
+                Token::new(Literals::Integer("0".to_string(), Radix::Decimal), 1, 2, 1),
+                Token::new(Symbols::SemiColon, 1, 3, 2),
+            ];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_term_parenthesised(&mut parser);
+
+            assert!(res.is_err_and(|e| matches!(
+                e,
+                TermError::TokenType(TokenTypeError::Expected { .. })
+            )));
+        }
+    }
+
+    mod test_parse_term_call {
+        use crate::lexer::token::Symbols;
+
+        use super::*;
+
+        fn name(name: &str) -> Token {
+            Token::new(TokenType::SomeName(name.to_string()), 1, 1, 0)
+        }
+
+        #[test]
+        fn should_parse_without_args() {
+            let tokens: Vec<Token> = vec![
+                name("f"),
+                Token::new(Symbols::OpenParen, 1, 2, 1),
+                Token::new(Symbols::CloseParen, 1, 3, 2),
+            ];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_term(&mut parser);
+
+            assert_eq!(
+                res,
+                Ok(Term::Call {
+                    id: NodeId::DUMMY,
+                    name: "f".to_string(),
+                    args: Vec::new(),
+                    span: Span::on_line(Position::new(1, 1, 0), 3),
+                })
+            );
+        }
+
+        #[test]
+        fn should_parse_args() {
+            // f(1, (2))
+            let tokens: Vec<Token> = vec![
+                name("f"),
+                Token::new(Symbols::OpenParen, 1, 2, 1),
+                Token::new(Literals::Integer("1".to_string(), Radix::Decimal), 1, 3, 2),
+                Token::new(Symbols::Comma, 1, 4, 3),
+                Token::new(Symbols::OpenParen, 1, 6, 5),
+                Token::new(Literals::Integer("2".to_string(), Radix::Decimal), 1, 7, 6),
+                Token::new(Symbols::CloseParen, 1, 8, 7),
+                Token::new(Symbols::CloseParen, 1, 9, 8),
+            ];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_term(&mut parser);
+
+            assert_eq!(
+                res,
+                Ok(Term::Call {
+                    id: NodeId::DUMMY,
+                    name: "f".to_string(),
+                    args: vec![
+                        Term::LiteralInteger {
+                            id: NodeId::DUMMY,
+                            value: 1,
+                            span: Span::on_line(Position::new(1, 3, 2), 1)
+                        }
+                        .into(),
+                        Term::Parenthesised {
+                            id: NodeId::DUMMY,
+                            expression: Box::new(
+                                Term::LiteralInteger {
+                                    id: NodeId::DUMMY,
+                                    value: 2,
+                                    span: Span::on_line(Position::new(1, 7, 6), 1)
+                                }
+                                .into()
+                            ),
+                            span: Span::on_line(Position::new(1, 6, 5), 3)
+                        }
+                        .into(),
+                    ],
+                    span: Span::on_line(Position::new(1, 1, 0), 9),
+                })
+            );
+        }
+
+        #[test]
+        fn should_error_trailing_comma() {
+            let tokens: Vec<Token> = vec![
+                name("f"),
+                Token::new(Symbols::OpenParen, 1, 2, 1),
+                Token::new(Literals::Integer("1".to_string(), Radix::Decimal), 1, 3, 2),
+                Token::new(Symbols::Comma, 1, 4, 3),
+                Token::new(Symbols::CloseParen, 1, 5, 4),
+            ];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_term(&mut parser);
+
+            assert_eq!(
+                res,
+                Err(TermError::NoTerm {
+                    span: Span::on_line(Position::new(1, 5, 4), 1)
+                })
+            );
+        }
+
+        #[test]
+        fn should_error_try_close_paren_expected() {
+            let tokens: Vec<Token> = vec![
+                name("f"),
+                Token::new(Symbols::OpenParen, 1, 2, 1),
+                Token::new(Literals::Integer("1".to_string(), Radix::Decimal), 1, 3, 2),
+                Token::new(Symbols::SemiColon, 1, 4, 3),
+            ];
+            let mut prog: Vec<Statement> = Vec::new();
+            let mut parser = Parser::new(tokens.into_iter().map(Ok), &mut prog);
+
+            let res = parse_term(&mut parser);
+
+            assert!(res.is_err_and(|e| matches!(
+                e,
+                TermError::TokenType(TokenTypeError::Expected { .. })
+            )));
+        }
+    }
 }
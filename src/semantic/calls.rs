@@ -0,0 +1,179 @@
+use crate::{
+    error::{SemanticError, SemanticResult},
+    parser::node::{Expression, Statement, Term},
+    semantic::resolve::SymbolTable,
+};
+
+/// Check that every call in the program passes as many arguments as the
+/// function has parameters.
+///
+/// Calls to undefined functions are rejected by [`crate::semantic::resolve`].
+pub fn check(prog: &[Statement], symbols: &SymbolTable) -> SemanticResult {
+    let checker = CallChecker { symbols };
+
+    checker.check_block(prog)
+}
+
+/// A representation of the functions which may be called.
+#[derive(Debug, PartialEq)]
+struct CallChecker<'a> {
+    /// Contains the function every call resolves to.
+    symbols: &'a SymbolTable,
+}
+
+impl CallChecker<'_> {
+    fn check_block(&self, body: &[Statement]) -> SemanticResult {
+        body.iter().try_for_each(|s| self.check_statement(s))
+    }
+
+    fn check_statement(&self, statement: &Statement) -> SemanticResult {
+        match statement {
+            Statement::FunctionDeclare { body, .. } => self.check_block(body),
+            Statement::Return {
+                expression: None, ..
+            } => Ok(()),
+            Statement::Return {
+                expression: Some(expression),
+                ..
+            }
+            | Statement::Declare { expression, .. }
+            | Statement::Assign { expression, .. }
+            | Statement::Expression { expression, .. } => self.check_expression(expression),
+            Statement::If {
+                condition,
+                body,
+                else_body,
+                ..
+            } => {
+                self.check_expression(condition)?;
+                self.check_block(body)?;
+                else_body
+                    .as_deref()
+                    .map_or(Ok(()), |else_body| self.check_block(else_body))
+            }
+            Statement::While {
+                condition, body, ..
+            } => {
+                self.check_expression(condition)?;
+                self.check_block(body)
+            }
+            Statement::For {
+                init,
+                condition,
+                step,
+                body,
+                ..
+            } => {
+                if let Some(init) = init {
+                    self.check_statement(init)?;
+                }
+                if let Some(condition) = condition {
+                    self.check_expression(condition)?;
+                }
+                if let Some(step) = step {
+                    self.check_statement(step)?;
+                }
+                self.check_block(body)
+            }
+            Statement::Break { .. } | Statement::Continue { .. } => Ok(()),
+        }
+    }
+
+    fn check_expression(&self, expression: &Expression) -> SemanticResult {
+        match expression {
+            Expression::Term(term) => self.check_term(term),
+            Expression::Unary { operand, .. } => self.check_expression(operand),
+            Expression::Binary { lhs, rhs, .. } => {
+                self.check_expression(lhs)?;
+                self.check_expression(rhs)
+            }
+        }
+    }
+
+    fn check_term(&self, term: &Term) -> SemanticResult {
+        match term {
+            Term::LiteralInteger { .. }
+            | Term::LiteralString { .. }
+            | Term::LiteralChar { .. }
+            | Term::LiteralBool { .. }
+            | Term::LiteralFloat { .. }
+            | Term::Identifier { .. } => Ok(()),
+            Term::Parenthesised { expression, .. } => self.check_expression(expression),
+            Term::Call {
+                name,
+                args,
+                id,
+                span,
+            } => {
+                let signature = self
+                    .symbols
+                    .lookup(*id)
+                    .and_then(|f| self.symbols.signature(f));
+                if let Some((_, params)) = signature
+                    && params.len() != args.len()
+                {
+                    return Err(SemanticError::ArgumentCount {
+                        name: name.clone(),
+                        expected: params.len(),
+                        got: args.len(),
+                        span: *span,
+                    });
+                }
+                args.iter().try_for_each(|arg| self.check_expression(arg))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixtures::{at, parse},
+        semantic::resolve::resolve,
+    };
+
+    use super::*;
+
+    /// Check the source against the table of its resolved names.
+    fn check(content: &str) -> SemanticResult {
+        let prog = parse(content);
+        super::check(&prog, &resolve(&prog).unwrap())
+    }
+
+    #[test]
+    fn should_accept_call_to_later_function() {
+        let content = "int main() { return add(1, 2); } int add(int a, int b) { return a; }";
+
+        assert_eq!(check(content), Ok(()));
+    }
+
+    #[test]
+    fn should_error_argument_count() {
+        let content = "int one(int a) { return a; } int main() { while (one(1, 2)) {} return 0; }";
+
+        assert_eq!(
+            check(content),
+            Err(SemanticError::ArgumentCount {
+                name: "one".to_string(),
+                expected: 1,
+                got: 2,
+                span: at(50, 9),
+            })
+        );
+    }
+
+    #[test]
+    fn should_check_nested_arguments() {
+        let content = "int one(int a) { return a; } int main() { return one(one()); }";
+
+        assert_eq!(
+            check(content),
+            Err(SemanticError::ArgumentCount {
+                name: "one".to_string(),
+                expected: 1,
+                got: 0,
+                span: at(54, 5),
+            })
+        );
+    }
+}
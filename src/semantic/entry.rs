@@ -0,0 +1,126 @@
+use crate::{
+    error::{EntryError, EntryResult},
+    lexer::token::Types,
+    parser::node::{Parameter, Statement},
+};
+
+/// The name of the function a program starts at unless another is given.
+pub const DEFAULT_ENTRY: &str = "main";
+
+/// Check that the program defines exactly one top level function with the
+/// given name, that it returns an `int` and that it takes no parameters.
+pub fn check(prog: &[Statement], entry: &str) -> EntryResult {
+    let mut found: Option<(&Types, &[Parameter], _)> = None;
+
+    for statement in prog {
+        match statement {
+            Statement::FunctionDeclare {
+                name,
+                return_type,
+                params,
+                span,
+                ..
+            } if name == entry => {
+                if found.is_some() {
+                    return Err(EntryError::Duplicate {
+                        name: name.clone(),
+                        span: *span,
+                    });
+                }
+                found = Some((return_type, params, *span));
+            }
+            _ => {}
+        }
+    }
+
+    match found {
+        None => Err(EntryError::Missing {
+            name: entry.to_string(),
+        }),
+        Some((return_type, _, span)) if *return_type != Types::Int => Err(EntryError::ReturnType {
+            name: entry.to_string(),
+            got: return_type.clone(),
+            span,
+        }),
+        Some((_, params @ [first, ..], _)) => Err(EntryError::Parameters {
+            name: entry.to_string(),
+            count: params.len(),
+            span: first.span.to(&params[params.len() - 1].span),
+        }),
+        Some(_) => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::{at, parse};
+
+    use super::*;
+
+    #[test]
+    fn should_accept_single_int_entry() {
+        let prog = parse("void f() {} int main() { return 0; }");
+
+        assert_eq!(check(&prog, DEFAULT_ENTRY), Ok(()));
+    }
+
+    #[test]
+    fn should_accept_other_entry() {
+        let prog = parse("int test_add() { return 0; }");
+
+        assert_eq!(check(&prog, "test_add"), Ok(()));
+    }
+
+    #[test]
+    fn should_error_missing_entry() {
+        let prog = parse("int f() { return 0; }");
+
+        assert_eq!(
+            check(&prog, DEFAULT_ENTRY),
+            Err(EntryError::Missing {
+                name: "main".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn should_error_duplicate_entry() {
+        let prog = parse("int main() { return 0; }\nint main() { return 1; }");
+
+        assert_eq!(
+            check(&prog, DEFAULT_ENTRY),
+            Err(EntryError::Duplicate {
+                name: "main".to_string(),
+                span: prog[1].span(),
+            })
+        );
+    }
+
+    #[test]
+    fn should_error_entry_with_parameters() {
+        let prog = parse("int main(int a, int b) { return 0; }");
+
+        assert_eq!(
+            check(&prog, DEFAULT_ENTRY),
+            Err(EntryError::Parameters {
+                name: "main".to_string(),
+                count: 2,
+                span: at(10, 12),
+            })
+        );
+    }
+
+    #[test]
+    fn should_error_entry_not_returning_int() {
+        let prog = parse("i64 main() { return 0; }");
+
+        assert_eq!(
+            check(&prog, DEFAULT_ENTRY),
+            Err(EntryError::ReturnType {
+                name: "main".to_string(),
+                got: Types::I64,
+                span: prog[0].span(),
+            })
+        );
+    }
+}
@@ -0,0 +1,420 @@
+use crate::{
+    error::{SemanticError, Warning},
+    lexer::token::Types,
+    parser::node::{Expression, Statement, Term},
+    span::{Position, Span},
+};
+
+/// Check that every function other than a `void` function returns on every
+/// path through its body.
+///
+/// Returns a [`Warning`] for the first statement of every run of statements
+/// which can never be executed.
+pub fn check(prog: &[Statement]) -> Result<Vec<Warning>, SemanticError> {
+    let mut warnings: Vec<Warning> = Vec::new();
+
+    for statement in prog {
+        let Statement::FunctionDeclare {
+            name,
+            return_type,
+            body,
+            span,
+            ..
+        } = statement
+        else {
+            continue;
+        };
+
+        let cfg = Cfg::build(body);
+        let reachable = cfg.reachable();
+        warnings.extend(cfg.unreachable(&reachable));
+        if *return_type != Types::Void && reachable[cfg.exit] {
+            // Point at the closing `}` the body falls off.
+            let close = Position::new(
+                span.end.line,
+                span.end.column.saturating_sub(1),
+                span.end.offset.saturating_sub(1),
+            );
+            return Err(SemanticError::MissingReturn {
+                name: name.clone(),
+                span: Span::on_line(close, 1),
+            });
+        }
+    }
+
+    Ok(warnings)
+}
+
+/// A representation of the control-flow graph of a function body.
+///
+/// Compound statements are held by the block which evaluates their
+/// condition, the blocks of their bodies follow.
+#[derive(Debug, PartialEq)]
+pub struct Cfg<'a> {
+    /// Contains every block in the order they were created, starting with
+    /// the entry.
+    pub blocks: Vec<Block<'a>>,
+    /// Contains the index of the block reached by falling off the end of the
+    /// body.
+    pub exit: usize,
+    /// Contains each list of statements in the body with the block each
+    /// statement starts in, and the block holding the statement the list
+    /// belongs to if it is not the body itself.
+    lists: Vec<(Option<usize>, List<'a>)>,
+}
+
+/// A list of statements with the index of the block each starts in.
+type List<'a> = Vec<(&'a Statement, usize)>;
+
+/// A representation of a straight line run of statements.
+#[derive(Debug, PartialEq, Default)]
+pub struct Block<'a> {
+    /// Contains the statements executed in order.
+    pub statements: Vec<&'a Statement>,
+    /// Contains the index of every block control may continue at once the
+    /// statements are executed.
+    ///
+    /// A block ending with a `return` has no successors.
+    pub successors: Vec<usize>,
+}
+
+impl<'a> Cfg<'a> {
+    /// Build the control-flow graph of the function body.
+    pub fn build(body: &'a [Statement]) -> Self {
+        let mut builder = CfgBuilder {
+            cfg: Cfg {
+                blocks: vec![Block::default()],
+                exit: 0,
+                lists: Vec::new(),
+            },
+            current: 0,
+            loops: Vec::new(),
+        };
+
+        builder.build_list(None, body);
+        let exit = builder.new_block();
+        builder.edge(builder.current, exit);
+        builder.cfg.exit = exit;
+
+        builder.cfg
+    }
+
+    /// Get whether each block can be reached from the entry.
+    pub fn reachable(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.blocks.len()];
+        let mut stack = vec![0];
+        while let Some(block) = stack.pop() {
+            if !reachable[block] {
+                reachable[block] = true;
+                stack.extend(&self.blocks[block].successors);
+            }
+        }
+
+        reachable
+    }
+
+    /// Get a warning for the first statement of each run of unreachable
+    /// statements, skipping those nested in a statement already warned
+    /// about.
+    fn unreachable(&self, reachable: &[bool]) -> Vec<Warning> {
+        let mut warnings: Vec<Warning> = Vec::new();
+
+        for (parent, list) in &self.lists {
+            let mut previous = parent.is_none_or(|parent| reachable[parent]);
+            for (statement, block) in list {
+                if previous && !reachable[*block] {
+                    warnings.push(Warning::Unreachable {
+                        span: statement.span(),
+                    });
+                }
+                previous = reachable[*block];
+            }
+        }
+        // Nested lists are visited before the rest of the list they are in.
+        warnings.sort_by_key(|w| w.span().start.offset);
+
+        warnings
+    }
+}
+
+/// A representation of the state while building a [`Cfg`].
+#[derive(Debug, PartialEq)]
+struct CfgBuilder<'a> {
+    /// Contains the graph built so far.
+    cfg: Cfg<'a>,
+    /// Contains the index of the block statements are added to.
+    current: usize,
+    /// Contains the `continue` and `break` blocks of each enclosing loop,
+    /// innermost last.
+    loops: Vec<(usize, usize)>,
+}
+
+impl<'a> CfgBuilder<'a> {
+    fn new_block(&mut self) -> usize {
+        self.cfg.blocks.push(Block::default());
+        self.cfg.blocks.len() - 1
+    }
+
+    fn edge(&mut self, from: usize, to: usize) {
+        self.cfg.blocks[from].successors.push(to);
+    }
+
+    /// Add the statement to the current block.
+    fn push(&mut self, statement: &'a Statement) {
+        self.cfg.blocks[self.current].statements.push(statement);
+    }
+
+    /// End the current block with a jump to the given block, if any, and
+    /// continue in a new block nothing jumps to.
+    fn jump(&mut self, to: Option<usize>) {
+        if let Some(to) = to {
+            self.edge(self.current, to);
+        }
+        self.current = self.new_block();
+    }
+
+    /// Add the statements of a body belonging to the statement in the
+    /// `parent` block.
+    fn build_list(&mut self, parent: Option<usize>, body: &'a [Statement]) {
+        let list = self.cfg.lists.len();
+        self.cfg.lists.push((parent, Vec::new()));
+        for statement in body {
+            self.cfg.lists[list].1.push((statement, self.current));
+            self.build_statement(statement);
+        }
+    }
+
+    fn build_statement(&mut self, statement: &'a Statement) {
+        match statement {
+            Statement::FunctionDeclare { .. }
+            | Statement::Declare { .. }
+            | Statement::Assign { .. }
+            | Statement::Expression { .. } => self.push(statement),
+            Statement::Return { .. } => {
+                self.push(statement);
+                self.jump(None);
+            }
+            Statement::Break { .. } | Statement::Continue { .. } => {
+                self.push(statement);
+                let target = self.loops.last().map(|(continue_block, break_block)| {
+                    if matches!(statement, Statement::Break { .. }) {
+                        *break_block
+                    } else {
+                        *continue_block
+                    }
+                });
+                self.jump(target);
+            }
+            Statement::If {
+                condition,
+                body,
+                else_body,
+                ..
+            } => {
+                self.push(statement);
+                let header = self.current;
+                let join = self.new_block();
+                let branches = [Some(body), else_body.as_ref()];
+                for (index, branch) in branches.into_iter().enumerate() {
+                    // The else branch is never taken when the condition is
+                    // always true.
+                    if index == 1 && is_always_true(Some(condition)) {
+                        continue;
+                    }
+                    let start = self.new_block();
+                    self.edge(header, start);
+                    self.current = start;
+                    if let Some(branch) = branch {
+                        self.build_list(Some(header), branch);
+                    }
+                    self.edge(self.current, join);
+                }
+                self.current = join;
+            }
+            Statement::While {
+                condition, body, ..
+            } => {
+                let header = self.new_block();
+                self.edge(self.current, header);
+                self.current = header;
+                self.push(statement);
+                self.build_loop(header, Some(condition), body, None);
+            }
+            Statement::For {
+                init,
+                condition,
+                step,
+                body,
+                ..
+            } => {
+                if let Some(init) = init {
+                    self.build_statement(init);
+                }
+                let header = self.new_block();
+                self.edge(self.current, header);
+                self.current = header;
+                self.push(statement);
+                self.build_loop(header, condition.as_ref(), body, step.as_deref());
+            }
+        }
+    }
+
+    /// Add the body of a loop whose condition is evaluated in the `header`
+    /// block, continuing at the block after the loop.
+    fn build_loop(
+        &mut self,
+        header: usize,
+        condition: Option<&Expression>,
+        body: &'a [Statement],
+        step: Option<&'a Statement>,
+    ) {
+        let start = self.new_block();
+        let step_block = self.new_block();
+        let exit = self.new_block();
+        self.edge(header, start);
+        if !is_always_true(condition) {
+            self.edge(header, exit);
+        }
+
+        self.loops.push((step_block, exit));
+        self.current = start;
+        self.build_list(Some(header), body);
+        self.loops.pop();
+
+        self.edge(self.current, step_block);
+        self.current = step_block;
+        if let Some(step) = step {
+            self.build_statement(step);
+        }
+        self.edge(self.current, header);
+        self.current = exit;
+    }
+}
+
+/// `true` if the condition is missing, a non-zero integer literal or `true`,
+/// looking through parentheses, `false` otherwise.
+fn is_always_true(condition: Option<&Expression>) -> bool {
+    match condition {
+        None => true,
+        Some(Expression::Term(Term::LiteralInteger { value, .. })) => *value != 0,
+        Some(Expression::Term(Term::LiteralBool { value, .. })) => *value,
+        Some(Expression::Term(Term::Parenthesised { expression, .. })) => {
+            is_always_true(Some(expression))
+        }
+        Some(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::parse;
+
+    use super::*;
+
+    fn body(prog: &[Statement]) -> &[Statement] {
+        match &prog[0] {
+            Statement::FunctionDeclare { body, .. } => body,
+            _ => panic!("expected a function"),
+        }
+    }
+
+    #[test]
+    fn should_build_if_else() {
+        let prog = parse("int f(int x) { if (x) { x = 1; } else { x = 2; } return x; }");
+        let cfg = Cfg::build(body(&prog));
+
+        // entry, join, then, else, dead after return, exit
+        assert_eq!(cfg.blocks.len(), 6);
+        assert_eq!(cfg.blocks[0].successors, vec![2, 3]);
+        assert_eq!(cfg.blocks[2].successors, vec![1]);
+        assert_eq!(cfg.blocks[3].successors, vec![1]);
+        assert!(cfg.blocks[1].successors.is_empty());
+        assert!(!cfg.reachable()[cfg.exit]);
+    }
+
+    #[test]
+    fn should_accept_return_on_every_branch() {
+        let prog = parse(
+            "int f(int x) { if (x) { return 1; } else if (x > 1) { return 2; } else { return 3; } }",
+        );
+
+        assert_eq!(check(&prog), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn should_accept_infinite_loop() {
+        let prog = parse("int f(int x) { while (1) { if (x) { return x; } } }");
+
+        assert_eq!(check(&prog), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn should_accept_always_true_bool() {
+        let while_true = parse("int f() { while (true) { return 1; } }");
+        let if_true = parse("int main() { if ((true)) { return 2; } }");
+
+        assert_eq!(check(&while_true), Ok(Vec::new()));
+        assert_eq!(check(&if_true), Ok(Vec::new()));
+        assert!(
+            check(&parse("int f() { while (false) { return 1; } }"))
+                .is_err_and(|e| matches!(e, SemanticError::MissingReturn { .. }))
+        );
+    }
+
+    #[test]
+    fn should_error_missing_return() {
+        let prog = parse("int main() {\n}");
+
+        assert_eq!(
+            check(&prog),
+            Err(SemanticError::MissingReturn {
+                name: "main".to_string(),
+                span: Span::on_line(Position::new(2, 1, 13), 1),
+            })
+        );
+    }
+
+    #[test]
+    fn should_error_missing_return_on_one_path() {
+        let if_without_else = parse("int f(int x) { if (x) { return 1; } }");
+        let loop_with_break = parse("int f() { for (;;) { break; } }");
+
+        assert!(
+            check(&if_without_else)
+                .is_err_and(|e| matches!(e, SemanticError::MissingReturn { .. }))
+        );
+        assert!(
+            check(&loop_with_break)
+                .is_err_and(|e| matches!(e, SemanticError::MissingReturn { .. }))
+        );
+    }
+
+    #[test]
+    fn should_accept_void_falling_off_the_end() {
+        let prog = parse("void f(int x) { if (x) { return; } }");
+
+        assert_eq!(check(&prog), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn should_warn_unreachable_once_per_run() {
+        let prog = parse(
+            "int f(int x) {\n    return 1;\n    x = 2;\n    if (x) { x = 3; }\n}\nvoid g() { while (1) { continue; g(); } g(); }",
+        );
+
+        assert_eq!(
+            check(&prog),
+            Ok(vec![
+                Warning::Unreachable {
+                    span: Span::on_line(Position::new(3, 5, 33), 6),
+                },
+                Warning::Unreachable {
+                    span: Span::on_line(Position::new(6, 34, 97), 4),
+                },
+                Warning::Unreachable {
+                    span: Span::on_line(Position::new(6, 41, 104), 4),
+                },
+            ])
+        );
+    }
+}
@@ -0,0 +1,5 @@
+pub mod calls;
+pub mod entry;
+pub mod flow;
+pub mod resolve;
+pub mod types;
@@ -0,0 +1,551 @@
+use std::collections::HashMap;
+
+use crate::{
+    error::SemanticError,
+    lexer::token::Types,
+    parser::node::{Expression, NodeId, Statement, Term},
+    span::Span,
+};
+
+/// Resolve every name in the program to the definition it refers to.
+///
+/// Functions may be called before they are declared, variables are resolved
+/// to the innermost declaration in scope following the same rules as the
+/// parser.
+pub fn resolve(prog: &[Statement]) -> Result<SymbolTable, SemanticError> {
+    let mut resolver = Resolver {
+        table: SymbolTable::with_functions(prog)?,
+        scopes: Vec::new(),
+    };
+    resolver.resolve_block(prog)?;
+
+    Ok(resolver.table)
+}
+
+/// A unique identifier of a [`Definition`] in a [`SymbolTable`].
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub struct DefId(pub usize);
+
+/// A representation of a function, parameter or variable definition.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Definition {
+    /// Contains the name being defined.
+    pub name: String,
+    /// Contains what the name is defined as.
+    pub kind: DefKind,
+    /// Contains the location of the definition.
+    pub span: Span,
+}
+
+/// The kinds of [`Definition`].
+#[derive(Debug, PartialEq, Clone)]
+pub enum DefKind {
+    /// Contains the signature of a function.
+    Function {
+        return_type: Types,
+        params: Vec<Types>,
+    },
+    /// Contains the type of a function parameter.
+    Parameter { param_type: Types },
+    /// Contains the type of a local variable.
+    Variable { var_type: Types },
+}
+
+impl DefKind {
+    /// Get the type of the value of the definition, which is the return type
+    /// of a function.
+    pub fn value_type(&self) -> &Types {
+        match self {
+            DefKind::Function { return_type, .. } => return_type,
+            DefKind::Parameter { param_type } => param_type,
+            DefKind::Variable { var_type } => var_type,
+        }
+    }
+}
+
+impl Definition {
+    /// Create the [`Definition`] of a [`Statement::FunctionDeclare`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the statement is not a function declaration.
+    fn function(statement: &Statement) -> Self {
+        let Statement::FunctionDeclare {
+            name,
+            return_type,
+            params,
+            span,
+            ..
+        } = statement
+        else {
+            unreachable!("only function declarations define functions");
+        };
+
+        Self {
+            name: name.clone(),
+            kind: DefKind::Function {
+                return_type: return_type.clone(),
+                params: params.iter().map(|p| p.param_type.clone()).collect(),
+            },
+            span: *span,
+        }
+    }
+}
+
+/// A representation of every definition in a program and the names which
+/// refer to them.
+#[derive(Debug, PartialEq, Default)]
+pub struct SymbolTable {
+    /// Contains every definition indexed by its [`DefId`].
+    pub definitions: Vec<Definition>,
+    /// Contains the definition referred to by every node naming one,
+    /// including the definitions themselves.
+    pub names: HashMap<NodeId, DefId>,
+    /// Contains the definition of every top level function.
+    functions: HashMap<String, DefId>,
+}
+
+impl SymbolTable {
+    /// Create a [`SymbolTable`] holding only the top level functions of the
+    /// program, without resolving any names.
+    pub fn with_functions(prog: &[Statement]) -> Result<Self, SemanticError> {
+        let mut table = Self::default();
+        for statement in prog {
+            if let Statement::FunctionDeclare { name, id, span, .. } = statement {
+                if table.function(name).is_some() {
+                    return Err(SemanticError::DuplicateFunction {
+                        name: name.clone(),
+                        span: *span,
+                    });
+                }
+                let def = table.define(Definition::function(statement), *id);
+                table.functions.insert(name.clone(), def);
+            }
+        }
+
+        Ok(table)
+    }
+
+    /// Get the definition with the given id.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the id was not created by this table.
+    pub fn get(&self, id: DefId) -> &Definition {
+        &self.definitions[id.0]
+    }
+
+    /// Get the id of the definition the node names.
+    pub fn lookup(&self, id: NodeId) -> Option<DefId> {
+        self.names.get(&id).copied()
+    }
+
+    /// Get the id of the top level function with the given name.
+    pub fn function(&self, name: &str) -> Option<DefId> {
+        self.functions.get(name).copied()
+    }
+
+    /// Get the return type and parameter types of the function with the
+    /// given id.
+    pub fn signature(&self, id: DefId) -> Option<(&Types, &[Types])> {
+        match &self.get(id).kind {
+            DefKind::Function {
+                return_type,
+                params,
+            } => Some((return_type, params)),
+            _ => None,
+        }
+    }
+
+    /// Add the definition made by the node to the table, returning its id.
+    fn define(&mut self, definition: Definition, node: NodeId) -> DefId {
+        let id = DefId(self.definitions.len());
+        self.names.insert(node, id);
+        self.definitions.push(definition);
+        id
+    }
+}
+
+/// A representation of the state while resolving the names of a program.
+#[derive(Debug, PartialEq)]
+struct Resolver<'a> {
+    /// Contains the table built so far.
+    table: SymbolTable,
+    /// Contains the variables declared in each enclosing scope, innermost
+    /// last.
+    scopes: Vec<Vec<(&'a str, DefId)>>,
+}
+
+impl<'a> Resolver<'a> {
+    /// Resolve the statements in a new scope.
+    fn resolve_block(&mut self, body: &'a [Statement]) -> Result<(), SemanticError> {
+        self.scopes.push(Vec::new());
+        let res = body.iter().try_for_each(|s| self.resolve_statement(s));
+        self.scopes.pop();
+
+        res
+    }
+
+    /// Define a variable in the innermost scope, which may shadow but not
+    /// redeclare another.
+    fn declare(
+        &mut self,
+        name: &'a str,
+        kind: DefKind,
+        node: NodeId,
+        span: Span,
+    ) -> Result<(), SemanticError> {
+        if self
+            .scopes
+            .last()
+            .is_some_and(|scope| scope.iter().any(|(n, _)| *n == name))
+        {
+            return Err(SemanticError::Redeclared {
+                name: name.to_string(),
+                span,
+            });
+        }
+        let id = self.table.define(
+            Definition {
+                name: name.to_string(),
+                kind,
+                span,
+            },
+            node,
+        );
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.push((name, id));
+        }
+
+        Ok(())
+    }
+
+    /// Record the innermost variable with the name as referred to by the
+    /// node at the location.
+    fn refer(&mut self, name: &str, node: NodeId, span: Span) -> Result<(), SemanticError> {
+        let id = self
+            .scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter().rev())
+            .find_map(|(n, id)| (*n == name).then_some(*id))
+            .ok_or(SemanticError::UndeclaredName {
+                name: name.to_string(),
+                span,
+            })?;
+        self.table.names.insert(node, id);
+
+        Ok(())
+    }
+
+    fn resolve_statement(&mut self, statement: &'a Statement) -> Result<(), SemanticError> {
+        match statement {
+            Statement::FunctionDeclare {
+                params, body, id, ..
+            } => {
+                // Nested functions are rejected when lowering, but their
+                // bodies are still resolved.
+                if self.table.lookup(*id).is_none() {
+                    self.table.define(Definition::function(statement), *id);
+                }
+                // The parameters are scoped outside the body so the body may
+                // shadow them.
+                self.scopes.push(Vec::new());
+                let res = params
+                    .iter()
+                    .try_for_each(|param| {
+                        let kind = DefKind::Parameter {
+                            param_type: param.param_type.clone(),
+                        };
+                        self.declare(&param.name, kind, param.id, param.span)
+                    })
+                    .and_then(|()| self.resolve_block(body));
+                self.scopes.pop();
+
+                res
+            }
+            Statement::Return {
+                expression: None, ..
+            }
+            | Statement::Break { .. }
+            | Statement::Continue { .. } => Ok(()),
+            Statement::Return {
+                expression: Some(expression),
+                ..
+            }
+            | Statement::Expression { expression, .. } => self.resolve_expression(expression),
+            Statement::Declare {
+                name,
+                var_type,
+                expression,
+                id,
+                span,
+            } => {
+                // The name is not in scope in its own initialiser.
+                self.resolve_expression(expression)?;
+                let kind = DefKind::Variable {
+                    var_type: var_type.clone(),
+                };
+                self.declare(name, kind, *id, *span)
+            }
+            Statement::Assign {
+                name,
+                expression,
+                id,
+                span,
+            } => {
+                self.resolve_expression(expression)?;
+                self.refer(name, *id, *span)
+            }
+            Statement::If {
+                condition,
+                body,
+                else_body,
+                ..
+            } => {
+                self.resolve_expression(condition)?;
+                self.resolve_block(body)?;
+                else_body
+                    .as_deref()
+                    .map_or(Ok(()), |else_body| self.resolve_block(else_body))
+            }
+            Statement::While {
+                condition, body, ..
+            } => {
+                self.resolve_expression(condition)?;
+                self.resolve_block(body)
+            }
+            Statement::For {
+                init,
+                condition,
+                step,
+                body,
+                ..
+            } => {
+                // The initialiser is scoped to the loop.
+                self.scopes.push(Vec::new());
+                let res =
+                    self.resolve_for(init.as_deref(), condition.as_ref(), step.as_deref(), body);
+                self.scopes.pop();
+
+                res
+            }
+        }
+    }
+
+    fn resolve_for(
+        &mut self,
+        init: Option<&'a Statement>,
+        condition: Option<&Expression>,
+        step: Option<&'a Statement>,
+        body: &'a [Statement],
+    ) -> Result<(), SemanticError> {
+        if let Some(init) = init {
+            self.resolve_statement(init)?;
+        }
+        if let Some(condition) = condition {
+            self.resolve_expression(condition)?;
+        }
+        if let Some(step) = step {
+            self.resolve_statement(step)?;
+        }
+        self.resolve_block(body)
+    }
+
+    fn resolve_expression(&mut self, expression: &Expression) -> Result<(), SemanticError> {
+        match expression {
+            Expression::Term(term) => self.resolve_term(term),
+            Expression::Unary { operand, .. } => self.resolve_expression(operand),
+            Expression::Binary { lhs, rhs, .. } => {
+                self.resolve_expression(lhs)?;
+                self.resolve_expression(rhs)
+            }
+        }
+    }
+
+    fn resolve_term(&mut self, term: &Term) -> Result<(), SemanticError> {
+        match term {
+            Term::LiteralInteger { .. }
+            | Term::LiteralString { .. }
+            | Term::LiteralChar { .. }
+            | Term::LiteralBool { .. }
+            | Term::LiteralFloat { .. } => Ok(()),
+            Term::Identifier { name, id, span } => self.refer(name, *id, *span),
+            Term::Parenthesised { expression, .. } => self.resolve_expression(expression),
+            Term::Call {
+                name,
+                args,
+                id,
+                span,
+            } => {
+                let def = self
+                    .table
+                    .function(name)
+                    .ok_or(SemanticError::UndefinedFunction {
+                        name: name.clone(),
+                        span: *span,
+                    })?;
+                self.table.names.insert(*id, def);
+                args.iter().try_for_each(|arg| self.resolve_expression(arg))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::{at, id_at, parse};
+
+    use super::*;
+
+    #[test]
+    fn should_resolve_call_to_later_function() {
+        let prog = parse("int main() { return f(1); } int f(int x) { return x; }");
+
+        let table = resolve(&prog).unwrap();
+        let f = table.function("f").unwrap();
+
+        assert_eq!(table.lookup(id_at(&prog, at(21, 4))), Some(f));
+        assert_eq!(table.signature(f), Some((&Types::Int, &[Types::Int][..])));
+        assert_eq!(table.get(f).span, prog[1].span());
+    }
+
+    #[test]
+    fn should_resolve_shadowed_names_to_distinct_definitions() {
+        let prog = parse("int f(int x) { int y = x; if (y) { int x = y; x = 1; } return x; }");
+
+        let table = resolve(&prog).unwrap();
+        let param = table.lookup(id_at(&prog, at(7, 5))).unwrap();
+        let inner = table.lookup(id_at(&prog, at(36, 10))).unwrap();
+
+        assert_eq!(
+            table.get(param).kind,
+            DefKind::Parameter {
+                param_type: Types::Int
+            }
+        );
+        assert_ne!(param, inner);
+        // int y = x;
+        assert_eq!(table.lookup(id_at(&prog, at(24, 1))), Some(param));
+        // x = 1;
+        assert_eq!(table.lookup(id_at(&prog, at(47, 6))), Some(inner));
+        // return x;
+        assert_eq!(table.lookup(id_at(&prog, at(63, 1))), Some(param));
+    }
+
+    #[test]
+    fn should_scope_for_initialiser_to_loop() {
+        let prog = parse("int f() { for (int i = 0; i < 2; i = i + 1) {} return 0; }");
+
+        let table = resolve(&prog).unwrap();
+        let i = table.lookup(id_at(&prog, at(16, 10))).unwrap();
+
+        assert_eq!(table.get(i).name, "i");
+        assert_eq!(table.lookup(id_at(&prog, at(27, 1))), Some(i));
+        assert_eq!(table.lookup(id_at(&prog, at(34, 9))), Some(i));
+    }
+
+    #[test]
+    fn should_error_duplicate_function() {
+        let prog = parse("int f() { return 1; }\nint f() { return 2; }");
+
+        assert_eq!(
+            resolve(&prog),
+            Err(SemanticError::DuplicateFunction {
+                name: "f".to_string(),
+                span: prog[1].span(),
+            })
+        );
+    }
+
+    #[test]
+    fn should_error_undefined_function() {
+        let prog = parse("int main() { return g(); }");
+
+        assert_eq!(
+            resolve(&prog),
+            Err(SemanticError::UndefinedFunction {
+                name: "g".to_string(),
+                span: at(21, 3),
+            })
+        );
+    }
+
+    #[test]
+    fn should_error_undeclared_name() {
+        let prog = parse("int f() { return x; }");
+
+        assert_eq!(
+            resolve(&prog),
+            Err(SemanticError::UndeclaredName {
+                name: "x".to_string(),
+                span: at(18, 1),
+            })
+        );
+    }
+
+    #[test]
+    fn should_error_use_in_own_initialiser() {
+        let prog = parse("int f() { int x = x + 1; return x; }");
+
+        assert_eq!(
+            resolve(&prog),
+            Err(SemanticError::UndeclaredName {
+                name: "x".to_string(),
+                span: at(19, 1),
+            })
+        );
+    }
+
+    #[test]
+    fn should_error_use_after_block() {
+        let prog = parse("int f() { if (1) { int x = 1; } return x; }");
+
+        assert_eq!(
+            resolve(&prog),
+            Err(SemanticError::UndeclaredName {
+                name: "x".to_string(),
+                span: at(40, 1),
+            })
+        );
+    }
+
+    #[test]
+    fn should_error_for_initialiser_after_loop() {
+        let prog = parse("int f() { for (int i = 0;;) {} i = 1; return 0; }");
+
+        assert_eq!(
+            resolve(&prog),
+            Err(SemanticError::UndeclaredName {
+                name: "i".to_string(),
+                span: at(32, 6),
+            })
+        );
+    }
+
+    #[test]
+    fn should_error_redeclared_variable() {
+        let prog = parse("int f() { int x = 1; int x = 2; return x; }");
+
+        assert_eq!(
+            resolve(&prog),
+            Err(SemanticError::Redeclared {
+                name: "x".to_string(),
+                span: at(22, 10),
+            })
+        );
+    }
+
+    #[test]
+    fn should_error_duplicate_param() {
+        let prog = parse("int f(int a, int a) { return a; }");
+
+        assert_eq!(
+            resolve(&prog),
+            Err(SemanticError::Redeclared {
+                name: "a".to_string(),
+                span: at(14, 5),
+            })
+        );
+    }
+}
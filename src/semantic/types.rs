@@ -0,0 +1,626 @@
+use std::collections::HashMap;
+
+use crate::{
+    error::{TypeError, TypeResult},
+    lexer::token::Types,
+    parser::node::{BinaryOp, Expression, NodeId, Statement, Term, UnaryOp},
+    semantic::resolve::SymbolTable,
+    span::Span,
+};
+
+/// Check that every value in the program has a type which converts to the
+/// type expected where it is used, returning the type of every expression.
+///
+/// Integers convert only to integers able to hold every value of theirs and
+/// to floating-point types, `float` converts to `double`, and `bool` and
+/// `char` convert to integers wide enough for them. Floating-point values
+/// also convert to any integer, truncating towards zero. Nothing converts to
+/// a `bool`, which only comes from comparisons and logical operations, and
+/// strings convert to nothing, only being compared for equality. Integer and
+/// floating-point literals take the numeric type expected of them.
+pub fn check(prog: &[Statement], symbols: &SymbolTable) -> Result<TypeTable, TypeError> {
+    let mut checker = TypeChecker {
+        symbols,
+        return_type: Types::Void,
+        types: TypeTable::default(),
+    };
+    checker.check_block(prog)?;
+
+    Ok(checker.types)
+}
+
+/// A representation of the type of every expression in a program.
+#[derive(Debug, PartialEq, Default)]
+pub struct TypeTable {
+    /// Contains the type of the value of every expression.
+    types: HashMap<NodeId, Types>,
+}
+
+impl TypeTable {
+    /// Get the type of the value of the expression.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the expression was not checked.
+    pub fn of(&self, expression: &Expression) -> &Types {
+        &self.types[&expression.id()]
+    }
+}
+
+/// The kinds of literal whose type depends on where they are used.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Literal {
+    /// Contains the magnitude of the integer and whether it is `negated`.
+    Integer {
+        value: u64,
+        negated: bool,
+    },
+    Float,
+}
+
+impl Literal {
+    /// `true` if the kind of literal converts to the type as long as it is in
+    /// range, `false` otherwise.
+    fn converts_to(&self, to: &Types) -> bool {
+        match self {
+            Literal::Integer { .. } => to.is_integer() || to.is_float() || *to == Types::Char,
+            Literal::Float => to.is_float(),
+        }
+    }
+
+    /// `true` if the literal is within the range of the type, `false`
+    /// otherwise. Floating-point literals fit in every type they convert to.
+    fn fits(&self, to: &Types) -> bool {
+        match self {
+            Literal::Integer {
+                value,
+                negated: true,
+            } => *value <= to.min_value().unsigned_abs(),
+            Literal::Integer { value, .. } => *value <= to.max_value(),
+            Literal::Float => true,
+        }
+    }
+}
+
+/// Get the kind of literal the expression is, looking through parentheses
+/// and negation.
+fn literal(expression: &Expression) -> Option<Literal> {
+    match expression {
+        Expression::Term(Term::LiteralInteger { value, .. }) => Some(Literal::Integer {
+            value: *value,
+            negated: false,
+        }),
+        Expression::Term(Term::LiteralFloat { .. }) => Some(Literal::Float),
+        Expression::Term(Term::Parenthesised { expression, .. }) => literal(expression),
+        Expression::Unary {
+            op: UnaryOp::Negate,
+            operand,
+            ..
+        } => match literal(operand)? {
+            Literal::Integer { value, negated } => Some(Literal::Integer {
+                value,
+                negated: !negated,
+            }),
+            Literal::Float => Some(Literal::Float),
+        },
+        _ => None,
+    }
+}
+
+/// `true` if a value of type `got`, which is the given kind of literal if
+/// any, converts implicitly to the type `expected`, `false` otherwise.
+///
+/// Unlike [`widens`] a floating-point value may be truncated to an integer.
+fn assignable(literal: Option<Literal>, got: &Types, expected: &Types) -> bool {
+    widens(literal, got, expected) || (got.is_float() && expected.is_integer())
+}
+
+/// `true` if a value of type `got`, which is the given kind of literal if
+/// any, converts to the type `expected` without losing its value, `false`
+/// otherwise.
+fn widens(literal: Option<Literal>, got: &Types, expected: &Types) -> bool {
+    use Types::*;
+
+    match (literal, got) {
+        _ if got == expected => true,
+        (Some(literal), _) => literal.converts_to(expected) && literal.fits(expected),
+        (None, Float) => *expected == Double,
+        (None, Bool) => expected.is_integer(),
+        (None, Char) => expected.is_integer() && expected.size() >= Char.size(),
+        (None, got) if got.is_integer() && expected.is_float() => true,
+        (None, got) if got.is_integer() && expected.is_integer() => {
+            // Unsigned integers fit in wider signed integers but signed
+            // integers never fit in unsigned ones.
+            match (got.is_signed(), expected.is_signed()) {
+                (false, true) => got.size() < expected.size(),
+                (signed, expected_signed) => {
+                    signed == expected_signed && got.size() <= expected.size()
+                }
+            }
+        }
+        _ => false,
+    }
+}
+
+/// `true` if a value of the type can be tested for being non-zero, `false`
+/// otherwise.
+fn testable(value_type: &Types) -> bool {
+    !matches!(value_type, Types::Void | Types::Str)
+}
+
+/// A representation of the state while checking the types of a program.
+#[derive(Debug, PartialEq)]
+struct TypeChecker<'a> {
+    /// Contains the definition of every name, holding its type.
+    symbols: &'a SymbolTable,
+    /// Contains the return type of the enclosing function.
+    return_type: Types,
+    /// Contains the type of every expression checked so far.
+    types: TypeTable,
+}
+
+impl<'a> TypeChecker<'a> {
+    fn check_block(&mut self, body: &'a [Statement]) -> TypeResult {
+        body.iter().try_for_each(|s| self.check_statement(s))
+    }
+
+    fn check_statement(&mut self, statement: &'a Statement) -> TypeResult {
+        match statement {
+            Statement::FunctionDeclare {
+                return_type, body, ..
+            } => {
+                let return_type = std::mem::replace(&mut self.return_type, return_type.clone());
+                let res = self.check_block(body);
+                self.return_type = return_type;
+
+                res
+            }
+            Statement::Return {
+                expression: None,
+                span,
+            } if self.return_type != Types::Void => Err(TypeError::Mismatch {
+                expected: self.return_type.clone(),
+                got: Types::Void,
+                span: *span,
+            }),
+            Statement::Return {
+                expression: Some(expression),
+                ..
+            } => self.expect(expression, &self.return_type.clone()),
+            Statement::Declare {
+                var_type,
+                expression,
+                ..
+            } => self.expect(expression, var_type),
+            Statement::Assign { expression, id, .. } => match self.variable(*id) {
+                Some(var_type) => self.expect(expression, &var_type),
+                None => self.type_of(expression).map(|_| ()),
+            },
+            Statement::If {
+                condition,
+                body,
+                else_body,
+                ..
+            } => {
+                self.check_condition(condition)?;
+                self.check_block(body)?;
+                else_body
+                    .as_deref()
+                    .map_or(Ok(()), |else_body| self.check_block(else_body))
+            }
+            Statement::While {
+                condition, body, ..
+            } => {
+                self.check_condition(condition)?;
+                self.check_block(body)
+            }
+            Statement::For {
+                init,
+                condition,
+                step,
+                body,
+                ..
+            } => {
+                if let Some(init) = init {
+                    self.check_statement(init)?;
+                }
+                if let Some(condition) = condition {
+                    self.check_condition(condition)?;
+                }
+                if let Some(step) = step {
+                    self.check_statement(step)?;
+                }
+                self.check_block(body)
+            }
+            Statement::Expression { expression, .. } => self.type_of(expression).map(|_| ()),
+            Statement::Return {
+                expression: None, ..
+            }
+            | Statement::Break { .. }
+            | Statement::Continue { .. } => Ok(()),
+        }
+    }
+
+    /// Check the value of the expression converts to the expected type.
+    fn expect(&mut self, expression: &Expression, expected: &Types) -> TypeResult {
+        let got = self.type_of(expression)?;
+        let literal = literal(expression);
+        if !assignable(literal, &got, expected) {
+            // A literal of a type it converts to is only out of range.
+            if let Some(literal @ Literal::Integer { value, negated }) = literal
+                && literal.converts_to(expected)
+            {
+                return Err(TypeError::LiteralOutOfRange {
+                    value: if negated {
+                        format!("-{value}")
+                    } else {
+                        value.to_string()
+                    },
+                    target: expected.clone(),
+                    span: expression.span(),
+                });
+            }
+            return Err(TypeError::Mismatch {
+                expected: expected.clone(),
+                got,
+                span: expression.span(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Check the expression can be tested for being non-zero.
+    fn check_condition(&mut self, condition: &Expression) -> TypeResult {
+        let got = self.type_of(condition)?;
+        if !testable(&got) {
+            return Err(TypeError::Mismatch {
+                expected: Types::Bool,
+                got,
+                span: condition.span(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Get the type of the value of the expression, checking and recording
+    /// the type of every subexpression on the way.
+    fn type_of(&mut self, expression: &Expression) -> Result<Types, TypeError> {
+        let expression_type = match expression {
+            Expression::Term(term) => self.type_of_term(term)?,
+            Expression::Unary { op, operand, .. } => {
+                let operand_type = self.type_of(operand)?;
+                match op {
+                    UnaryOp::Negate => {
+                        numeric(op, &operand_type, operand.span())?;
+                        operand_type
+                    }
+                    UnaryOp::Not => {
+                        operator(op, &operand_type, operand.span(), testable)?;
+                        Types::Bool
+                    }
+                }
+            }
+            Expression::Binary { op, lhs, rhs, .. } => {
+                let lhs_type = self.type_of(lhs)?;
+                let rhs_type = self.type_of(rhs)?;
+                let operands = [(&lhs_type, lhs), (&rhs_type, rhs)];
+                match op {
+                    BinaryOp::And | BinaryOp::Or => {
+                        for (operand_type, operand) in operands {
+                            operator(op, operand_type, operand.span(), testable)?;
+                        }
+                        Types::Bool
+                    }
+                    BinaryOp::Add
+                    | BinaryOp::Subtract
+                    | BinaryOp::Multiply
+                    | BinaryOp::Divide
+                    | BinaryOp::Modulo => {
+                        for (operand_type, operand) in operands {
+                            numeric(op, operand_type, operand.span())?;
+                            if *op == BinaryOp::Modulo {
+                                operator(op, operand_type, operand.span(), Types::is_integer)?;
+                            }
+                        }
+                        common(lhs, &lhs_type, rhs, &rhs_type)?
+                    }
+                    _ => {
+                        // Strings are only compared by their address.
+                        let accepted = match op {
+                            BinaryOp::Equal | BinaryOp::NotEqual => |t: &Types| *t != Types::Void,
+                            _ => testable,
+                        };
+                        for (operand_type, operand) in operands {
+                            operator(op, operand_type, operand.span(), accepted)?;
+                        }
+                        common(lhs, &lhs_type, rhs, &rhs_type)?;
+                        Types::Bool
+                    }
+                }
+            }
+        };
+        self.types
+            .types
+            .insert(expression.id(), expression_type.clone());
+
+        Ok(expression_type)
+    }
+
+    fn type_of_term(&mut self, term: &Term) -> Result<Types, TypeError> {
+        match term {
+            // An integer literal is an `int` unless it is too large for one.
+            Term::LiteralInteger { value, .. } => Ok([Types::Int, Types::I64]
+                .into_iter()
+                .find(|t| *value <= t.max_value())
+                .unwrap_or(Types::U64)),
+            Term::LiteralFloat { .. } => Ok(Types::Double),
+            Term::LiteralChar { .. } => Ok(Types::Char),
+            Term::LiteralBool { .. } => Ok(Types::Bool),
+            Term::LiteralString { .. } => Ok(Types::Str),
+            // Undeclared names are rejected by [`crate::semantic::resolve`].
+            Term::Identifier { id, .. } => Ok(self.variable(*id).unwrap_or(Types::Int)),
+            Term::Parenthesised { expression, .. } => self.type_of(expression),
+            Term::Call { args, id, .. } => {
+                let signature = self
+                    .symbols
+                    .lookup(*id)
+                    .and_then(|f| self.symbols.signature(f));
+                for (index, arg) in args.iter().enumerate() {
+                    match signature.and_then(|(_, params)| params.get(index)) {
+                        Some(param) => self.expect(arg, param)?,
+                        None => self.type_of(arg).map(|_| ())?,
+                    }
+                }
+
+                // Calls to undeclared functions are rejected by
+                // [`crate::semantic::resolve`].
+                Ok(signature.map_or(Types::Int, |(return_type, _)| return_type.clone()))
+            }
+        }
+    }
+
+    /// Find the type of the variable the node names.
+    fn variable(&self, id: NodeId) -> Option<Types> {
+        let definition = self.symbols.get(self.symbols.lookup(id)?);
+
+        Some(definition.kind.value_type().clone())
+    }
+}
+
+/// Get the type both operands of a binary operator convert to.
+///
+/// A literal takes the type of the other operand, otherwise the narrower
+/// operand is widened to the type of the other.
+fn common(
+    lhs: &Expression,
+    lhs_type: &Types,
+    rhs: &Expression,
+    rhs_type: &Types,
+) -> Result<Types, TypeError> {
+    let (lhs_literal, rhs_literal) = (literal(lhs), literal(rhs));
+    let to_rhs = widens(lhs_literal, lhs_type, rhs_type);
+    let to_lhs = widens(rhs_literal, rhs_type, lhs_type);
+
+    match (lhs_literal, rhs_literal) {
+        (Some(_), None) if to_rhs => Ok(rhs_type.clone()),
+        (None, Some(_)) if to_lhs => Ok(lhs_type.clone()),
+        _ if to_lhs => Ok(lhs_type.clone()),
+        _ if to_rhs => Ok(rhs_type.clone()),
+        _ => Err(TypeError::Mismatch {
+            expected: lhs_type.clone(),
+            got: rhs_type.clone(),
+            span: rhs.span(),
+        }),
+    }
+}
+
+/// Check the arithmetic operator can be applied to an operand of the type.
+fn numeric(op: &impl ToString, operand: &Types, span: Span) -> TypeResult {
+    operator(op, operand, span, |t| t.is_integer() || t.is_float())
+}
+
+/// Check the operator can be applied to an operand of the type, which it
+/// can if the type is accepted.
+fn operator(
+    op: &impl ToString,
+    operand: &Types,
+    span: Span,
+    accepted: impl Fn(&Types) -> bool,
+) -> TypeResult {
+    if !accepted(operand) {
+        return Err(TypeError::InvalidOperand {
+            op: op.to_string(),
+            operand: operand.clone(),
+            span,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixtures::{at, id_at, parse},
+        semantic::resolve::resolve,
+    };
+
+    use super::*;
+
+    /// Check the program in the source after resolving its names.
+    fn check(content: &str) -> Result<TypeTable, TypeError> {
+        let prog = parse(content);
+
+        super::check(&prog, &resolve(&prog).unwrap())
+    }
+
+    /// Check the program in the source expecting it to be rejected.
+    fn error(content: &str) -> TypeError {
+        check(content).unwrap_err()
+    }
+
+    fn mismatch(expected: Types, got: Types, span: Span) -> TypeError {
+        TypeError::Mismatch {
+            expected,
+            got,
+            span,
+        }
+    }
+
+    #[test]
+    fn should_accept_implicit_conversions() {
+        let content = "double half(float x) { return x / 2; } \
+            i64 main() { u8 small = 200; u16 big = small; i32 n = big; i64 wide = n + 1; \
+            bool b = wide > 0; int flag = b; int c = 'c'; double d = half(1.5) + c; \
+            i64 large = 5000000000; i8 low = -128; u64 top = 18446744073709551615; \
+            bool t = true; int truncated = d; u8 byte = 2.5; return wide + c; }";
+
+        assert!(check(content).is_ok());
+    }
+
+    #[test]
+    fn should_record_expression_types() {
+        let content = "void f(i64 a, float x) { i64 s = a + a; float y = x * 2.5; int z = 1 + 2; bool w = 3 < a; }";
+        let prog = parse(content);
+
+        let types = super::check(&prog, &resolve(&prog).unwrap()).unwrap();
+
+        let type_at = |column, length| &types.types[&id_at(&prog, at(column, length))];
+        assert_eq!(type_at(34, 5), &Types::I64);
+        assert_eq!(type_at(51, 7), &Types::Float);
+        assert_eq!(type_at(68, 5), &Types::Int);
+        assert_eq!(type_at(84, 5), &Types::Bool);
+    }
+
+    #[test]
+    fn should_error_narrowing_conversions() {
+        assert_eq!(
+            error("void f(int i) { bool b = i + 2.5; }"),
+            mismatch(Types::Bool, Types::Double, at(26, 7))
+        );
+        assert_eq!(
+            error("void f() { bool b = 3.14; }"),
+            mismatch(Types::Bool, Types::Double, at(21, 4))
+        );
+        assert_eq!(
+            error("void f() { bool b = 1; }"),
+            mismatch(Types::Bool, Types::Int, at(21, 1))
+        );
+        assert_eq!(
+            error("void f(i64 a) { int b = a; }"),
+            mismatch(Types::Int, Types::I64, at(25, 1))
+        );
+        assert_eq!(
+            error("void f(int a) { u64 b = a; }"),
+            mismatch(Types::U64, Types::Int, at(25, 1))
+        );
+        assert_eq!(
+            error("void f(u32 a, i32 b) { bool c = a < b; }"),
+            mismatch(Types::U32, Types::I32, at(37, 1))
+        );
+    }
+
+    #[test]
+    fn should_error_string_as_integer() {
+        assert_eq!(
+            error(r#"int main() { return "hi"; }"#),
+            mismatch(Types::Int, Types::Str, at(21, 4))
+        );
+        assert_eq!(
+            error(r#"void f() { int x = "a" + 1; }"#),
+            TypeError::InvalidOperand {
+                op: "+".to_string(),
+                operand: Types::Str,
+                span: at(20, 3),
+            }
+        );
+    }
+
+    #[test]
+    fn should_error_literal_out_of_range() {
+        let out_of_range = |value: &str, target, span| TypeError::LiteralOutOfRange {
+            value: value.to_string(),
+            target,
+            span,
+        };
+
+        assert_eq!(
+            error("void f() { u8 x = 256; }"),
+            out_of_range("256", Types::U8, at(19, 3))
+        );
+        assert_eq!(
+            error("void f() { u8 x = -1; }"),
+            out_of_range("-1", Types::U8, at(19, 2))
+        );
+        assert_eq!(
+            error("void f() { int x = 5000000000; }"),
+            out_of_range("5000000000", Types::Int, at(20, 10))
+        );
+        assert_eq!(
+            error("void f(i8 a) { i8 b = a + 128; }"),
+            mismatch(Types::I8, Types::Int, at(23, 7))
+        );
+    }
+
+    #[test]
+    fn should_error_return_mismatch() {
+        assert_eq!(
+            error("void f() { return 1; }"),
+            mismatch(Types::Void, Types::Int, at(19, 1))
+        );
+        assert_eq!(
+            error("int g() { return; }"),
+            mismatch(Types::Int, Types::Void, at(11, 7))
+        );
+    }
+
+    #[test]
+    fn should_error_void_value() {
+        assert_eq!(
+            error("void f() {} int main() { f(); int x = f(); }"),
+            mismatch(Types::Int, Types::Void, at(39, 3))
+        );
+    }
+
+    #[test]
+    fn should_error_void_condition_and_argument() {
+        assert_eq!(
+            error("void f() {} int main() { while (f()) {} }"),
+            mismatch(Types::Bool, Types::Void, at(33, 3))
+        );
+        assert_eq!(
+            error("void f() {} int g(int x) { return g(f()); }"),
+            mismatch(Types::Int, Types::Void, at(37, 3))
+        );
+    }
+
+    #[test]
+    fn should_error_invalid_operand() {
+        assert_eq!(
+            error("void f() {} int main() { return !f(); }"),
+            TypeError::InvalidOperand {
+                op: "!".to_string(),
+                operand: Types::Void,
+                span: at(34, 3),
+            }
+        );
+        assert_eq!(
+            error("int main() { return 5 % 2.0; }"),
+            TypeError::InvalidOperand {
+                op: "%".to_string(),
+                operand: Types::Double,
+                span: at(25, 3),
+            }
+        );
+    }
+
+    #[test]
+    fn should_scope_variable_types() {
+        assert_eq!(
+            error("void f() {} void main(int x) { if (1) { double x = 1.0; } x = f(); }"),
+            mismatch(Types::Int, Types::Void, at(63, 3))
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use std::fmt::Display;
+
+/// A representation of a position in an Oxygen source file.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct Position {
+    /// Contains the line number, starting at 1.
+    pub line: usize,
+    /// Contains the column number, starting at 1.
+    pub column: usize,
+    /// Contains the byte offset from the start of the file, starting at 0.
+    pub offset: usize,
+}
+
+impl Position {
+    /// Creates a new [`Position`].
+    pub fn new(line: usize, column: usize, offset: usize) -> Self {
+        Self {
+            line,
+            column,
+            offset,
+        }
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// A representation of a region of an Oxygen source file.
+///
+/// The `end` is exclusive so an empty span has `start == end`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct Span {
+    /// Contains the position of the first character in the span.
+    pub start: Position,
+    /// Contains the position just past the last character in the span.
+    pub end: Position,
+}
+
+impl Span {
+    /// Creates a new [`Span`].
+    pub fn new(start: Position, end: Position) -> Self {
+        Self { start, end }
+    }
+
+    /// Creates a new [`Span`] covering `length` single byte characters of a
+    /// single line from the start.
+    pub fn on_line(start: Position, length: usize) -> Self {
+        Self {
+            start,
+            end: Position::new(start.line, start.column + length, start.offset + length),
+        }
+    }
+
+    /// Creates a new [`Span`] from the start of this span to the end of the
+    /// other.
+    pub fn to(&self, other: &Span) -> Self {
+        Self {
+            start: self.start,
+            end: other.end,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_create_span_on_line() {
+        assert_eq!(
+            Span::on_line(Position::new(2, 5, 20), 3),
+            Span::new(Position::new(2, 5, 20), Position::new(2, 8, 23))
+        );
+    }
+
+    #[test]
+    fn should_join_spans() {
+        let start = Span::on_line(Position::new(1, 1, 0), 3);
+        let end = Span::on_line(Position::new(2, 4, 10), 1);
+
+        assert_eq!(
+            start.to(&end),
+            Span::new(Position::new(1, 1, 0), Position::new(2, 5, 11))
+        );
+    }
+
+    #[test]
+    fn should_display_position() {
+        assert_eq!(Position::new(2, 12, 24).to_string(), "2:12");
+    }
+}